@@ -1,6 +1,6 @@
-use crate::types::{address_to_hex, b256_to_hex, format_hex, u256_to_string};
+use crate::types::{address_to_hex, b256_to_hex, bytes_from_hex, format_hex, u256_to_string};
 use crate::types::{BundleAttributesView, InteropBundle, InteropBundleView as BundleView};
-use crate::types::{InteropCallView, MessageInclusionProof};
+use crate::types::{InteropCallView, MessageInclusionProof, BUNDLE_IDENTIFIER};
 use alloy_primitives::ruint::aliases::U256;
 use alloy_primitives::{keccak256, Address, Bytes, B256, U256 as AlloyU256};
 use alloy_sol_types::{SolCall, SolError, SolValue};
@@ -135,6 +135,15 @@ pub fn decode_interop_bundle_sent(data: Bytes) -> Result<(B256, B256, InteropBun
     ))
 }
 
+/// Decode the `bytes _message` of an L1MessageSent log.
+///
+/// The L1 messenger indexes the message hash as `keccak256(_message)`; for a
+/// bundle the message is `0x01 ++ abi.encode(bundle)`.
+pub fn decode_l1_message_sent(data: &[u8]) -> Result<Bytes> {
+    let (message,) = <(Bytes,)>::abi_decode_params(data)?;
+    Ok(message)
+}
+
 pub fn decode_message_sent(data: Bytes) -> Result<MessageSentData> {
     Ok(MessageSentData::abi_decode_params(&data)?)
 }
@@ -146,84 +155,240 @@ pub fn decode_u8(data: Bytes) -> Result<u8> {
     Ok(v)
 }
 
+/// Every interop error declared above as (selector, signature), e.g.
+/// `WrongSourceChainId(bytes32,uint256,uint256)`.
+pub fn known_errors() -> Vec<([u8; 4], &'static str)> {
+    vec![
+        (
+            AttributeAlreadySet::SELECTOR,
+            AttributeAlreadySet::SIGNATURE,
+        ),
+        (
+            AttributeViolatesRestriction::SELECTOR,
+            AttributeViolatesRestriction::SIGNATURE,
+        ),
+        (
+            BundleAlreadyProcessed::SELECTOR,
+            BundleAlreadyProcessed::SIGNATURE,
+        ),
+        (
+            BundleVerifiedAlready::SELECTOR,
+            BundleVerifiedAlready::SIGNATURE,
+        ),
+        (
+            CallAlreadyExecuted::SELECTOR,
+            CallAlreadyExecuted::SIGNATURE,
+        ),
+        (CallNotExecutable::SELECTOR, CallNotExecutable::SIGNATURE),
+        (CanNotUnbundle::SELECTOR, CanNotUnbundle::SIGNATURE),
+        (
+            ExecutingNotAllowed::SELECTOR,
+            ExecutingNotAllowed::SIGNATURE,
+        ),
+        (
+            IndirectCallValueMismatch::SELECTOR,
+            IndirectCallValueMismatch::SIGNATURE,
+        ),
+        (
+            InteroperableAddressChainReferenceNotEmpty::SELECTOR,
+            InteroperableAddressChainReferenceNotEmpty::SIGNATURE,
+        ),
+        (
+            InteroperableAddressNotEmpty::SELECTOR,
+            InteroperableAddressNotEmpty::SIGNATURE,
+        ),
+        (
+            InvalidInteropBundleVersion::SELECTOR,
+            InvalidInteropBundleVersion::SIGNATURE,
+        ),
+        (
+            InvalidInteropCallVersion::SELECTOR,
+            InvalidInteropCallVersion::SIGNATURE,
+        ),
+        (MessageNotIncluded::SELECTOR, MessageNotIncluded::SIGNATURE),
+        (
+            UnauthorizedMessageSender::SELECTOR,
+            UnauthorizedMessageSender::SIGNATURE,
+        ),
+        (
+            UnbundlingNotAllowed::SELECTOR,
+            UnbundlingNotAllowed::SIGNATURE,
+        ),
+        (
+            WrongCallStatusLength::SELECTOR,
+            WrongCallStatusLength::SIGNATURE,
+        ),
+        (
+            WrongDestinationChainId::SELECTOR,
+            WrongDestinationChainId::SIGNATURE,
+        ),
+        (WrongSourceChainId::SELECTOR, WrongSourceChainId::SIGNATURE),
+    ]
+}
+
 // Create a map from every error selector to its name
 pub fn error_selector_map() -> HashMap<String, &'static str> {
-    let mut map = HashMap::new();
-    map.insert(
-        hex::encode(AttributeAlreadySet::SELECTOR),
-        "AttributeAlreadySet",
-    );
-    map.insert(
-        hex::encode(AttributeViolatesRestriction::SELECTOR),
-        "AttributeViolatesRestriction",
-    );
-    map.insert(
-        hex::encode(BundleAlreadyProcessed::SELECTOR),
-        "BundleAlreadyProcessed",
-    );
-
-    map.insert(
-        hex::encode(BundleVerifiedAlready::SELECTOR),
-        "BundleVerifiedAlready",
-    );
-    map.insert(
-        hex::encode(CallAlreadyExecuted::SELECTOR),
-        "CallAlreadyExecuted",
-    );
-    map.insert(
-        hex::encode(CallNotExecutable::SELECTOR),
-        "CallNotExecutable",
-    );
-    map.insert(hex::encode(CanNotUnbundle::SELECTOR), "CanNotUnbundle");
-    map.insert(
-        hex::encode(ExecutingNotAllowed::SELECTOR),
-        "ExecutingNotAllowed",
-    );
-    map.insert(
-        hex::encode(IndirectCallValueMismatch::SELECTOR),
-        "IndirectCallValueMismatch",
-    );
-    map.insert(
-        hex::encode(InteroperableAddressChainReferenceNotEmpty::SELECTOR),
-        "InteroperableAddressChainReferenceNotEmpty",
-    );
-    map.insert(
-        hex::encode(InteroperableAddressNotEmpty::SELECTOR),
-        "InteroperableAddressNotEmpty",
-    );
-    map.insert(
-        hex::encode(InvalidInteropBundleVersion::SELECTOR),
-        "InvalidInteropBundleVersion",
-    );
-    map.insert(
-        hex::encode(InvalidInteropCallVersion::SELECTOR),
-        "InvalidInteropCallVersion",
-    );
-    map.insert(
-        hex::encode(MessageNotIncluded::SELECTOR),
-        "MessageNotIncluded",
-    );
-    map.insert(
-        hex::encode(UnauthorizedMessageSender::SELECTOR),
-        "UnauthorizedMessageSender",
-    );
-    map.insert(
-        hex::encode(UnbundlingNotAllowed::SELECTOR),
-        "UnbundlingNotAllowed",
-    );
-    map.insert(
-        hex::encode(WrongCallStatusLength::SELECTOR),
-        "WrongCallStatusLength",
-    );
-    map.insert(
-        hex::encode(WrongDestinationChainId::SELECTOR),
-        "WrongDestinationChainId",
-    );
-    map.insert(
-        hex::encode(WrongSourceChainId::SELECTOR),
-        "WrongSourceChainId",
-    );
-    map
+    known_errors()
+        .into_iter()
+        .map(|(selector, signature)| {
+            let name = signature.split('(').next().unwrap_or(signature);
+            (hex::encode(selector), name)
+        })
+        .collect()
+}
+
+/// Decode a known interop error and format it with its fields,
+/// e.g. `WrongDestinationChainId(bundleHash=0x.., expected=324, actual=271)`.
+///
+/// `data` is the full revert data including the 4-byte selector. Returns None
+/// for unknown selectors or data that does not decode as the matching error.
+pub fn decode_error_with_params(data: &[u8]) -> Option<String> {
+    let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    let data = &data[4..];
+    match selector {
+        AttributeAlreadySet::SELECTOR => format_error(data, |e: AttributeAlreadySet| {
+            format!("selector={:#x}", e.selector)
+        }),
+        AttributeViolatesRestriction::SELECTOR => {
+            format_error(data, |e: AttributeViolatesRestriction| {
+                format!("selector={:#x}, restriction={}", e.selector, e.restriction)
+            })
+        }
+        BundleAlreadyProcessed::SELECTOR => format_error(data, |e: BundleAlreadyProcessed| {
+            format!("bundleHash={:#x}", e.bundleHash)
+        }),
+        BundleVerifiedAlready::SELECTOR => format_error(data, |e: BundleVerifiedAlready| {
+            format!("bundleHash={:#x}", e.bundleHash)
+        }),
+        CallAlreadyExecuted::SELECTOR => format_error(data, |e: CallAlreadyExecuted| {
+            format!("bundleHash={:#x}, callIndex={}", e.bundleHash, e.callIndex)
+        }),
+        CallNotExecutable::SELECTOR => format_error(data, |e: CallNotExecutable| {
+            format!("bundleHash={:#x}, callIndex={}", e.bundleHash, e.callIndex)
+        }),
+        CanNotUnbundle::SELECTOR => format_error(data, |e: CanNotUnbundle| {
+            format!("bundleHash={:#x}", e.bundleHash)
+        }),
+        ExecutingNotAllowed::SELECTOR => format_error(data, |e: ExecutingNotAllowed| {
+            format!(
+                "bundleHash={:#x}, callerAddress={}, executionAddress={}",
+                e.bundleHash, e.callerAddress, e.executionAddress
+            )
+        }),
+        IndirectCallValueMismatch::SELECTOR => {
+            format_error(data, |e: IndirectCallValueMismatch| {
+                format!("expected={}, actual={}", e.expected, e.actual)
+            })
+        }
+        InteroperableAddressChainReferenceNotEmpty::SELECTOR => {
+            format_error(data, |e: InteroperableAddressChainReferenceNotEmpty| {
+                format!("interoperableAddress={}", e.interoperableAddress)
+            })
+        }
+        InteroperableAddressNotEmpty::SELECTOR => {
+            format_error(data, |e: InteroperableAddressNotEmpty| {
+                format!("interoperableAddress={}", e.interoperableAddress)
+            })
+        }
+        InvalidInteropBundleVersion::SELECTOR => {
+            format_error(data, |_: InvalidInteropBundleVersion| String::new())
+        }
+        InvalidInteropCallVersion::SELECTOR => {
+            format_error(data, |_: InvalidInteropCallVersion| String::new())
+        }
+        MessageNotIncluded::SELECTOR => format_error(data, |_: MessageNotIncluded| String::new()),
+        UnauthorizedMessageSender::SELECTOR => {
+            format_error(data, |e: UnauthorizedMessageSender| {
+                format!("expected={:#x}, actual={:#x}", e.expected, e.actual)
+            })
+        }
+        UnbundlingNotAllowed::SELECTOR => format_error(data, |e: UnbundlingNotAllowed| {
+            format!(
+                "bundleHash={:#x}, callerAddress={}, unbundlerAddress={}",
+                e.bundleHash, e.callerAddress, e.unbundlerAddress
+            )
+        }),
+        WrongCallStatusLength::SELECTOR => format_error(data, |e: WrongCallStatusLength| {
+            format!(
+                "bundleCallsLength={}, providedCallStatusLength={}",
+                e.bundleCallsLength, e.providedCallStatusLength
+            )
+        }),
+        WrongDestinationChainId::SELECTOR => format_error(data, |e: WrongDestinationChainId| {
+            format!(
+                "bundleHash={:#x}, expected={}, actual={}",
+                e.bundleHash, e.expected, e.actual
+            )
+        }),
+        WrongSourceChainId::SELECTOR => format_error(data, |e: WrongSourceChainId| {
+            format!(
+                "bundleHash={:#x}, expected={}, actual={}",
+                e.bundleHash, e.expected, e.actual
+            )
+        }),
+        _ => None,
+    }
+}
+
+/// Decode the error body and render it as `Name(fields)`.
+fn format_error<E: SolError>(data: &[u8], fields: impl FnOnce(E) -> String) -> Option<String> {
+    let error = E::abi_decode_raw(data).ok()?;
+    let name = E::SIGNATURE.split('(').next().unwrap_or(E::SIGNATURE);
+    Some(format!("{name}({})", fields(error)))
+}
+
+/// A revert decoded from its data.
+pub struct DecodedRevert {
+    pub selector: [u8; 4],
+    /// Solidity error name, e.g. `Error`, `Panic` or `WrongSourceChainId`.
+    pub name: String,
+    /// e.g. the revert string, `panic(17)` or `revert: Name(fields)`.
+    pub reason: String,
+}
+
+/// Extract the revert data from a node error message: the first `0x` hex run.
+pub fn revert_data_from_message(message: &str) -> Option<Bytes> {
+    let hex_start = message.find("0x")?;
+    let hex_data = &message[hex_start..];
+    let hex_end = hex_data.find('"').unwrap_or(hex_data.len());
+    bytes_from_hex(&hex_data[..hex_end]).ok()
+}
+
+/// Decode revert data as `Error(string)`, `Panic(uint256)` or a known
+/// interop error. Returns None for short data and unknown selectors.
+pub fn decode_revert(data: &[u8]) -> Option<DecodedRevert> {
+    if data.len() < 4 {
+        println!("revert data too short, len={}", data.len());
+        return None;
+    }
+    let selector: [u8; 4] = data[..4].try_into().ok()?;
+    let decoded = |name: &str, reason: String| {
+        Some(DecodedRevert {
+            selector,
+            name: name.to_string(),
+            reason,
+        })
+    };
+    if selector == [0x08, 0xc3, 0x79, 0xa0] {
+        if let Ok((reason,)) = <(String,)>::abi_decode_params(&data[4..]) {
+            return decoded("Error", reason);
+        }
+    } else if selector == [0x4e, 0x48, 0x7b, 0x71] {
+        let code = U256::from_be_slice(&data[4..]);
+        return decoded("Panic", format!("panic({code})"));
+    }
+    if let Some(error) = decode_error_with_params(data) {
+        let name = error.split('(').next().unwrap_or_default().to_string();
+        return decoded(&name, format!("revert: {error}"));
+    }
+    let selector_hex = hex::encode(selector);
+    match error_selector_map().get(&selector_hex) {
+        Some(name) => decoded(name, format!("revert: {name}")),
+        None => {
+            println!("unknown revert selector 0x{selector_hex}");
+            None
+        }
+    }
 }
 
 pub fn bundle_view(bundle: &InteropBundle) -> BundleView {
@@ -251,6 +416,43 @@ pub fn bundle_view(bundle: &InteropBundle) -> BundleView {
     }
 }
 
+/// Hash a bundle the way the handler keys it:
+/// keccak256(abi.encode(sourceChainId, encodedBundle)).
+pub fn interop_bundle_hash(source_chain_id: AlloyU256, encoded_bundle: &[u8]) -> B256 {
+    keccak256((source_chain_id, Bytes::copy_from_slice(encoded_bundle)).abi_encode_params())
+}
+
+/// Decode an ABI-encoded bundle (the `bundle.hex` format), rejecting
+/// non-canonical encodings so its hash matches the one the handler computes.
+pub fn decode_bundle(bytes: &[u8]) -> Result<InteropBundle> {
+    Ok(InteropBundle::abi_decode_validate(bytes)?)
+}
+
+/// Like [`decode_bundle`], but also reject bytes left over after the bundle,
+/// and name a leading `0x01` bundle identifier that was not stripped.
+pub fn decode_bundle_strict(bytes: &[u8]) -> Result<InteropBundle> {
+    let bundle = match decode_bundle(bytes) {
+        Ok(bundle) => bundle,
+        Err(err) => {
+            if bytes.first() == Some(&BUNDLE_IDENTIFIER) && decode_bundle(&bytes[1..]).is_ok() {
+                return Err(anyhow!(
+                    "bundle starts with the 0x01 bundle identifier; drop the first byte"
+                ));
+            }
+            return Err(err);
+        }
+    };
+    let consumed = encode_interop_bundle(&bundle).len();
+    if bytes.len() > consumed {
+        return Err(anyhow!(
+            "{} trailing bytes after the bundle ({consumed} of {} bytes decoded); check for joined or mis-pasted hex",
+            bytes.len() - consumed,
+            bytes.len()
+        ));
+    }
+    Ok(bundle)
+}
+
 pub fn encode_interop_bundle(bundle: &InteropBundle) -> Bytes {
     let encoded = bundle.abi_encode();
     Bytes::from(encoded)
@@ -357,17 +559,73 @@ fn proof_to_sol(proof: MessageInclusionProof) -> Result<MessageInclusionProofSol
 
 pub fn decode_bundle_status(data: Bytes) -> Result<u8> {
     let value: (U256,) = <(U256,)>::abi_decode(&data)?;
-    let tmp: u64 = value.0.try_into().unwrap();
-    Ok(tmp as u8)
+    u8::try_from(value.0).map_err(|_| anyhow!("unexpected bundle status value {}", value.0))
 }
 
 pub fn decode_call_status(data: Bytes) -> Result<u8> {
     let value: (U256,) = <(U256,)>::abi_decode(&data)?;
-    let tmp: u64 = value.0.try_into().unwrap();
-    Ok(tmp as u8)
+    u8::try_from(value.0).map_err(|_| anyhow!("unexpected call status value {}", value.0))
 }
 
 pub fn decode_bytes32(data: Bytes) -> Result<B256> {
     let value: (B256,) = <(B256,)>::abi_decode(&data)?;
     Ok(value.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    /// Data of an `InteropBundleSent` log for a single greeting call from
+    /// chain 271 to chain 260, laid out as `InteropCenter._sendBundle` emits it.
+    const INTEROP_BUNDLE_SENT_DATA: &[&str] = &[
+        "5d03b786e712628ac8690693f446b42f097d7596943d5598ea9130f0f7a474e5",
+        "2727cc465f4bf69b5078f9a5d9bdc7f38c69647d19223c52ee60385a20d8ea0e",
+        "0000000000000000000000000000000000000000000000000000000000000060",
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "000000000000000000000000000000000000000000000000000000000000010f",
+        "0000000000000000000000000000000000000000000000000000000000000104",
+        "0e8fe5a647edf1c0117b533eee1679d521af836f5c7a35f378a4227a001a03cb",
+        "00000000000000000000000000000000000000000000000000000000000000c0",
+        "0000000000000000000000000000000000000000000000000000000000000260",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000005e1b6d4fa3a0d8cafb4bb0e4f4e7bc7e7e60f0b1",
+        "00000000000000000000000036615cf349d7f6344891b1e7ca7c72883f5dc049",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000000000000000000000000000000000c0",
+        "0000000000000000000000000000000000000000000000000000000000000064",
+        "a413686200000000000000000000000000000000000000000000000000000000",
+        "0000002000000000000000000000000000000000000000000000000000000000",
+        "0000000c68656c6c6f2046726f6d204100000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000040",
+        "0000000000000000000000000000000000000000000000000000000000000060",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "000000000000000000000000000000000000000000000000000000000000001c",
+        "0001000002010f1436615cf349d7f6344891b1e7ca7c72883f5dc04900000000",
+    ];
+
+    #[test]
+    fn interop_bundle_hash_matches_interop_bundle_sent() {
+        let data = bytes_from_hex(&INTEROP_BUNDLE_SENT_DATA.concat()).unwrap();
+        let (msg_hash, bundle_hash, bundle) = decode_interop_bundle_sent(data).unwrap();
+        assert_eq!(
+            bundle_hash,
+            b256!("2727cc465f4bf69b5078f9a5d9bdc7f38c69647d19223c52ee60385a20d8ea0e")
+        );
+
+        let encoded = bundle.abi_encode();
+        assert_eq!(
+            interop_bundle_hash(bundle.sourceChainId, &encoded),
+            bundle_hash
+        );
+
+        let mut message = vec![BUNDLE_IDENTIFIER];
+        message.extend_from_slice(&encoded);
+        assert_eq!(keccak256(&message), msg_hash);
+    }
+}
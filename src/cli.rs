@@ -1,9 +1,14 @@
 use crate::commands;
-use crate::config::Config;
-use crate::types::AddressBook;
-use anyhow::Result;
-use clap::{Args, Parser, Subcommand};
+use crate::config::{parse_bundle_identifier, Config, OutputFormat, ResolvedRpc};
+use crate::types::{AddressBook, ChecksFailed, DEFAULT_RPC_TIMEOUT_MS};
+use anyhow::{Context, Result};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::ffi::OsString;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 /// Entry point for the cast-interop CLI.
 ///
@@ -25,6 +30,30 @@ pub struct Cli {
     )]
     pub config_path: Option<PathBuf>,
 
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Config profile to use ([profiles.<NAME>] overrides chains, addresses, and signer). Default: unset (top-level config)."
+    )]
+    pub profile: Option<String>,
+
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Only print results (or JSON): no preflight summaries, progress logs, or debug hints. Warnings and errors still go to stderr. Default: false."
+    )]
+    pub quiet: bool,
+
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Send value-bearing transactions (token send/send-batch, send message/bundle) without asking for confirmation. Required when stdin is not a terminal. Default: false."
+    )]
+    pub yes: bool,
+
     #[arg(
         long,
         global = true,
@@ -49,13 +78,106 @@ pub struct Cli {
     )]
     pub root_storage: Option<String>,
 
+    #[arg(
+        long,
+        global = true,
+        value_name = "MS",
+        default_value_t = DEFAULT_RPC_TIMEOUT_MS,
+        help = "Timeout for each RPC request in milliseconds. Default: 30000."
+    )]
+    pub rpc_timeout_ms: u64,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "HEADER",
+        help = "Extra HTTP header for every RPC request, as \"Name: Value\" (e.g. an Authorization or API-key header). Repeatable. Default: none."
+    )]
+    pub rpc_header: Vec<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Log every JSON-RPC request and response (method, params, result or error) to stderr. The signer's private key is masked and only the RPC host is shown. Default: false."
+    )]
+    pub trace: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Write the --trace records to PATH as JSON lines instead of stderr; implies --trace. Default: unset."
+    )]
+    pub trace_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "HEX_BYTE",
+        help = "Bundle identifier byte prepended to an encoded bundle in its L2→L1 message, for protocol versions that change it. Default: config interop.bundle_identifier, else 0x01."
+    )]
+    pub bundle_prefix: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Simulate every transaction a command would send with eth_call instead, reporting decoded reverts (token send/register/send-batch, send message/bundle, bundle verify/execute/relay/relay-batch). Default: false."
+    )]
+    pub dry_run: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (text or json). Default: config `output`, else text."
+    )]
+    pub output: Option<OutputFormat>,
+
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "output",
+        help = "Deprecated alias for --output json."
+    )]
+    pub json: bool,
+
+    #[arg(
+        long,
+        global = true,
+        alias = "no-color",
+        help = "Print [ok]/[warn]/[fail] instead of emoji icons in check reports (doctor, explain). Default: only when stdout is not a terminal."
+    )]
+    pub ascii: bool,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
 impl Cli {
     /// Dispatch the selected command.
-    pub async fn run(self, config: Config) -> Result<()> {
+    ///
+    /// Failing `debug doctor`/`bundle explain` checks become a non-zero exit
+    /// code rather than an error, since their report is already printed.
+    pub async fn run(self, mut config: Config) -> Result<ExitCode> {
+        config.rpc_timeout = std::time::Duration::from_millis(self.rpc_timeout_ms);
+        config.rpc_headers = crate::rpc::parse_rpc_headers(&self.rpc_header)?;
+        if self.trace || self.trace_file.is_some() {
+            crate::rpc_trace::init(self.trace_file.as_deref())?;
+        }
+        config.dry_run = self.dry_run;
+        config.plain_icons = self.ascii || !std::io::stdout().is_terminal();
+        if let Some(prefix) = self.bundle_prefix.as_deref() {
+            config.bundle_identifier =
+                parse_bundle_identifier(prefix).context("invalid --bundle-prefix")?;
+        }
+        if self.json {
+            config.output = Some(OutputFormat::Json);
+        } else if self.output.is_some() {
+            config.output = self.output;
+        }
+        config.quiet = self.quiet;
+        config.yes = self.yes;
         let addresses = AddressBook::from_config_and_flags(
             &config,
             self.center.as_deref(),
@@ -63,14 +185,83 @@ impl Cli {
             self.root_storage.as_deref(),
         )?;
 
-        match self.command {
+        let result = match self.command {
             Command::Token(cmd) => cmd.run(config, addresses).await,
             Command::Bundle(cmd) => cmd.run(config, addresses).await,
             Command::Send(cmd) => cmd.run(config, addresses).await,
             Command::Debug(cmd) => cmd.run(config, addresses).await,
             Command::Encode(cmd) => cmd.run(config, addresses).await,
             Command::Chains(cmd) => cmd.run(config, addresses).await,
+        };
+        match result {
+            Ok(()) => Ok(ExitCode::SUCCESS),
+            Err(err) => match err.downcast_ref::<ChecksFailed>() {
+                Some(failed) => {
+                    eprintln!("error: {failed}");
+                    Ok(ExitCode::from(failed.exit_code()))
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    /// Parse `argv`, rejecting per-command flags declared with
+    /// `conflicts_with = "dry_run"` whichever side of the subcommand
+    /// `--dry-run` is given on.
+    pub fn try_parse_checked_from<I, T>(argv: I) -> std::result::Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(argv)?;
+        check_dry_run_conflicts(&mut command, &matches)?;
+        Self::from_arg_matches(&matches)
+    }
+
+    /// [`Cli::try_parse_checked_from`] on the process arguments, exiting
+    /// with clap's usage error on failure.
+    pub fn parse_checked() -> Self {
+        Self::try_parse_checked_from(std::env::args_os()).unwrap_or_else(|err| err.exit())
+    }
+}
+
+/// Enforce the `conflicts_with = "dry_run"` declarations of the selected
+/// subcommand.
+///
+/// clap only checks them when `--dry-run` follows the subcommand; given
+/// before it, the global flag is propagated after validation.
+fn check_dry_run_conflicts(
+    command: &mut clap::Command,
+    matches: &ArgMatches,
+) -> std::result::Result<(), clap::Error> {
+    if !matches.get_flag("dry_run") {
+        return Ok(());
+    }
+    let (mut command, mut matches) = (command, matches);
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        command = command
+            .find_subcommand_mut(name)
+            .expect("matched subcommand is defined");
+        matches = sub_matches;
+    }
+    command.build();
+    let conflict = command.get_arguments().find(|arg| {
+        matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+            && command
+                .get_arg_conflicts_with(arg)
+                .iter()
+                .any(|other| other.get_id() == "dry_run")
+    });
+    match conflict {
+        Some(arg) => {
+            let flag = arg.get_long().unwrap_or(arg.get_id().as_str()).to_string();
+            Err(command.error(
+                ErrorKind::ArgumentConflict,
+                format!("the argument '--{flag}' cannot be used with '--dry-run'"),
+            ))
         }
+        None => Ok(()),
     }
 }
 
@@ -128,11 +319,31 @@ pub enum DebugSubcommand {
         long_about = "Fetch the transaction receipt and decode interop-related events.\nUse this to confirm bundle hashes and event data.\nExample: cast-interop debug tx --chain era 0xTX_HASH"
     )]
     Tx(TxShowArgs),
+    #[command(
+        about = "Decode interop events from a block range.",
+        long_about = "Query eth_getLogs for interop event topics and decode each matching log.\nUse this to find a lost source transaction or bundle hash.\nExample: cast-interop debug logs --chain era --from-block 1000 --to-block 1100"
+    )]
+    Logs(LogsArgs),
+    #[command(
+        about = "List the L2→L1 messages sent by a transaction.",
+        long_about = "Enumerate the L1MessageSent logs in the receipt with their message index, sender, and hash.\nUse this to pick --msg-index when a transaction sends more than one message.\nExample: cast-interop debug messages --chain era --tx 0xTX_HASH"
+    )]
+    Messages(MessagesArgs),
     #[command(
         about = "Fetch a log proof for an interop transaction.",
         long_about = "Wait for finalization and fetch the L2→L1 log proof (getLogProof).\nUse this when you need a proof for bundle verify/execute.\nExample: cast-interop debug proof --chain era --tx 0xTX_HASH"
     )]
     Proof(ProofArgs),
+    #[command(
+        about = "Check a proof against its root offline.",
+        long_about = "Recompute the Merkle root from the proof's message and nodes and compare it with the proof's root, without an RPC.\nUse this to catch a malformed proof before spending a verify/execute transaction.\nExample: cast-interop debug proof-verify --proof proof.json --bundle bundle.hex"
+    )]
+    ProofVerify(ProofVerifyArgs),
+    #[command(
+        about = "List and decode known interop error selectors.",
+        long_about = "Print every known 4-byte error selector with its signature, or look up one selector and decode its parameters.\nUse this to identify a bare revert selector reported by another tool.\nExample: cast-interop debug errors --selector 0x534ab1b2"
+    )]
+    Errors(ErrorsArgs),
     #[command(
         about = "Wait for an interop root on the destination chain.",
         long_about = "Poll interopRoots(chainId, batchNumber) until the expected root appears.\nUse this after the source log proof is available.\nExample: cast-interop debug root --chain test --source-chain 324 --batch 123 --expected-root 0xROOT"
@@ -140,7 +351,7 @@ pub enum DebugSubcommand {
     Root(RootWaitArgs),
     #[command(
         about = "Check RPC feature support.",
-        long_about = "Ping the RPC to detect zkSync-specific methods and finalized blocks.\nUse this to validate an RPC before running other commands.\nExample: cast-interop debug rpc --chain era"
+        long_about = "Ping the RPC to detect zkSync-specific methods and finalized blocks, or with --watch keep probing its latency.\nUse this to validate an RPC before running other commands, or as a lightweight endpoint monitor.\nExample: cast-interop debug rpc --chain era"
     )]
     Rpc(RpcPingArgs),
     #[command(
@@ -150,7 +361,7 @@ pub enum DebugSubcommand {
     Contracts(ContractsArgs),
     #[command(
         about = "Run a diagnostics checklist.",
-        long_about = "Validate RPC connectivity, ABI availability, and address configuration.\nUse this before debugging a failing relay.\nExample: cast-interop debug doctor --chain era"
+        long_about = "Validate RPC connectivity, ABI availability, address configuration, and signer gas balance.\nUse this before debugging a failing relay.\nExample: cast-interop debug doctor --chain era"
     )]
     Doctor(DoctorArgs),
     #[command(
@@ -165,7 +376,15 @@ impl DebugCommand {
     pub async fn run(self, config: Config, addresses: AddressBook) -> Result<()> {
         match self.command {
             DebugSubcommand::Tx(args) => commands::tx_show::run(args, config, addresses).await,
+            DebugSubcommand::Logs(args) => commands::logs::run(args, config, addresses).await,
+            DebugSubcommand::Messages(args) => {
+                commands::messages::run(args, config, addresses).await
+            }
             DebugSubcommand::Proof(args) => commands::proof::run(args, config, addresses).await,
+            DebugSubcommand::ProofVerify(args) => {
+                commands::proof_verify::run(args, config, addresses)
+            }
+            DebugSubcommand::Errors(args) => commands::errors::run(args, config),
             DebugSubcommand::Root(args) => commands::root_wait::run(args, config, addresses).await,
             DebugSubcommand::Rpc(args) => commands::rpc_ping::run(args, config, addresses).await,
             DebugSubcommand::Contracts(args) => {
@@ -196,6 +415,21 @@ pub enum BundleSubcommand {
         long_about = "Locate the InteropBundleSent event and write the encoded bundle.\nUse this to prepare manual verify/execute steps.\nExample: cast-interop bundle extract --chain era --tx 0xTX_HASH --out bundle.hex"
     )]
     Extract(BundleExtractArgs),
+    #[command(
+        about = "Decode an encoded interop bundle.",
+        long_about = "Decode raw bundle hex (inline or from a file) into its calls and attributes, offline.\nUse this when you only have the encoded bundle and no transaction or RPC.\nExample: cast-interop bundle decode --bundle bundle.hex"
+    )]
+    Decode(BundleDecodeArgs),
+    #[command(
+        about = "Compute the hash of an encoded interop bundle.",
+        long_about = "Hash an encoded bundle offline as keccak256(abi.encode(sourceChainId, encodedBundle)), the interopBundleHash emitted in InteropBundleSent.\nUse this to get the key for bundle status or to check a bundle file against an event.\nExample: cast-interop bundle hash --bundle bundle.hex"
+    )]
+    Hash(BundleHashArgs),
+    #[command(
+        about = "Compare two encoded interop bundles field by field.",
+        long_about = "Decode two bundles offline and list every field that differs: version, chain IDs, salt, attributes, and each call.\nUse this to find out why a re-encoded bundle hashes differently. Exits non-zero when the bundles differ.\nExample: cast-interop bundle diff --a bundle.hex --b reencoded.hex"
+    )]
+    Diff(BundleDiffArgs),
     #[command(
         about = "Verify a bundle on the destination chain.",
         long_about = "Submit a bundle proof to mark it verified on the handler contract.\nUse this before executing a bundle.\nExample: cast-interop bundle verify --chain test --bundle bundle.hex --proof proof.json --private-key $PRIVATE_KEY"
@@ -218,9 +452,14 @@ pub enum BundleSubcommand {
     Explain(ExplainArgs),
     #[command(
         about = "Relay a bundle end-to-end.",
-        long_about = "Fetch proof from source, wait for root, and verify/execute on destination.\nUse this to automate the full relay flow.\nExample: cast-interop bundle relay --chain-src era --chain-dest test --tx 0xTX_HASH --mode execute --private-key $PRIVATE_KEY"
+        long_about = "Fetch proof from source, wait for root, and verify/execute on destination.\nUse this to automate the full relay flow, for `send bundle` and `send message` transactions alike (a message is sent as a single-call bundle).\nExample: cast-interop bundle relay --chain-src era --chain-dest test --tx 0xTX_HASH --mode execute --private-key $PRIVATE_KEY"
     )]
     Relay(RelayArgs),
+    #[command(
+        about = "Relay bundles from many source transactions.",
+        long_about = "Fetch proofs for every transaction in a file concurrently, then verify/execute each bundle on destination with sequential nonces.\nUse this to drain a backlog of pending bundles.\nExample: cast-interop bundle relay-batch --chain-src era --chain-dest test --tx-file txs.txt --concurrency 8 --private-key $PRIVATE_KEY"
+    )]
+    RelayBatch(RelayBatchArgs),
 }
 
 impl BundleCommand {
@@ -230,6 +469,9 @@ impl BundleCommand {
             BundleSubcommand::Extract(args) => {
                 commands::bundle_extract::run(args, config, addresses).await
             }
+            BundleSubcommand::Decode(args) => commands::bundle_decode::run(args, config),
+            BundleSubcommand::Hash(args) => commands::bundle_hash::run(args, config),
+            BundleSubcommand::Diff(args) => commands::bundle_diff::run(args, config),
             BundleSubcommand::Verify(args) => {
                 commands::bundle_action::run_verify(args, config, addresses).await
             }
@@ -241,6 +483,9 @@ impl BundleCommand {
                 commands::explain::run(args, config, addresses).await
             }
             BundleSubcommand::Relay(args) => commands::relay::run(args, config, addresses).await,
+            BundleSubcommand::RelayBatch(args) => {
+                commands::relay_batch::run(args, config, addresses).await
+            }
         }
     }
 }
@@ -261,7 +506,7 @@ pub struct SendCommand {
 pub enum SendSubcommand {
     #[command(
         about = "Send a single interop message.",
-        long_about = "Build and send a single interop message to a destination chain.\nUse this for one-off calls or pings.\nExample: cast-interop send message --chain era --to-chain test --to 0xTARGET --payload 0xdeadbeef --private-key $PRIVATE_KEY"
+        long_about = "Build and send a single interop message to a destination chain.\nUse this for one-off calls or pings; omit the payload to send value only with --interop-value or --indirect.\nExample: cast-interop send message --chain era --to-chain test --to 0xTARGET --payload 0xdeadbeef --private-key $PRIVATE_KEY"
     )]
     Message(SendMessageArgs),
     #[command(
@@ -304,16 +549,43 @@ pub enum TokenSubcommand {
         long_about = "Resolve the wrapped token address and metadata on the destination chain.\nUse this before sending to confirm the wrapped token exists.\nExample: cast-interop token info --chain-src era --chain-dest test --token 0xTOKEN"
     )]
     Info(TokenInfoArgs),
+    #[command(
+        about = "Look up the token for an asset ID.",
+        long_about = "Call tokenAddress(assetId) on the native token vault of the destination chain and print the token with its metadata.\nUse this to find the wrapped token behind an assetId seen in a bundle or event.\nExample: cast-interop token from-asset-id --chain-dest test --asset-id 0xASSET_ID"
+    )]
+    FromAssetId(TokenFromAssetIdArgs),
     #[command(
         about = "Check a destination balance.",
         long_about = "Look up the wrapped token balance for a recipient on the destination chain.\nUse this to verify token delivery.\nExample: cast-interop token balance --chain-src era --chain-dest test --token 0xTOKEN --to 0xRECIPIENT"
     )]
     Balance(TokenBalanceArgs),
+    #[command(
+        about = "Check a token allowance for the vault.",
+        long_about = "Read the ERC20 allowance an owner has granted to the native token vault (or another spender).\nUse this to see whether token send will need an approve step.\nExample: cast-interop token allowance --chain era --token 0xTOKEN --owner 0xOWNER"
+    )]
+    Allowance(TokenAllowanceArgs),
+    #[command(
+        about = "Register a token with the native token vault.",
+        long_about = "Call ensureTokenIsRegistered on the native token vault and print the asset ID.\nUse this in a setup step so later token send --skip-register runs are reliable.\nExample: cast-interop token register --chain era --token 0xTOKEN --private-key $PRIVATE_KEY"
+    )]
+    Register(TokenRegisterArgs),
     #[command(
         about = "Send a token across chains.",
         long_about = "Send an ERC20 across chains via interop (Type B flow).\nUse this for cross-chain token transfers, with optional watch mode.\nExample: cast-interop token send --chain-src era --chain-dest test --token 0xTOKEN --amount 1 --to 0xRECIPIENT --private-key $PRIVATE_KEY"
     )]
-    Send(TokenSendArgs),
+    Send(Box<TokenSendArgs>),
+    #[command(
+        name = "send-continue",
+        about = "Resume a token send after the bundle was sent.",
+        long_about = "Pick up an already-sent token bundle by its source tx and run only the destination step.\nUse this when token send failed after sendBundleTx (e.g. the root wait timed out) to avoid a duplicate send.\nExample: cast-interop token send-continue --chain-src era --chain-dest test --source-tx 0xTX_HASH --private-key $PRIVATE_KEY"
+    )]
+    SendContinue(TokenSendContinueArgs),
+    #[command(
+        name = "send-batch",
+        about = "Send a token to many recipients in one bundle.",
+        long_about = "Read a JSON file of {to, amount} entries and send one asset router call per recipient in a single bundle.\nUse this for airdrops instead of one token send per recipient.\nExample: cast-interop token send-batch --chain-src era --chain-dest test --token 0xTOKEN --recipients recipients.json --private-key $PRIVATE_KEY"
+    )]
+    SendBatch(TokenSendBatchArgs),
 }
 
 impl TokenCommand {
@@ -321,10 +593,27 @@ impl TokenCommand {
     pub async fn run(self, config: Config, addresses: AddressBook) -> Result<()> {
         match self.command {
             TokenSubcommand::Info(args) => commands::token::run_info(args, config, addresses).await,
+            TokenSubcommand::FromAssetId(args) => {
+                commands::token::run_from_asset_id(args, config, addresses).await
+            }
             TokenSubcommand::Balance(args) => {
                 commands::token::run_balance(args, config, addresses).await
             }
-            TokenSubcommand::Send(args) => commands::token::run_send(args, config, addresses).await,
+            TokenSubcommand::Allowance(args) => {
+                commands::token::run_allowance(args, config, addresses).await
+            }
+            TokenSubcommand::Register(args) => {
+                commands::token::run_register(args, config, addresses).await
+            }
+            TokenSubcommand::Send(args) => {
+                commands::token::run_send(*args, config, addresses).await
+            }
+            TokenSubcommand::SendContinue(args) => {
+                commands::token::run_send_continue(args, config, addresses).await
+            }
+            TokenSubcommand::SendBatch(args) => {
+                commands::token::run_send_batch(args, config, addresses).await
+            }
         }
     }
 }
@@ -350,14 +639,20 @@ pub enum EncodeSubcommand {
     )]
     Erc7930(Encode7930Args),
     #[command(
-        about = "Encode interop call attributes.",
-        long_about = "Build the attribute list for interop calls (interop value, indirect value, etc.).\nUse this to precompute bundle attributes.\nExample: cast-interop encode attrs --interop-value 0 --execution-address permissionless"
+        name = "decode-7930",
+        about = "Decode ERC-7930 chain/address bytes.",
+        long_about = "Decode ERC-7930 bytes back into chain ID and address.\nUse this to inspect recipients or execution/unbundler addresses from bundles.\nExample: cast-interop encode decode-7930 --bytes 0x0001000002012c00"
+    )]
+    Erc7930Decode(Decode7930Args),
+    #[command(
+        about = "Encode or decode interop call attributes.",
+        long_about = "Build the attribute list for interop calls (interop value, indirect value, etc.), or decode raw attribute bytes with --decode.\nUse this to precompute bundle attributes or identify attributes taken from events.\nExample: cast-interop encode attrs --interop-value 0 --execution-address permissionless"
     )]
     Attrs(EncodeAttrsArgs),
     #[command(
         name = "asset-id",
         about = "Encode an interop asset ID.",
-        long_about = "Compute the assetId hash from chain ID, token, and vault.\nUse this for token bridging diagnostics.\nExample: cast-interop encode asset-id --chain-id 324 --token 0xTOKEN"
+        long_about = "Compute the assetId hash from chain ID, token, and vault.\nUse this for token bridging diagnostics; repeat --token or pass --token-file to encode many tokens at once.\nExample: cast-interop encode asset-id --chain-id 324 --token 0xTOKEN"
     )]
     AssetId(EncodeAssetIdArgs),
 }
@@ -369,6 +664,9 @@ impl EncodeCommand {
             EncodeSubcommand::Erc7930(args) => {
                 commands::encode::run_7930(args, config, addresses).await
             }
+            EncodeSubcommand::Erc7930Decode(args) => {
+                commands::encode::run_decode_7930(args, config, addresses).await
+            }
             EncodeSubcommand::Attrs(args) => {
                 commands::encode::run_attrs(args, config, addresses).await
             }
@@ -403,6 +701,11 @@ pub enum ChainsSubcommand {
         long_about = "Store a chain alias with its RPC URL and chain ID.\nUse this to simplify future CLI commands.\nExample: cast-interop chains add era --rpc https://mainnet.era.zksync.io"
     )]
     Add(ChainsAddArgs),
+    #[command(
+        about = "Update a chain alias.",
+        long_about = "Change the RPC URL of an existing chain alias and re-probe its chain ID.\nUse this instead of rm + add when an endpoint moves.\nExample: cast-interop chains update era --rpc https://mainnet.era.zksync.io"
+    )]
+    Update(ChainsUpdateArgs),
     #[command(
         about = "Remove a chain alias.",
         long_about = "Delete a chain alias from the config file.\nUse this to clean up outdated entries.\nExample: cast-interop chains rm era"
@@ -418,6 +721,9 @@ impl ChainsCommand {
                 commands::chains::run_list(args, config, addresses).await
             }
             ChainsSubcommand::Add(args) => commands::chains::run_add(args, config, addresses).await,
+            ChainsSubcommand::Update(args) => {
+                commands::chains::run_update(args, config, addresses).await
+            }
             ChainsSubcommand::Rm(args) => {
                 commands::chains::run_remove(args, config, addresses).await
             }
@@ -443,6 +749,13 @@ pub struct RpcSelectionArgs {
     pub chain: Option<String>,
 }
 
+impl RpcSelectionArgs {
+    /// Resolve `--rpc`/`--chain` (or the configured default chain).
+    pub fn resolve(&self, config: &Config) -> Result<ResolvedRpc> {
+        config.resolve_rpc(self.rpc.as_deref(), self.chain.as_deref())
+    }
+}
+
 /// Signer selection flags for sending transactions.
 #[derive(Args, Debug, Clone)]
 pub struct SignerArgs {
@@ -459,6 +772,59 @@ pub struct SignerArgs {
         help = "Environment variable holding the private key. Default: PRIVATE_KEY or config signer.private_key_env."
     )]
     pub private_key_env: Option<String>,
+
+    #[arg(
+        long,
+        help = "Sign with a Ledger hardware wallet. Use instead of --private-key. Requires the ledger build feature. Default: false."
+    )]
+    pub ledger: bool,
+
+    #[arg(
+        long,
+        value_name = "INDEX",
+        requires = "ledger",
+        help = "Ledger Live account index to derive. Default: 0."
+    )]
+    pub ledger_account: Option<usize>,
+}
+
+/// Fee, gas limit, and nonce overrides for sending transactions.
+#[derive(Args, Debug, Clone)]
+pub struct TxOverrideArgs {
+    #[arg(
+        long,
+        value_name = "WEI",
+        help = "EIP-1559 max fee per gas in wei; the gas price when --legacy is set. Default: estimated by the RPC."
+    )]
+    pub max_fee_per_gas: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "WEI",
+        conflicts_with = "legacy",
+        help = "EIP-1559 max priority fee per gas in wei. Default: estimated by the RPC."
+    )]
+    pub max_priority_fee_per_gas: Option<String>,
+
+    #[arg(
+        long,
+        help = "Send a legacy transaction priced with gas_price instead of EIP-1559 fees. Default: false."
+    )]
+    pub legacy: bool,
+
+    #[arg(
+        long,
+        value_name = "GAS",
+        help = "Gas limit for each transaction. Default: estimated by the RPC."
+    )]
+    pub gas_limit: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "NONCE",
+        help = "Nonce for the transaction, e.g. to replace a stuck one. Has no effect with --dry-run. Default: next pending nonce of the signer."
+    )]
+    pub nonce: Option<u64>,
 }
 
 /// Decode interop events from a transaction receipt.
@@ -473,201 +839,1129 @@ pub struct TxShowArgs {
     #[arg(long, help = "Only show interop-specific events. Default: false.")]
     pub interop_only: bool,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
-}
+    #[arg(
+        long,
+        help = "Wait for a pending transaction to be mined instead of failing. Default: false."
+    )]
+    pub follow: bool,
 
-/// Extract a bundle from an interop transaction.
-#[derive(Args, Debug)]
-pub struct BundleExtractArgs {
-    #[command(flatten)]
-    pub rpc: RpcSelectionArgs,
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Timeout while waiting for the receipt with --follow, or for --confirmations. Default: config defaults.timeout_ms, else 300000."
+    )]
+    pub timeout_ms: Option<u64>,
 
     #[arg(
         long,
-        value_name = "TX_HASH",
-        help = "Transaction hash to extract from."
+        value_name = "BLOCKS",
+        help = "Wait until the transaction's block is BLOCKS behind the latest block, and check it was not reorged, before using the receipt. Default: config defaults.confirmations, else 0."
     )]
-    pub tx: String,
+    pub confirmations: Option<u64>,
 
     #[arg(
         long,
-        value_name = "PATH",
-        help = "Write the encoded bundle hex to a file. Default: unset."
+        value_name = "MILLISECONDS",
+        help = "Polling interval while waiting for the receipt with --follow, or for --confirmations. Default: config defaults.poll_ms, else 1000."
     )]
-    pub out: Option<PathBuf>,
+    pub poll_ms: Option<u64>,
 
     #[arg(
         long,
         value_name = "PATH",
-        help = "Write the JSON bundle view to a file. Default: unset."
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub json_out: Option<PathBuf>,
-
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    pub out: Option<PathBuf>,
 }
 
-/// Fetch a log proof for an interop transaction.
+/// List the L2→L1 messages sent by a transaction.
 #[derive(Args, Debug)]
-pub struct ProofArgs {
+pub struct MessagesArgs {
     #[command(flatten)]
     pub rpc: RpcSelectionArgs,
 
-    #[arg(long, value_name = "TX_HASH", help = "Transaction hash to prove.")]
+    #[arg(long, value_name = "TX_HASH", help = "Transaction hash to inspect.")]
     pub tx: String,
 
     #[arg(
         long,
-        value_name = "INDEX",
-        default_value_t = 0,
-        help = "Message index within the transaction. Default: 0."
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub msg_index: u32,
+    pub out: Option<PathBuf>,
+}
+
+/// Decode interop events from a block range.
+#[derive(Args, Debug)]
+pub struct LogsArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(long, value_name = "BLOCK", help = "First block to scan.")]
+    pub from_block: u64,
 
     #[arg(
         long,
-        value_name = "PATH",
-        help = "Write the proof JSON to a file. Default: unset."
+        value_name = "BLOCK",
+        help = "Last block to scan (inclusive). Default: latest."
     )]
-    pub out: Option<PathBuf>,
+    pub to_block: Option<u64>,
 
     #[arg(
         long,
-        help = "Do not wait for finalization before fetching proof. Default: false."
+        value_name = "ADDRESS",
+        help = "Only include logs emitted by this contract. Default: any address."
     )]
-    pub no_wait: bool,
-
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    pub address: Option<String>,
 
     #[arg(
         long,
-        value_name = "MILLISECONDS",
-        help = "Timeout while waiting for proof availability. Default: 300000."
+        help = "Drop logs that match an interop topic but were not emitted by the interop system contracts. Default: false."
     )]
-    pub timeout_ms: Option<u64>,
+    pub interop_only: bool,
 
     #[arg(
         long,
-        value_name = "MILLISECONDS",
-        help = "Polling interval for proof availability. Default: 1000."
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub poll_ms: Option<u64>,
+    pub out: Option<PathBuf>,
 }
 
-/// Wait for an interop root on the destination chain.
+/// Extract a bundle from an interop transaction.
 #[derive(Args, Debug)]
-pub struct RootWaitArgs {
+pub struct BundleExtractArgs {
     #[command(flatten)]
     pub rpc: RpcSelectionArgs,
 
     #[arg(
         long,
-        value_name = "CHAIN_ID",
-        help = "Source chain ID (not alias) used in interopRoots."
+        value_name = "TX_HASH",
+        help = "Transaction hash to extract from."
     )]
-    pub source_chain: String,
-
-    #[arg(long, value_name = "BATCH", help = "L1 batch number to check.")]
-    pub batch: u64,
+    pub tx: String,
 
     #[arg(
         long,
-        value_name = "ROOT",
-        help = "Expected interop root value (0x...)."
+        value_name = "INDEX",
+        help = "InteropBundleSent event to use when the transaction sends several bundles, counting from 0. List them with debug tx. Default: 0, with a warning if there are several."
     )]
-    pub expected_root: Option<String>,
+    pub bundle_index: Option<usize>,
 
     #[arg(
         long,
-        value_name = "MILLISECONDS",
-        help = "Timeout while waiting for root availability. Default: 300000."
+        value_name = "PATH",
+        help = "Write the encoded bundle hex to a file. Default: unset."
     )]
-    pub timeout_ms: Option<u64>,
+    pub out: Option<PathBuf>,
 
     #[arg(
         long,
-        value_name = "MILLISECONDS",
-        help = "Polling interval while waiting for root. Default: 1000."
+        value_name = "PATH",
+        help = "Write the JSON bundle view to a file. Default: unset."
     )]
-    pub poll_ms: Option<u64>,
+    pub json_out: Option<PathBuf>,
 }
 
-/// Verify or execute a bundle on the destination chain.
+/// Decode an encoded bundle without an RPC.
 #[derive(Args, Debug)]
-pub struct BundleActionArgs {
-    #[command(flatten)]
-    pub rpc: RpcSelectionArgs,
-
+pub struct BundleDecodeArgs {
     #[arg(
         long,
         value_name = "HEX_OR_PATH",
-        help = "Encoded bundle hex string or path to a bundle file."
+        help = "Encoded bundle hex, a path to a file containing it, or - to read stdin."
     )]
     pub bundle: String,
 
     #[arg(
         long,
-        value_name = "JSON_OR_PATH",
-        help = "Bundle proof JSON string or path to proof file."
+        help = "Reject bytes left over after the encoded bundle, such as two joined bundles. Default: false."
     )]
-    pub proof: String,
+    pub strict: bool,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Override the interop handler address. Default: config addresses.interop_handler."
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub handler: Option<String>,
+    pub out: Option<PathBuf>,
+}
 
+/// Hash an encoded bundle without an RPC.
+#[derive(Args, Debug)]
+pub struct BundleHashArgs {
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Override the interop center address. Default: config addresses.interop_center."
+        value_name = "HEX_OR_PATH",
+        help = "Encoded bundle hex, a path to a file containing it as written by bundle extract, or - to read stdin."
+    )]
+    pub bundle: String,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Compare two encoded bundles without an RPC.
+#[derive(Args, Debug)]
+pub struct BundleDiffArgs {
+    #[arg(
+        long,
+        value_name = "HEX_OR_PATH",
+        help = "First encoded bundle: hex, a path to a file containing it, or - to read stdin."
+    )]
+    pub a: String,
+
+    #[arg(
+        long,
+        value_name = "HEX_OR_PATH",
+        help = "Second encoded bundle: hex, a path to a file containing it, or - to read stdin."
+    )]
+    pub b: String,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Fetch a log proof for an interop transaction.
+#[derive(Args, Debug)]
+pub struct ProofArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(long, value_name = "TX_HASH", help = "Transaction hash to prove.")]
+    pub tx: String,
+
+    #[arg(
+        long,
+        value_name = "INDEX",
+        default_value_t = 0,
+        help = "Message index within the transaction. Default: 0."
+    )]
+    pub msg_index: u32,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the proof JSON to a file, or `-` for stdout only. Default: unset (print to stdout)."
+    )]
+    pub out: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Do not wait for finalization before fetching proof. Default: false."
+    )]
+    pub no_wait: bool,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Timeout while waiting for proof availability. Default: config defaults.timeout_ms, else 300000."
+    )]
+    pub timeout_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Wait until the source transaction's block is BLOCKS behind the latest block, and check it was not reorged, before using the receipt. Default: config defaults.confirmations, else 0."
+    )]
+    pub confirmations: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Give up once the latest block on the chain being proven has advanced by BLOCKS, instead of after the default timeout; an explicit --timeout-ms still applies. Default: unset."
+    )]
+    pub max_wait_blocks: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Polling interval for proof availability. Default: config defaults.poll_ms, else 1000."
+    )]
+    pub poll_ms: Option<u64>,
+}
+
+/// Check a proof against its root without an RPC.
+#[derive(Args, Debug)]
+pub struct ProofVerifyArgs {
+    #[arg(
+        long,
+        value_name = "JSON_OR_PATH",
+        help = "Proof JSON string, path to a proof file as written by debug proof, or - to read stdin."
+    )]
+    pub proof: String,
+
+    #[arg(
+        long,
+        value_name = "HEX_OR_PATH",
+        help = "Encoded bundle hex, a path to a file containing it, or - to read stdin; the proven message is 0x01 followed by the bundle. Default: the proof's message.data, else its l2l1MsgHash."
+    )]
+    pub bundle: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// List known error selectors, or look one up.
+#[derive(Args, Debug)]
+pub struct ErrorsArgs {
+    #[arg(
+        long,
+        value_name = "HEX",
+        help = "4-byte selector to look up. Default: unset (print the whole table)."
+    )]
+    pub selector: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "HEX",
+        requires = "selector",
+        help = "ABI-encoded error parameters (the revert data after the selector) to decode. Default: unset."
+    )]
+    pub data: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Wait for an interop root on the destination chain.
+#[derive(Args, Debug)]
+pub struct RootWaitArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(
+        long,
+        value_name = "CHAIN_ID",
+        help = "Source chain ID (not alias) used in interopRoots."
+    )]
+    pub source_chain: String,
+
+    #[arg(long, value_name = "BATCH", help = "L1 batch number to check.")]
+    pub batch: u64,
+
+    #[arg(
+        long,
+        value_name = "ROOT",
+        help = "Expected interop root value (0x...)."
+    )]
+    pub expected_root: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Timeout while waiting for root availability. Default: config defaults.timeout_ms, else 300000."
+    )]
+    pub timeout_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Give up once the latest block on the destination chain has advanced by BLOCKS, instead of after the default timeout; an explicit --timeout-ms still applies. Default: unset."
+    )]
+    pub max_wait_blocks: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Polling interval while waiting for root. Default: config defaults.poll_ms, else 1000."
+    )]
+    pub poll_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCK",
+        conflicts_with_all = ["timeout_ms", "max_wait_blocks", "poll_ms"],
+        help = "Check the root once, as stored at this block: a number or latest, safe, finalized, earliest, pending. Default: unset (wait for the root at the latest block)."
+    )]
+    pub block: Option<String>,
+}
+
+/// Verify or execute a bundle on the destination chain.
+#[derive(Args, Debug)]
+pub struct BundleActionArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(
+        long,
+        value_name = "HEX_OR_PATH",
+        help = "Encoded bundle hex string, path to a bundle file, or - to read stdin."
+    )]
+    pub bundle: String,
+
+    #[arg(
+        long,
+        value_name = "JSON_OR_PATH",
+        help = "Bundle proof JSON string, path to proof file, or - to read stdin."
+    )]
+    pub proof: String,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop handler address. Default: config addresses.interop_handler."
+    )]
+    pub handler: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop center address. Default: config addresses.interop_center."
+    )]
+    pub center: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "dry_run",
+        help = "Estimate gas for the handler transaction and exit without sending. Default: false."
+    )]
+    pub gas_estimate_only: bool,
+
+    #[arg(
+        long,
+        help = "Reject bytes left over after the encoded bundle, such as two joined bundles. Default: false."
+    )]
+    pub strict: bool,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
+}
+
+/// Check bundle status on the destination chain.
+#[derive(Args, Debug)]
+pub struct StatusArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(long, value_name = "BUNDLE_HASH", help = "Bundle hash to query.")]
+    pub bundle_hash: String,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop handler address. Default: config addresses.interop_handler."
+    )]
+    pub handler: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "HEX_OR_PATH",
+        help = "Optional bundle hex, bundle file path, or - for stdin, for per-call status lookup. Default: unset."
+    )]
+    pub bundle: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "BLOCK",
+        help = "Read the bundle and call status at this block: a number or latest, safe, finalized, earliest, pending. Default: latest."
+    )]
+    pub block: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Relay a bundle end-to-end across chains.
+#[derive(Args, Debug)]
+pub struct RelayArgs {
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Source chain RPC URL. Use instead of --chain-src. Default: uses configured default chain if set."
+    )]
+    pub rpc_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Source chain alias. Use instead of --rpc-src. Default: uses configured default chain if set."
+    )]
+    pub chain_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
+    )]
+    pub rpc_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
+    )]
+    pub chain_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "TX_HASH",
+        required_unless_present = "resume",
+        help = "Source transaction hash. Required unless --resume is set."
+    )]
+    pub tx: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with_all = ["tx", "msg_index", "bundle_index"],
+        help = "Resume from a previous --out-dir (bundle.hex, proof.json, relay_summary.json) and skip straight to the destination handler step. Default: unset."
+    )]
+    pub resume: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "INDEX",
+        default_value_t = 0,
+        help = "Message index within the transaction. Default: 0."
+    )]
+    pub msg_index: u32,
+
+    #[arg(
+        long,
+        value_name = "INDEX",
+        help = "InteropBundleSent event to use when the transaction sends several bundles, counting from 0. List them with debug tx. Default: 0, with a warning if there are several."
+    )]
+    pub bundle_index: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "MODE",
+        default_value = "execute",
+        help = "Relay mode (execute or verify). Default: execute."
+    )]
+    pub mode: String,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Directory to write relay artifacts. Default: unset."
+    )]
+    pub out_dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        conflicts_with = "dry_run",
+        help = "Estimate gas for the handler transaction and exit without sending. Default: false."
+    )]
+    pub gas_estimate_only: bool,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop handler address. Default: config addresses.interop_handler."
+    )]
+    pub handler: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop center address. Default: config addresses.interop_center."
+    )]
+    pub center: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop root storage address. Default: config addresses.interop_root_storage."
+    )]
+    pub root_storage: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Timeout while waiting for proof/root. Default: config defaults.timeout_ms, else 300000."
+    )]
+    pub timeout_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Wait until the source transaction's block is BLOCKS behind the latest block, and check it was not reorged, before using the receipt. Default: config defaults.confirmations, else 0."
+    )]
+    pub confirmations: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Give up once the latest block on the chain being polled (source for finalization and proof, destination for the root) has advanced by BLOCKS, instead of after the default timeout; an explicit --timeout-ms still applies. Default: unset."
+    )]
+    pub max_wait_blocks: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Polling interval for proof/root. Default: config defaults.poll_ms, else 1000."
+    )]
+    pub poll_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Cache proofs whose interop root is available in DIR, keyed by tx hash and message index, so a retry skips the waits. Default: unset."
+    )]
+    pub proof_cache: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Relay the bundles of many source transactions.
+#[derive(Args, Debug)]
+pub struct RelayBatchArgs {
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Source chain RPC URL. Use instead of --chain-src. Default: uses configured default chain if set."
+    )]
+    pub rpc_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Source chain alias. Use instead of --rpc-src. Default: uses configured default chain if set."
+    )]
+    pub chain_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
+    )]
+    pub rpc_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
+    )]
+    pub chain_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "File with one source transaction hash per line (blank lines and # comments ignored)."
+    )]
+    pub tx_file: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "INDEX",
+        default_value_t = 0,
+        help = "Message index within each transaction. Default: 0."
+    )]
+    pub msg_index: u32,
+
+    #[arg(
+        long,
+        value_name = "MODE",
+        default_value = "execute",
+        help = "Relay mode (execute or verify). Default: execute."
+    )]
+    pub mode: String,
+
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 4,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Maximum number of source proofs fetched at once. Default: 4."
+    )]
+    pub concurrency: u32,
+
+    #[arg(
+        long,
+        conflicts_with = "dry_run",
+        help = "Estimate gas for each handler transaction and exit without sending. Default: false."
+    )]
+    pub gas_estimate_only: bool,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop handler address. Default: config addresses.interop_handler."
+    )]
+    pub handler: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop center address. Default: config addresses.interop_center."
+    )]
+    pub center: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Override the interop root storage address. Default: config addresses.interop_root_storage."
+    )]
+    pub root_storage: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Timeout while waiting for each proof/root. Default: config defaults.timeout_ms, else 300000."
+    )]
+    pub timeout_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Wait until the source transaction's block is BLOCKS behind the latest block, and check it was not reorged, before using the receipt. Default: config defaults.confirmations, else 0."
+    )]
+    pub confirmations: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Give up once the latest block on the chain being polled (source for finalization and proof, destination for the root) has advanced by BLOCKS, instead of after the default timeout; an explicit --timeout-ms still applies. Default: unset."
+    )]
+    pub max_wait_blocks: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        help = "Polling interval for proof/root. Default: config defaults.poll_ms, else 1000."
+    )]
+    pub poll_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Cache proofs whose interop root is available in DIR, keyed by tx hash and message index, so a retry skips the waits. Default: unset."
+    )]
+    pub proof_cache: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// List configured chains.
+#[derive(Args, Debug)]
+pub struct ChainsListArgs {
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Add a chain alias.
+#[derive(Args, Debug)]
+pub struct ChainsAddArgs {
+    #[arg(value_name = "ALIAS", help = "Alias name to store.")]
+    pub alias: String,
+
+    #[arg(long, value_name = "RPC_URL", help = "RPC URL for the chain.")]
+    pub rpc: String,
+
+    #[arg(
+        long,
+        value_name = "CHAIN_ID",
+        help = "Store this chain ID instead of requiring a reachable RPC (warns if a reachable RPC reports a different one). Default: probed from --rpc."
+    )]
+    pub chain_id: Option<u64>,
+}
+
+/// Update an existing chain alias.
+#[derive(Args, Debug)]
+pub struct ChainsUpdateArgs {
+    #[arg(value_name = "ALIAS", help = "Existing alias to update.")]
+    pub alias: String,
+
+    #[arg(long, value_name = "RPC_URL", help = "New RPC URL for the chain.")]
+    pub rpc: String,
+
+    #[arg(
+        long,
+        help = "Keep the stored chain ID instead of probing the new RPC. Default: false."
+    )]
+    pub no_probe: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Remove a chain alias.
+#[derive(Args, Debug)]
+pub struct ChainsRemoveArgs {
+    #[arg(value_name = "ALIAS", help = "Alias name to remove.")]
+    pub alias: String,
+}
+
+/// Check RPC capabilities and status.
+#[derive(Args, Debug)]
+pub struct RpcPingArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(
+        long,
+        conflicts_with = "out",
+        help = "Keep probing instead of reporting once: time eth_chainId and the finalized block call every --interval-ms and print each sample with running min/avg/max latency, until Ctrl-C. Default: false."
+    )]
+    pub watch: bool,
+
+    #[arg(
+        long,
+        value_name = "MILLISECONDS",
+        requires = "watch",
+        help = "Time between --watch probes. Default: config defaults.poll_ms, else 1000."
+    )]
+    pub interval_ms: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "watch",
+        help = "Stop --watch after N probes. Default: unset (until Ctrl-C)."
+    )]
+    pub count: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Print interop contract addresses.
+#[derive(Args, Debug)]
+pub struct ContractsArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(
+        long,
+        value_name = "BLOCK",
+        help = "Read contract code at this block: a number or latest, safe, finalized, earliest, pending. Default: latest."
+    )]
+    pub block: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Send a single interop message.
+#[derive(Args, Debug)]
+pub struct SendMessageArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Destination chain: a configured alias (resolved to its chainId) or a numeric chain ID."
+    )]
+    pub to_chain: String,
+
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL, only used to check that its eth_chainId matches --to-chain. Default: unset (no check)."
+    )]
+    pub rpc_dest: Option<String>,
+
+    #[arg(
+        long,
+        help = "Only warn, instead of failing, when the destination chain is the source chain. Default: false."
+    )]
+    pub allow_same_chain: bool,
+
+    #[arg(long, value_name = "ADDRESS", help = "Target contract address.")]
+    pub to: String,
+
+    #[arg(
+        long,
+        value_name = "HEX",
+        help = "Hex payload data. Use instead of --payload-file. Default: 0x when --interop-value or --indirect is set, otherwise required."
+    )]
+    pub payload: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Path to a hex payload file. Use instead of --payload. Default: unset."
+    )]
+    pub payload_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "WEI",
+        help = "Interop call value in wei, or with a wei/gwei/ether suffix (e.g. 0.01ether). Default: 0."
+    )]
+    pub interop_value: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "WEI",
+        help = "Indirect message value in wei, or with a wei/gwei/ether suffix (e.g. 5gwei). Default: 0."
+    )]
+    pub indirect: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Execution address or 'permissionless'. Default: unset (permissionless)."
+    )]
+    pub execution_address: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Unbundler address for the call. Default: unset."
+    )]
+    pub unbundler: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "dry_run",
+        help = "Print the interop center target, value, and calldata for signing elsewhere, then exit without a signer. Default: false."
+    )]
+    pub calldata_only: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["dry_run", "calldata_only"],
+        help = "Show the decoded destination, recipient, payload, attributes, and total value, then exit without sending. Default: false."
+    )]
+    pub preview: bool,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Send a bundle of interop calls.
+#[derive(Args, Debug)]
+pub struct SendBundleArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Destination chain: a configured alias (resolved to its chainId) or a numeric chain ID."
+    )]
+    pub to_chain: String,
+
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL, only used to check that its eth_chainId matches --to-chain. Default: unset (no check)."
+    )]
+    pub rpc_dest: Option<String>,
+
+    #[arg(
+        long,
+        help = "Only warn, instead of failing, when the destination chain is the source chain. Default: false."
+    )]
+    pub allow_same_chain: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Path to bundle calls JSON, or - to read it from stdin."
+    )]
+    pub calls: PathBuf,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Bundle execution address or 'permissionless'. Default: unset."
+    )]
+    pub bundle_execution_address: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Bundle unbundler address. Default: unset."
+    )]
+    pub bundle_unbundler: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "dry_run",
+        help = "Print the interop center target, value, and calldata for signing elsewhere, then exit without a signer. Default: false."
+    )]
+    pub calldata_only: bool,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+/// Resolve wrapped token metadata.
+#[derive(Args, Debug)]
+pub struct TokenInfoArgs {
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Source chain RPC URL. Use instead of --chain-src. Default: uses configured default chain if set."
+    )]
+    pub rpc_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Source chain alias. Use instead of --rpc-src. Default: uses configured default chain if set."
+    )]
+    pub chain_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
+    )]
+    pub rpc_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
+    )]
+    pub chain_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        required_unless_present = "asset_id",
+        conflicts_with = "asset_id",
+        help = "Token address on the source chain."
     )]
-    pub center: Option<String>,
+    pub token: Option<String>,
 
     #[arg(
         long,
-        help = "Simulate the call without sending a transaction. Default: false."
+        value_name = "ASSET_ID",
+        help = "Asset ID (0x...) to look up directly, when the source token is unknown. Use instead of --token. Default: unset."
     )]
-    pub dry_run: bool,
+    pub asset_id: Option<String>,
 
-    #[command(flatten)]
-    pub signer: SignerArgs,
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
+    )]
+    pub native_token_vault: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
 }
 
-/// Check bundle status on the destination chain.
+/// Look up the token registered for an asset ID.
 #[derive(Args, Debug)]
-pub struct StatusArgs {
-    #[command(flatten)]
-    pub rpc: RpcSelectionArgs,
+pub struct TokenFromAssetIdArgs {
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
+    )]
+    pub rpc_dest: Option<String>,
 
-    #[arg(long, value_name = "BUNDLE_HASH", help = "Bundle hash to query.")]
-    pub bundle_hash: String,
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
+    )]
+    pub chain_dest: Option<String>,
+
+    #[arg(long, value_name = "BYTES32", help = "Asset ID to look up.")]
+    pub asset_id: String,
 
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Override the interop handler address. Default: config addresses.interop_handler."
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
     )]
-    pub handler: Option<String>,
+    pub native_token_vault: Option<String>,
 
     #[arg(
         long,
-        value_name = "HEX_OR_PATH",
-        help = "Optional bundle hex for per-call status lookup. Default: unset."
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub bundle: Option<String>,
-
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    pub out: Option<PathBuf>,
 }
 
-/// Relay a bundle end-to-end across chains.
+/// Check wrapped token balances.
 #[derive(Args, Debug)]
-pub struct RelayArgs {
+pub struct TokenBalanceArgs {
     #[arg(
         long,
         value_name = "RPC_URL",
@@ -696,346 +1990,325 @@ pub struct RelayArgs {
     )]
     pub chain_dest: Option<String>,
 
-    #[arg(long, value_name = "TX_HASH", help = "Source transaction hash.")]
-    pub tx: String,
-
     #[arg(
         long,
-        value_name = "INDEX",
-        default_value_t = 0,
-        help = "Message index within the transaction. Default: 0."
+        value_name = "ADDRESS",
+        required_unless_present = "asset_id",
+        conflicts_with = "asset_id",
+        help = "Token address on the source chain. Repeat to check several tokens at once; the output is then a list."
     )]
-    pub msg_index: u32,
+    pub token: Vec<String>,
 
     #[arg(
         long,
-        value_name = "MODE",
-        default_value = "execute",
-        help = "Relay mode (execute or verify). Default: execute."
+        value_name = "ASSET_ID",
+        help = "Asset ID (0x...) to look up directly, when the source token is unknown. Use instead of --token. Default: unset."
     )]
-    pub mode: String,
+    pub asset_id: Option<String>,
 
     #[arg(
         long,
-        value_name = "DIR",
-        help = "Directory to write relay artifacts. Default: unset."
+        value_name = "ADDRESS",
+        help = "Recipient address on the destination chain."
     )]
-    pub out_dir: Option<PathBuf>,
+    pub to: String,
 
     #[arg(
         long,
-        help = "Simulate the relay without sending transactions. Default: false."
+        value_name = "ADDRESS",
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
     )]
-    pub dry_run: bool,
-
-    #[command(flatten)]
-    pub signer: SignerArgs,
+    pub native_token_vault: Option<String>,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Override the interop handler address. Default: config addresses.interop_handler."
+        value_name = "BLOCK",
+        help = "Read the wrapped token and balance at this block: a number or latest, safe, finalized, earliest, pending. Default: latest."
     )]
-    pub handler: Option<String>,
+    pub block: Option<String>,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Override the interop center address. Default: config addresses.interop_center."
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub center: Option<String>,
+    pub out: Option<PathBuf>,
+}
+
+/// Check a token allowance on the source chain.
+#[derive(Args, Debug)]
+pub struct TokenAllowanceArgs {
+    #[command(flatten)]
+    pub rpc: RpcSelectionArgs,
+
+    #[arg(long, value_name = "ADDRESS", help = "Token address.")]
+    pub token: String,
+
+    #[arg(long, value_name = "ADDRESS", help = "Token owner address.")]
+    pub owner: String,
 
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Override the interop root storage address. Default: config addresses.interop_root_storage."
+        help = "Spender address. Default: the native token vault."
     )]
-    pub root_storage: Option<String>,
+    pub spender: Option<String>,
 
     #[arg(
         long,
-        value_name = "MILLISECONDS",
-        help = "Timeout while waiting for proof/root. Default: 300000."
+        value_name = "ADDRESS",
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
     )]
-    pub timeout_ms: Option<u64>,
+    pub native_token_vault: Option<String>,
 
     #[arg(
         long,
-        value_name = "MILLISECONDS",
-        help = "Polling interval for proof/root. Default: 1000."
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub poll_ms: Option<u64>,
-
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
-}
-
-/// List configured chains.
-#[derive(Args, Debug)]
-pub struct ChainsListArgs {
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
-}
-
-/// Add a chain alias.
-#[derive(Args, Debug)]
-pub struct ChainsAddArgs {
-    #[arg(value_name = "ALIAS", help = "Alias name to store.")]
-    pub alias: String,
-
-    #[arg(long, value_name = "RPC_URL", help = "RPC URL for the chain.")]
-    pub rpc: String,
-}
-
-/// Remove a chain alias.
-#[derive(Args, Debug)]
-pub struct ChainsRemoveArgs {
-    #[arg(value_name = "ALIAS", help = "Alias name to remove.")]
-    pub alias: String,
+    pub out: Option<PathBuf>,
 }
 
-/// Check RPC capabilities and status.
+/// Register a token with the native token vault.
 #[derive(Args, Debug)]
-pub struct RpcPingArgs {
+pub struct TokenRegisterArgs {
     #[command(flatten)]
     pub rpc: RpcSelectionArgs,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
-}
+    #[arg(long, value_name = "ADDRESS", help = "Token address to register.")]
+    pub token: String,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
+    )]
+    pub native_token_vault: Option<String>,
 
-/// Print interop contract addresses.
-#[derive(Args, Debug)]
-pub struct ContractsArgs {
     #[command(flatten)]
-    pub rpc: RpcSelectionArgs,
+    pub signer: SignerArgs,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
 }
 
-/// Send a single interop message.
+/// Send a token across chains.
 #[derive(Args, Debug)]
-pub struct SendMessageArgs {
-    #[command(flatten)]
-    pub rpc: RpcSelectionArgs,
-
+pub struct TokenSendArgs {
     #[arg(
         long,
-        value_name = "CHAIN_ID",
-        help = "Destination chain ID (not alias)."
+        value_name = "RPC_URL",
+        help = "Source chain RPC URL. Use instead of --chain-src. Default: uses configured default chain if set."
     )]
-    pub to_chain: String,
-
-    #[arg(long, value_name = "ADDRESS", help = "Target contract address.")]
-    pub to: String,
+    pub rpc_src: Option<String>,
 
     #[arg(
         long,
-        value_name = "HEX",
-        help = "Hex payload data. Use instead of --payload-file. Required unless --payload-file is set."
+        value_name = "CHAIN",
+        help = "Source chain alias. Use instead of --rpc-src. Default: uses configured default chain if set."
     )]
-    pub payload: Option<String>,
+    pub chain_src: Option<String>,
 
     #[arg(
         long,
-        value_name = "PATH",
-        help = "Path to a hex payload file. Use instead of --payload. Required unless --payload is set."
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
     )]
-    pub payload_file: Option<PathBuf>,
+    pub rpc_dest: Option<String>,
 
     #[arg(
         long,
-        value_name = "WEI",
-        help = "Interop call value in wei. Default: 0."
+        value_name = "CHAIN",
+        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
     )]
-    pub interop_value: Option<String>,
+    pub chain_dest: Option<String>,
 
     #[arg(
         long,
-        value_name = "WEI",
-        help = "Indirect message value in wei. Default: 0."
+        help = "Only warn, instead of failing, when the destination chain is the source chain. Default: false."
     )]
-    pub indirect: Option<String>,
+    pub allow_same_chain: bool,
 
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Execution address or 'permissionless'. Default: unset (permissionless)."
+        required_unless_present = "native",
+        conflicts_with = "native",
+        help = "Token address on the source chain. Required unless --native is set."
     )]
-    pub execution_address: Option<String>,
+    pub token: Option<String>,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Unbundler address for the call. Default: unset."
+        conflicts_with_all = ["interop_value", "indirect_msg_value", "approve_amount"],
+        help = "Send the chain's native (base) token instead of an ERC20. The amount travels as the call's interop value, so no register/approve is needed. Default: false."
     )]
-    pub unbundler: Option<String>,
+    pub native: bool,
 
     #[arg(
         long,
-        help = "Simulate the message without sending a transaction. Default: false."
+        value_name = "AMOUNT",
+        help = "Token amount in human units (uses decimals). Use instead of --amount-wei. Requires --decimals if token decimals are unavailable."
     )]
-    pub dry_run: bool,
-
-    #[command(flatten)]
-    pub signer: SignerArgs,
-
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
-}
-
-/// Send a bundle of interop calls.
-#[derive(Args, Debug)]
-pub struct SendBundleArgs {
-    #[command(flatten)]
-    pub rpc: RpcSelectionArgs,
+    pub amount: Option<String>,
 
     #[arg(
         long,
-        value_name = "CHAIN_ID",
-        help = "Destination chain ID (not alias)."
+        value_name = "WEI",
+        help = "Token amount in wei (raw). Use instead of --amount. Default: unset."
     )]
-    pub to_chain: String,
+    pub amount_wei: Option<String>,
 
-    #[arg(long, value_name = "PATH", help = "Path to bundle calls JSON.")]
-    pub calls: PathBuf,
+    #[arg(
+        long,
+        conflicts_with_all = ["amount", "amount_wei", "native"],
+        help = "Send the signer's whole source token balance (balanceOf at preflight). Use instead of --amount/--amount-wei. Default: false."
+    )]
+    pub amount_all: bool,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Bundle execution address or 'permissionless'. Default: unset."
+        value_name = "DECIMALS",
+        help = "Token decimals used with --amount. Default: fetched from token; required if decimals are unavailable."
     )]
-    pub bundle_execution_address: Option<String>,
+    pub decimals: Option<u32>,
 
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Bundle unbundler address. Default: unset."
+        help = "Recipient address on destination."
     )]
-    pub bundle_unbundler: Option<String>,
+    pub to: String,
 
     #[arg(
         long,
-        help = "Simulate the bundle without sending a transaction. Default: false."
+        value_name = "WEI",
+        default_value = "0",
+        help = "Indirect message value in wei, or with a wei/gwei/ether suffix (e.g. 5gwei). Default: 0."
     )]
-    pub dry_run: bool,
+    pub indirect_msg_value: String,
 
-    #[command(flatten)]
-    pub signer: SignerArgs,
+    #[arg(
+        long,
+        value_name = "WEI",
+        help = "Interop call value in wei, or with a wei/gwei/ether suffix (e.g. 0.01ether). Default: none."
+    )]
+    pub interop_value: Option<String>,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
-}
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Unbundler address on destination. Default: recipient."
+    )]
+    pub unbundler: Option<String>,
 
-/// Resolve wrapped token metadata.
-#[derive(Args, Debug)]
-pub struct TokenInfoArgs {
     #[arg(
         long,
-        value_name = "RPC_URL",
-        help = "Source chain RPC URL. Use instead of --chain-src. Default: uses configured default chain if set."
+        value_name = "ADDRESS",
+        help = "Asset router address. Default: config addresses.asset_router, else 0x0000000000000000000000000000000000010003."
     )]
-    pub rpc_src: Option<String>,
+    pub asset_router: Option<String>,
 
     #[arg(
         long,
-        value_name = "CHAIN",
-        help = "Source chain alias. Use instead of --rpc-src. Default: uses configured default chain if set."
+        value_name = "ADDRESS",
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
     )]
-    pub chain_src: Option<String>,
+    pub native_token_vault: Option<String>,
+
+    #[arg(long, help = "Skip token registration step. Default: false.")]
+    pub skip_register: bool,
 
     #[arg(
         long,
-        value_name = "RPC_URL",
-        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
+        help = "Skip token approve step. Default: false (approve only runs when the current allowance is too low)."
     )]
-    pub rpc_dest: Option<String>,
+    pub skip_approve: bool,
 
     #[arg(
         long,
-        value_name = "CHAIN",
-        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
+        help = "Skip checking that the signer holds --amount of the token before register/approve. Default: false (always skipped under --dry-run)."
     )]
-    pub chain_dest: Option<String>,
+    pub skip_balance_check: bool,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Token address on the source chain."
+        value_name = "AMOUNT",
+        help = "Approve amount (wei or 'infinite'). Default: equals send amount."
     )]
-    pub token: String,
+    pub approve_amount: Option<String>,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Native token vault address. Default: 0x0000000000000000000000000000000000010004."
+        value_name = "MODE",
+        default_value = "execute",
+        help = "Handler action (execute or verify). Default: execute."
     )]
-    pub native_token_vault: Option<String>,
+    pub mode: String,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
-}
+    #[arg(long, help = "Watch the relay flow until completion. Default: false.")]
+    pub watch: bool,
 
-/// Check wrapped token balances.
-#[derive(Args, Debug)]
-pub struct TokenBalanceArgs {
     #[arg(
         long,
-        value_name = "RPC_URL",
-        help = "Source chain RPC URL. Use instead of --chain-src. Default: uses configured default chain if set."
+        value_name = "MILLISECONDS",
+        help = "Timeout while waiting for proof/root. Default: config defaults.timeout_ms, else 300000."
     )]
-    pub rpc_src: Option<String>,
+    pub timeout_ms: Option<u64>,
 
     #[arg(
         long,
-        value_name = "CHAIN",
-        help = "Source chain alias. Use instead of --rpc-src. Default: uses configured default chain if set."
+        value_name = "BLOCKS",
+        help = "Wait until the source transaction's block is BLOCKS behind the latest block, and check it was not reorged, before using the receipt. Default: config defaults.confirmations, else 0."
     )]
-    pub chain_src: Option<String>,
+    pub confirmations: Option<u64>,
 
     #[arg(
         long,
-        value_name = "RPC_URL",
-        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
+        value_name = "MILLISECONDS",
+        help = "Polling interval for proof/root. Default: config defaults.poll_ms, else 1000."
     )]
-    pub rpc_dest: Option<String>,
+    pub poll_ms: Option<u64>,
 
     #[arg(
         long,
-        value_name = "CHAIN",
-        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
+        value_name = "DIR",
+        help = "Cache proofs whose interop root is available in DIR, keyed by tx hash and message index, so a retry skips the waits. Default: unset."
     )]
-    pub chain_dest: Option<String>,
+    pub proof_cache: Option<PathBuf>,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Token address on the source chain."
+        conflicts_with = "dry_run",
+        help = "Print the target, value, and calldata of each source transaction (register, approve, sendBundle) for signing elsewhere, then exit without a signer. Default: false."
     )]
-    pub token: String,
+    pub calldata_only: bool,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Recipient address on the destination chain."
+        value_name = "PATH",
+        conflicts_with_all = ["dry_run", "calldata_only"],
+        help = "Record each completed step (register, approve, sendBundle, bundle hash, handler tx) in PATH and resume from it on a re-run; proofs are cached next to it unless --proof-cache is set. Default: unset."
     )]
-    pub to: String,
+    pub state_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Native token vault address. Default: 0x0000000000000000000000000000000000010004."
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
     )]
-    pub native_token_vault: Option<String>,
-
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    pub out: Option<PathBuf>,
 }
 
-/// Send a token across chains.
+/// Send a token to many recipients in a single bundle.
 #[derive(Args, Debug)]
-pub struct TokenSendArgs {
+pub struct TokenSendBatchArgs {
     #[arg(
         long,
         value_name = "RPC_URL",
@@ -1066,87 +2339,120 @@ pub struct TokenSendArgs {
 
     #[arg(
         long,
-        value_name = "ADDRESS",
-        help = "Token address on the source chain."
+        help = "Only warn, instead of failing, when the destination chain is the source chain. Default: false."
     )]
-    pub token: String,
+    pub allow_same_chain: bool,
 
     #[arg(
         long,
-        value_name = "AMOUNT",
-        help = "Token amount in human units (uses decimals). Use instead of --amount-wei. Requires --decimals if token decimals are unavailable."
+        value_name = "ADDRESS",
+        help = "Token address on the source chain."
     )]
-    pub amount: Option<String>,
+    pub token: String,
 
     #[arg(
         long,
-        value_name = "WEI",
-        help = "Token amount in wei (raw). Use instead of --amount. Default: unset."
+        value_name = "PATH",
+        help = "Path to a recipients JSON file: {\"recipients\": [{\"to\": \"0x..\", \"amount\": \"1.5\"}]}. Use \"amountWei\" instead of \"amount\" for raw values."
     )]
-    pub amount_wei: Option<String>,
+    pub recipients: PathBuf,
 
     #[arg(
         long,
         value_name = "DECIMALS",
-        help = "Token decimals used with --amount. Default: fetched from token; required if decimals are unavailable."
+        help = "Token decimals used with \"amount\" entries. Default: fetched from token; required if decimals are unavailable."
     )]
     pub decimals: Option<u32>,
 
-    #[arg(
-        long,
-        value_name = "ADDRESS",
-        help = "Recipient address on destination."
-    )]
-    pub to: String,
-
     #[arg(
         long,
         value_name = "WEI",
         default_value = "0",
-        help = "Indirect message value in wei. Default: 0."
+        help = "Indirect message value per recipient call in wei, or with a wei/gwei/ether suffix (e.g. 5gwei). Default: 0."
     )]
     pub indirect_msg_value: String,
 
-    #[arg(
-        long,
-        value_name = "WEI",
-        help = "Interop call value in wei. Default: none."
-    )]
-    pub interop_value: Option<String>,
-
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Unbundler address on destination. Default: recipient."
+        help = "Unbundler address on destination. Default: signer address; required with --dry-run and no signer."
     )]
     pub unbundler: Option<String>,
 
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Asset router address. Default: 0x0000000000000000000000000000000000010003."
+        help = "Asset router address. Default: config addresses.asset_router, else 0x0000000000000000000000000000000000010003."
     )]
     pub asset_router: Option<String>,
 
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Native token vault address. Default: 0x0000000000000000000000000000000000010004."
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
     )]
     pub native_token_vault: Option<String>,
 
     #[arg(long, help = "Skip token registration step. Default: false.")]
     pub skip_register: bool,
 
-    #[arg(long, help = "Skip token approve step. Default: false.")]
+    #[arg(
+        long,
+        help = "Skip token approve step. Default: false (approve only runs when the current allowance is too low)."
+    )]
     pub skip_approve: bool,
 
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
+}
+
+/// Resume a token send from an already-sent bundle.
+#[derive(Args, Debug)]
+pub struct TokenSendContinueArgs {
     #[arg(
         long,
-        value_name = "AMOUNT",
-        help = "Approve amount (wei or 'infinite'). Default: equals send amount."
+        value_name = "RPC_URL",
+        help = "Source chain RPC URL. Use instead of --chain-src. Default: uses configured default chain if set."
     )]
-    pub approve_amount: Option<String>,
+    pub rpc_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Source chain alias. Use instead of --rpc-src. Default: uses configured default chain if set."
+    )]
+    pub chain_src: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        help = "Destination chain RPC URL. Use instead of --chain-dest. Default: uses configured default chain if set."
+    )]
+    pub rpc_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        help = "Destination chain alias. Use instead of --rpc-dest. Default: uses configured default chain if set."
+    )]
+    pub chain_dest: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "TX_HASH",
+        help = "Source transaction hash of the sendBundle step."
+    )]
+    pub source_tx: String,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
+    )]
+    pub native_token_vault: Option<String>,
 
     #[arg(
         long,
@@ -1156,31 +2462,39 @@ pub struct TokenSendArgs {
     )]
     pub mode: String,
 
-    #[arg(long, help = "Watch the relay flow until completion. Default: false.")]
-    pub watch: bool,
-
     #[arg(
         long,
         value_name = "MILLISECONDS",
-        help = "Timeout while waiting for proof/root. Default: 300000."
+        help = "Timeout while waiting for proof/root. Default: config defaults.timeout_ms, else 300000."
     )]
     pub timeout_ms: Option<u64>,
 
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Wait until the source transaction's block is BLOCKS behind the latest block, and check it was not reorged, before using the receipt. Default: config defaults.confirmations, else 0."
+    )]
+    pub confirmations: Option<u64>,
+
     #[arg(
         long,
         value_name = "MILLISECONDS",
-        help = "Polling interval for proof/root. Default: 1000."
+        help = "Polling interval for proof/root. Default: config defaults.poll_ms, else 1000."
     )]
     pub poll_ms: Option<u64>,
 
     #[arg(
         long,
-        help = "Simulate the token transfer without sending transactions. Default: false."
+        value_name = "DIR",
+        help = "Cache proofs whose interop root is available in DIR, keyed by tx hash and message index, so a retry skips the waits. Default: unset."
     )]
-    pub dry_run: bool,
+    pub proof_cache: Option<PathBuf>,
 
     #[command(flatten)]
     pub signer: SignerArgs,
+
+    #[command(flatten)]
+    pub overrides: TxOverrideArgs,
 }
 
 /// Encode ERC-7930 bytes.
@@ -1206,6 +2520,28 @@ pub struct Encode7930Args {
         help = "Encode only the address without chain ID. Use instead of --chain-id/--address."
     )]
     pub address_only: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "BYTES",
+        conflicts_with = "address_only",
+        help = "Left-pad the chain reference to BYTES bytes (1-32) instead of the minimal encoding; fails if the chain ID does not fit. Default: minimal."
+    )]
+    pub chain_ref_len: Option<usize>,
+}
+
+/// Decode ERC-7930 bytes.
+#[derive(Args, Debug)]
+pub struct Decode7930Args {
+    #[arg(long, value_name = "HEX", help = "ERC-7930 bytes to decode (0x...).")]
+    pub bytes: String,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
 }
 
 /// Encode interop attributes.
@@ -1214,14 +2550,14 @@ pub struct EncodeAttrsArgs {
     #[arg(
         long,
         value_name = "WEI",
-        help = "Interop call value in wei. Default: none."
+        help = "Interop call value in wei, or with a wei/gwei/ether suffix (e.g. 0.01ether). Default: none."
     )]
     pub interop_value: Option<String>,
 
     #[arg(
         long,
         value_name = "WEI",
-        help = "Indirect message value in wei. Default: none."
+        help = "Indirect message value in wei, or with a wei/gwei/ether suffix (e.g. 5gwei). Default: none."
     )]
     pub indirect: Option<String>,
 
@@ -1239,8 +2575,20 @@ pub struct EncodeAttrsArgs {
     )]
     pub unbundler: Option<String>,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    #[arg(
+        long,
+        value_name = "HEX",
+        conflicts_with_all = ["interop_value", "indirect", "execution_address", "unbundler"],
+        help = "Decode an ABI-encoded attribute instead of encoding. Repeat for several attributes. Default: none."
+    )]
+    pub decode: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
 }
 
 /// Encode a token asset ID.
@@ -1249,15 +2597,34 @@ pub struct EncodeAssetIdArgs {
     #[arg(long, value_name = "CHAIN_ID", help = "Source chain ID.")]
     pub chain_id: String,
 
-    #[arg(long, value_name = "ADDRESS", help = "Token address on source chain.")]
-    pub token: String,
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        required_unless_present = "token_file",
+        help = "Token address on source chain. Repeat to encode several tokens; the output is then a list."
+    )]
+    pub token: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "File with one token address per line (blank lines and # comments skipped), encoded after any --token. Default: unset."
+    )]
+    pub token_file: Option<PathBuf>,
 
     #[arg(
         long,
         value_name = "ADDRESS",
-        help = "Native token vault address. Default: 0x0000000000000000000000000000000000010004."
+        help = "Native token vault address. Default: config addresses.native_token_vault, else 0x0000000000000000000000000000000000010004."
     )]
     pub native_token_vault: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
 }
 
 /// Watch interop progress.
@@ -1294,9 +2661,17 @@ pub struct WatchArgs {
     #[arg(
         long,
         value_name = "TX_HASH",
-        help = "Source transaction hash to watch."
+        required_unless_present = "tx_file",
+        help = "Source transaction hash to watch. Repeat to watch several transactions concurrently."
     )]
-    pub tx: String,
+    pub tx: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "File with source transaction hashes to watch, one per line. Default: unset."
+    )]
+    pub tx_file: Option<PathBuf>,
 
     #[arg(
         long,
@@ -1306,29 +2681,47 @@ pub struct WatchArgs {
     )]
     pub msg_index: u32,
 
+    #[arg(
+        long,
+        value_name = "HEX_OR_PATH",
+        help = "Encoded bundle hex string, path to a bundle file, or - to read stdin; also emits call_status events for each of its calls. Default: unset."
+    )]
+    pub bundle: Option<String>,
+
     #[arg(
         long,
         value_name = "STATE",
-        help = "Stop when the bundle is verified or executed. Values: verified|executed. Default: unset."
+        help = "Stop when the bundle is verified or executed; with several transactions, waits for all of them. Values: verified|executed. Default: unset."
     )]
     pub until: Option<String>,
 
     #[arg(
         long,
         value_name = "MILLISECONDS",
-        help = "Polling interval while watching. Default: 1000."
+        help = "Polling interval while watching. Default: config defaults.poll_ms, else 1000."
     )]
     pub poll_ms: Option<u64>,
 
     #[arg(
         long,
         value_name = "MILLISECONDS",
-        help = "Timeout while watching. Default: 300000."
+        help = "Timeout while watching. Default: config defaults.timeout_ms, else 300000."
     )]
     pub timeout_ms: Option<u64>,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Wait until the source transaction's block is BLOCKS behind the latest block, and check it was not reorged, before using the receipt. Default: config defaults.confirmations, else 0."
+    )]
+    pub confirmations: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "BLOCKS",
+        help = "Give up once the latest block on the source chain has advanced by BLOCKS, instead of after the default timeout; an explicit --timeout-ms still applies. Default: unset."
+    )]
+    pub max_wait_blocks: Option<u64>,
 }
 
 /// Run diagnostic checks.
@@ -1337,8 +2730,21 @@ pub struct DoctorArgs {
     #[command(flatten)]
     pub rpc: RpcSelectionArgs,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    #[command(flatten)]
+    pub signer: SignerArgs,
+
+    #[arg(
+        long,
+        help = "Also exit non-zero when a check only warns. Default: false."
+    )]
+    pub fail_on_warn: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
 }
 
 /// Explain a bundle proof execution.
@@ -1350,14 +2756,14 @@ pub struct ExplainArgs {
     #[arg(
         long,
         value_name = "HEX_OR_PATH",
-        help = "Encoded bundle hex string or path to a bundle file."
+        help = "Encoded bundle hex string, path to a bundle file, or - to read stdin."
     )]
     pub bundle: String,
 
     #[arg(
         long,
         value_name = "JSON_OR_PATH",
-        help = "Bundle proof JSON string or path to proof file."
+        help = "Bundle proof JSON string, path to proof file, or - to read stdin."
     )]
     pub proof: String,
 
@@ -1368,9 +2774,145 @@ pub struct ExplainArgs {
     )]
     pub handler: Option<String>,
 
+    #[arg(
+        long,
+        help = "Also simulate the handler call with eth_call and decode any revert. Default: false."
+    )]
+    pub simulate: bool,
+
+    #[arg(
+        long,
+        value_name = "MODE",
+        default_value = "execute",
+        requires = "simulate",
+        help = "Handler action to simulate (execute or verify). Default: execute."
+    )]
+    pub mode: String,
+
+    #[arg(
+        long,
+        help = "Reject bytes left over after the encoded bundle, such as two joined bundles. Default: false."
+    )]
+    pub strict: bool,
+
+    #[arg(
+        long,
+        help = "Also exit non-zero when a check only warns. Default: false."
+    )]
+    pub fail_on_warn: bool,
+
     #[command(flatten)]
     pub signer: SignerArgs,
 
-    #[arg(long, help = "Emit JSON output. Default: false.")]
-    pub json: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Also write the JSON output to PATH, whatever the --output format. Default: unset."
+    )]
+    pub out: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN_SEND: &[&str] = &[
+        "token",
+        "send",
+        "--calldata-only",
+        "--rpc-src",
+        "http://127.0.0.1:8545",
+        "--token",
+        "0x0000000000000000000000000000000000000001",
+        "--amount",
+        "1",
+        "--to",
+        "0x0000000000000000000000000000000000000002",
+    ];
+
+    #[test]
+    fn dry_run_before_subcommand_still_conflicts() {
+        let argv = ["cast-interop", "--dry-run", "--yes"]
+            .iter()
+            .chain(TOKEN_SEND)
+            .copied();
+        let err = Cli::try_parse_checked_from(argv).unwrap_err();
+        assert!(err.to_string().contains("'--calldata-only'"), "{err}");
+    }
+
+    /// Two chains, so a resolution that ignored `--chain` and fell back to
+    /// the only configured chain could not pass.
+    fn chain_alias_config() -> Config {
+        let chain = |rpc: &str| crate::config::ChainConfig {
+            rpc: rpc.to_string(),
+            ..Default::default()
+        };
+        Config {
+            chains: Some(
+                [
+                    ("era".to_string(), chain("http://era.example:3050")),
+                    ("test".to_string(), chain("http://test.example:3050")),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bundle_status_resolves_chain_alias() {
+        let cli = Cli::try_parse_from([
+            "cast-interop",
+            "bundle",
+            "status",
+            "--chain",
+            "test",
+            "--bundle-hash",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ])
+        .unwrap();
+        let Command::Bundle(BundleCommand {
+            command: BundleSubcommand::Status(args),
+        }) = cli.command
+        else {
+            panic!("expected bundle status");
+        };
+        let resolved = args.rpc.resolve(&chain_alias_config()).unwrap();
+        assert_eq!(resolved.url, "http://test.example:3050");
+        assert_eq!(resolved.alias.as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn debug_root_resolves_chain_alias() {
+        let cli = Cli::try_parse_from([
+            "cast-interop",
+            "debug",
+            "root",
+            "--chain",
+            "test",
+            "--source-chain",
+            "324",
+            "--batch",
+            "1",
+        ])
+        .unwrap();
+        let Command::Debug(DebugCommand {
+            command: DebugSubcommand::Root(args),
+        }) = cli.command
+        else {
+            panic!("expected debug root");
+        };
+        let resolved = args.rpc.resolve(&chain_alias_config()).unwrap();
+        assert_eq!(resolved.url, "http://test.example:3050");
+        assert_eq!(resolved.alias.as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn dry_run_after_subcommand_is_rejected_by_clap() {
+        let argv = std::iter::once("cast-interop")
+            .chain(TOKEN_SEND.iter().copied())
+            .chain(["--dry-run"]);
+        assert!(Cli::try_parse_from(argv).is_err());
+    }
 }
@@ -1,24 +1,29 @@
-use crate::abi::{encode_execute_bundle_call, encode_verify_bundle_call, error_selector_map};
+use crate::abi::{
+    decode_bundle_strict, decode_revert, encode_execute_bundle_call, encode_verify_bundle_call,
+    revert_data_from_message,
+};
 use crate::cli::BundleActionArgs;
 use crate::config::Config;
-use crate::rpc::{eth_call, RpcClient};
-use crate::signer::{load_signer, SignerOptions};
+use crate::error::CastInteropError;
+use crate::output::read_stdin;
+use crate::rpc::{estimate_gas, eth_call, RpcClient, TxOverrides};
+use crate::signer::{load_signer, signer_address, SignerOptions};
 use crate::types::{
-    require_signer_or_dry_run, AddressBook, MessageInclusionProof, BUNDLE_IDENTIFIER,
+    bundle_message, format_hex, parse_checked_address, require_signer_or_dry_run, AddressBook,
+    MessageInclusionProof,
 };
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_primitives::Bytes;
 use alloy_provider::transport::TransportResult;
-use alloy_provider::{Provider, ProviderBuilder};
+use alloy_provider::Provider;
 use alloy_rpc_types::TransactionInput;
-use alloy_sol_types::SolValue;
 use anyhow::{anyhow, Context, Result};
 use std::fs;
 use std::path::Path;
-use std::str::FromStr;
 
 /// Verify a bundle proof on the destination chain.
 ///
 /// This submits a verify call and reports the transaction hash or dry-run result.
+#[tracing::instrument(name = "bundle verify", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run_verify(
     args: BundleActionArgs,
     config: Config,
@@ -30,6 +35,7 @@ pub async fn run_verify(
 /// Execute a bundle proof on the destination chain.
 ///
 /// This submits an execute call and reports the transaction hash or dry-run result.
+#[tracing::instrument(name = "bundle execute", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run_execute(
     args: BundleActionArgs,
     config: Config,
@@ -51,14 +57,14 @@ async fn run_bundle_action(
     let handler = args
         .handler
         .as_deref()
-        .map(|value| Address::from_str(value))
+        .map(parse_checked_address)
         .transpose()
         .context("invalid handler address")?
         .unwrap_or(addresses.interop_handler);
     let center = args
         .center
         .as_deref()
-        .map(|value| Address::from_str(value))
+        .map(parse_checked_address)
         .transpose()
         .context("invalid center address")?
         .unwrap_or(addresses.interop_center);
@@ -67,14 +73,31 @@ async fn run_bundle_action(
         SignerOptions {
             private_key: args.signer.private_key.as_deref(),
             private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
         },
         &config,
+    )
+    .await?;
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
+
+    require_signer_or_dry_run(
+        wallet.is_some(),
+        config.dry_run || args.gas_estimate_only,
+        cmd,
     )?;
 
-    require_signer_or_dry_run(wallet.is_some(), args.dry_run, cmd)?;
-
     let encoded_bundle = load_hex_or_path(&args.bundle)?;
+    if args.strict {
+        decode_bundle_strict(&encoded_bundle).context("invalid bundle")?;
+    }
     let mut proof = load_proof(&args.proof)?;
+    proof.validate()?;
+    let message = bundle_message(config.bundle_identifier, &encoded_bundle);
+    proof
+        .check_message(&message)
+        .context("--proof does not prove --bundle")?;
 
     let expected_sender = format!("{center:#x}");
     if proof.message.sender.to_lowercase() != expected_sender.to_lowercase() {
@@ -84,11 +107,7 @@ async fn run_bundle_action(
         );
     }
     proof.message.sender = expected_sender;
-    proof.message.data = format!(
-        "0x{}{}",
-        hex::encode([BUNDLE_IDENTIFIER]),
-        hex::encode(&encoded_bundle)
-    );
+    proof.message.data = format_hex(&message);
 
     let calldata = if is_verify {
         encode_verify_bundle_call(Bytes::from(encoded_bundle.clone()), proof.clone())?
@@ -97,37 +116,39 @@ async fn run_bundle_action(
     };
 
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
-    if args.dry_run {
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+    if config.dry_run {
         match eth_call(&client, handler, calldata.clone()).await {
             Ok(_) => {
                 println!("dry-run success");
             }
-            Err(err) => {
-                if let Some(reason) = decode_revert_reason(err.to_string()) {
-                    println!("dry-run revert: {reason}");
-                } else {
-                    println!("dry-run failed: {err}");
-                }
-            }
+            Err(err) => println!("{}", describe_dry_run_failure(&err)),
         }
         return Ok(());
     }
 
-    let wallet = wallet.expect("wallet required");
-    let chain_id = client.provider.get_chain_id().await?;
+    let from = wallet.as_ref().map(signer_address).transpose()?;
+    match estimate_gas(&client, from, handler, calldata.clone()).await {
+        Ok(gas) => println!("estimated gas: {gas}"),
+        // An explicit --gas-limit still lets the transaction go out.
+        Err(err) if overrides.gas_limit.is_some() && !args.gas_estimate_only => {
+            eprintln!("warning: {err}");
+        }
+        Err(err) => return Err(err),
+    }
+    if args.gas_estimate_only {
+        return Ok(());
+    }
 
-    let provider = ProviderBuilder::new()
-        .wallet(wallet)
-        .with_chain_id(chain_id)
-        .connect(&resolved.url)
-        .await?;
+    let wallet = wallet.expect("wallet required");
+    let provider = client.signer_provider(wallet).await?;
 
-    let request = alloy_rpc_types::TransactionRequest {
+    let mut request = alloy_rpc_types::TransactionRequest {
         to: Some(alloy_primitives::TxKind::Call(handler)),
         input: TransactionInput::new(calldata),
         ..Default::default()
     };
+    overrides.apply(&client, &mut request).await?;
     let pending = decode_send_transaction(provider.send_transaction(request).await)?;
 
     let tx_hash = pending.tx_hash();
@@ -135,8 +156,11 @@ async fn run_bundle_action(
     Ok(())
 }
 
-/// Load a hex string or read hex contents from a file path.
+/// Load a hex string, or read hex contents from a file path or stdin (`-`).
 fn load_hex_or_path(value: &str) -> Result<Vec<u8>> {
+    if value == "-" {
+        return decode_hex(&read_stdin("--bundle")?);
+    }
     if Path::new(value).exists() {
         let contents = fs::read_to_string(value)?;
         return decode_hex(&contents);
@@ -151,8 +175,12 @@ fn decode_hex(value: &str) -> Result<Vec<u8>> {
     hex::decode(raw).map_err(|err| anyhow!("invalid hex {value}: {err}"))
 }
 
-/// Load a MessageInclusionProof from a JSON string or file path.
-fn load_proof(value: &str) -> Result<MessageInclusionProof> {
+/// Load a MessageInclusionProof from a JSON string, a file path, or stdin (`-`).
+pub(crate) fn load_proof(value: &str) -> Result<MessageInclusionProof> {
+    if value == "-" {
+        let contents = read_stdin("--proof")?;
+        return serde_json::from_str(&contents).context("invalid proof json");
+    }
     if Path::new(value).exists() {
         let contents = fs::read_to_string(value)?;
         return serde_json::from_str(&contents).context("invalid proof json");
@@ -163,49 +191,30 @@ fn load_proof(value: &str) -> Result<MessageInclusionProof> {
     anyhow::bail!("proof must be a JSON string or path")
 }
 
-/// Decode a revert reason from an error string, if present.
-pub fn decode_revert_reason(message: String) -> Option<String> {
-    let hex_start = message.find("0x")?;
-    let hex_data = &message[hex_start..];
-    let hex_end = hex_data.find('"').unwrap_or(hex_data.len());
-    let hex_data = &hex_data[..hex_end];
-    let data = decode_hex(hex_data).ok()?;
-    if data.len() < 4 {
-        println!("revert data too short, len={}", data.len());
-        return None;
+/// Render a failed dry-run `eth_call`, naming the interop error (e.g.
+/// `WrongSourceChainId(...)`) when the revert data decodes.
+pub(crate) fn describe_dry_run_failure(err: &anyhow::Error) -> String {
+    match err.downcast_ref::<CastInteropError>() {
+        Some(CastInteropError::Revert { reason, .. }) => format!("dry-run revert: {reason}"),
+        // eth_call already prefixes other failures with "dry-run failed".
+        _ => err.to_string(),
     }
-    let selector = &data[..4];
-    if selector == [0x08, 0xc3, 0x79, 0xa0] {
-        if let Ok(reason) = decode_error_string(&data[4..]) {
-            return Some(reason);
-        }
-    } else if selector == [0x4e, 0x48, 0x7b, 0x71] {
-        let code = U256::from_be_slice(&data[4..]);
-        return Some(format!("panic({code})"));
-    }
-    // selector to hex string
-    let selector_hex = hex::encode(selector);
-    error_selector_map()
-        .get(&selector_hex)
-        .map(|name| format!("revert: {}", name.to_string()))
-        .or_else(|| {
-            println!("unknown revert selector 0x{}", selector_hex);
-            None
-        })
 }
 
-/// Decode an ABI-encoded revert string payload.
-fn decode_error_string(data: &[u8]) -> Result<String> {
-    let value: (String,) = <(String,)>::abi_decode(data)?;
-    Ok(value.0)
+/// Decode a revert reason from an error string, if present.
+pub fn decode_revert_reason(message: String) -> Option<String> {
+    let data = revert_data_from_message(&message)?;
+    decode_revert(&data).map(|revert| revert.reason)
 }
 
 pub fn decode_send_transaction<T>(pending: TransportResult<T>) -> Result<T> {
     let pending = match pending {
         Ok(pending) => pending,
         Err(err) => {
-            if let Some(reason) = decode_revert_reason(err.to_string()) {
-                return Err(anyhow!("transaction submission reverted: {reason}"));
+            if let Some(revert) =
+                CastInteropError::revert("transaction submission", &err.to_string())
+            {
+                return Err(revert.into());
             } else {
                 return Err(anyhow!("transaction submission failed: {err}"));
             }
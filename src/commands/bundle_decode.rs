@@ -0,0 +1,68 @@
+use crate::abi::{decode_bundle, decode_bundle_strict, interop_bundle_hash};
+use crate::cli::BundleDecodeArgs;
+use crate::config::Config;
+use crate::output::{emit, read_stdin};
+use crate::types::{bytes_from_hex, format_hex, BundleExtractOutput};
+use anyhow::{Context, Result};
+
+/// Decode an encoded bundle offline and print its calls and attributes.
+///
+/// The output matches `bundle extract`, so no transaction or RPC is needed.
+#[tracing::instrument(name = "bundle decode", skip_all)]
+pub fn run(args: BundleDecodeArgs, config: Config) -> Result<()> {
+    let bytes = load_hex_or_path(&args.bundle)?;
+    let decode = if args.strict {
+        decode_bundle_strict
+    } else {
+        decode_bundle
+    };
+    let bundle = decode(&bytes).context("invalid encoded bundle")?;
+
+    let bundle_hash = interop_bundle_hash(bundle.sourceChainId, &bytes);
+    let output = BundleExtractOutput {
+        bundle_hash: format!("{bundle_hash:#x}"),
+        encoded_bundle_hex: format_hex(&bytes),
+        bundle: crate::abi::bundle_view(&bundle),
+    };
+
+    if emit(&output, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+
+    let view = &output.bundle;
+    println!("bundleHash: {}", output.bundle_hash);
+    println!("version: {}", view.version);
+    println!("sourceChainId: {}", view.source_chain_id);
+    println!("destinationChainId: {}", view.destination_chain_id);
+    println!("interopBundleSalt: {}", view.interop_bundle_salt);
+    println!(
+        "executionAddress: {}",
+        view.bundle_attributes.execution_address
+    );
+    println!(
+        "unbundlerAddress: {}",
+        view.bundle_attributes.unbundler_address
+    );
+    println!("calls: {}", view.calls.len());
+    for (index, call) in view.calls.iter().enumerate() {
+        println!("call[{index}]");
+        println!("  to: {}", call.to);
+        println!("  from: {}", call.from);
+        println!("  value: {}", call.value);
+        println!("  shadowAccount: {}", call.shadow_account);
+        println!("  data: {}", call.data);
+    }
+    Ok(())
+}
+
+/// Load a bundle hex string from inline text, a file, or stdin (`-`).
+pub(crate) fn load_hex_or_path(value: &str) -> Result<Vec<u8>> {
+    if value == "-" {
+        return bytes_from_hex(&read_stdin("--bundle")?).map(|bytes| bytes.0.to_vec());
+    }
+    if std::path::Path::new(value).exists() {
+        let contents = std::fs::read_to_string(value)?;
+        return bytes_from_hex(&contents).map(|bytes| bytes.0.to_vec());
+    }
+    bytes_from_hex(value).map(|bytes| bytes.0.to_vec())
+}
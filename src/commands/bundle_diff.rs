@@ -0,0 +1,134 @@
+use crate::abi::{bundle_view, decode_bundle, interop_bundle_hash};
+use crate::cli::BundleDiffArgs;
+use crate::commands::bundle_decode::load_hex_or_path;
+use crate::config::Config;
+use crate::output::emit;
+use crate::types::{BundleDiffOutput, FieldDiff, InteropBundleView, InteropCallView};
+use anyhow::{Context, Result};
+
+/// Decode two bundles offline and report the fields that differ.
+///
+/// Fails once the report is printed when anything differs, so scripts can
+/// use the exit code alone.
+#[tracing::instrument(name = "bundle diff", skip_all)]
+pub fn run(args: BundleDiffArgs, config: Config) -> Result<()> {
+    let bytes_a = load_hex_or_path(&args.a).context("failed to load --a")?;
+    let bytes_b = load_hex_or_path(&args.b).context("failed to load --b")?;
+    let bundle_a = decode_bundle(&bytes_a).context("invalid encoded bundle in --a")?;
+    let bundle_b = decode_bundle(&bytes_b).context("invalid encoded bundle in --b")?;
+
+    let mut differences = diff_bundles(&bundle_view(&bundle_a), &bundle_view(&bundle_b));
+    // Equal fields can still encode differently, e.g. with trailing bytes.
+    if differences.is_empty() && bytes_a != bytes_b {
+        differences.push(FieldDiff {
+            field: "encodedBundle".to_string(),
+            a: Some(format!("{} bytes", bytes_a.len())),
+            b: Some(format!("{} bytes", bytes_b.len())),
+        });
+    }
+
+    let output = BundleDiffOutput {
+        bundle_hash_a: format!(
+            "{:#x}",
+            interop_bundle_hash(bundle_a.sourceChainId, &bytes_a)
+        ),
+        bundle_hash_b: format!(
+            "{:#x}",
+            interop_bundle_hash(bundle_b.sourceChainId, &bytes_b)
+        ),
+        identical: differences.is_empty(),
+        differences,
+    };
+
+    if !emit(&output, config.json_output(), args.out.as_deref())? {
+        println!("bundleHash a: {}", output.bundle_hash_a);
+        println!("bundleHash b: {}", output.bundle_hash_b);
+        if output.identical {
+            println!("bundles are identical");
+        }
+        let side = |value: &Option<String>| value.clone().unwrap_or_else(|| "(none)".to_string());
+        for diff in &output.differences {
+            println!("{}", diff.field);
+            println!("  a: {}", side(&diff.a));
+            println!("  b: {}", side(&diff.b));
+        }
+    }
+    if !output.identical {
+        anyhow::bail!("bundles differ in {} field(s)", output.differences.len());
+    }
+    Ok(())
+}
+
+/// List the differing fields of two bundle views, named as in the JSON view
+/// (`calls[1].data`, `bundleAttributes.unbundlerAddress`, ...).
+fn diff_bundles(a: &InteropBundleView, b: &InteropBundleView) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+    let mut compare = |field: String, a: Option<&str>, b: Option<&str>| {
+        if a != b {
+            diffs.push(FieldDiff {
+                field,
+                a: a.map(str::to_string),
+                b: b.map(str::to_string),
+            });
+        }
+    };
+
+    compare("version".into(), Some(&a.version), Some(&b.version));
+    compare(
+        "sourceChainId".into(),
+        Some(&a.source_chain_id),
+        Some(&b.source_chain_id),
+    );
+    compare(
+        "destinationChainId".into(),
+        Some(&a.destination_chain_id),
+        Some(&b.destination_chain_id),
+    );
+    compare(
+        "interopBundleSalt".into(),
+        Some(&a.interop_bundle_salt),
+        Some(&b.interop_bundle_salt),
+    );
+    compare(
+        "bundleAttributes.executionAddress".into(),
+        Some(&a.bundle_attributes.execution_address),
+        Some(&b.bundle_attributes.execution_address),
+    );
+    compare(
+        "bundleAttributes.unbundlerAddress".into(),
+        Some(&a.bundle_attributes.unbundler_address),
+        Some(&b.bundle_attributes.unbundler_address),
+    );
+    compare(
+        "calls.length".into(),
+        Some(&a.calls.len().to_string()),
+        Some(&b.calls.len().to_string()),
+    );
+
+    for index in 0..a.calls.len().max(b.calls.len()) {
+        let call_a = a.calls.get(index).map(call_fields);
+        let call_b = b.calls.get(index).map(call_fields);
+        for (position, name) in CALL_FIELDS.iter().enumerate() {
+            compare(
+                format!("calls[{index}].{name}"),
+                call_a.as_ref().map(|fields| fields[position].as_str()),
+                call_b.as_ref().map(|fields| fields[position].as_str()),
+            );
+        }
+    }
+    diffs
+}
+
+const CALL_FIELDS: [&str; 6] = ["version", "shadowAccount", "to", "from", "value", "data"];
+
+/// A call's fields in `CALL_FIELDS` order.
+fn call_fields(call: &InteropCallView) -> [String; 6] {
+    [
+        call.version.clone(),
+        call.shadow_account.to_string(),
+        call.to.clone(),
+        call.from.clone(),
+        call.value.clone(),
+        call.data.clone(),
+    ]
+}
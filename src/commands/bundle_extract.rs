@@ -1,6 +1,9 @@
-use crate::abi::{decode_interop_bundle_sent, encode_interop_bundle, interop_bundle_sent_topic};
+use crate::abi::{decode_bundle, encode_interop_bundle};
 use crate::cli::BundleExtractArgs;
+use crate::commands::tx_show::print_bundle_tree;
 use crate::config::Config;
+use crate::interop::select_sent_bundle;
+use crate::output::emit;
 use crate::rpc::{get_transaction_receipt, RpcClient};
 use crate::types::{format_hex, AddressBook, BundleExtractOutput};
 use alloy_primitives::B256;
@@ -10,28 +13,22 @@ use std::str::FromStr;
 
 /// Extract an encoded bundle from an interop transaction.
 ///
-/// Scans for InteropBundleSent logs and prints/writes the encoded bundle.
+/// Picks an InteropBundleSent log (`--bundle-index`, else the first) and
+/// prints/writes the encoded bundle.
+#[tracing::instrument(name = "bundle extract", skip_all, fields(chain = args.rpc.chain.as_deref(), tx = %args.tx))]
 pub async fn run(args: BundleExtractArgs, config: Config, _addresses: AddressBook) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
     let tx_hash =
         B256::from_str(&args.tx).with_context(|| format!("invalid tx hash {}", args.tx))?;
     let receipt = get_transaction_receipt(&client, tx_hash).await?;
 
-    let mut found = None;
-    for log in receipt.logs() {
-        if log.topics().first().copied() == Some(interop_bundle_sent_topic()) {
-            let decoded = decode_interop_bundle_sent(log.data().data.clone())?;
-            found = Some(decoded);
-            break;
-        }
-    }
-
-    let Some((_, bundle_hash, bundle)) = found else {
-        anyhow::bail!("InteropBundleSent not found in receipt");
-    };
+    let (_, bundle_hash, bundle) = select_sent_bundle(&receipt, tx_hash, args.bundle_index)?;
 
     let encoded = encode_interop_bundle(&bundle);
+    // The written hex is read back by `bundle decode` and relay; make sure it
+    // passes the same decoder.
+    decode_bundle(&encoded).context("extracted bundle does not decode")?;
     let encoded_hex = format_hex(&encoded.0);
     let output = BundleExtractOutput {
         bundle_hash: format!("{bundle_hash:#x}"),
@@ -39,18 +36,13 @@ pub async fn run(args: BundleExtractArgs, config: Config, _addresses: AddressBoo
         bundle: crate::abi::bundle_view(&bundle),
     };
 
-    println!("encodedBundleHex: {}", encoded_hex);
-    println!("bundleHash: {bundle_hash:#x}");
-
     if let Some(path) = args.out {
         fs::write(path, encoded_hex)?;
     }
-    if let Some(path) = args.json_out {
-        fs::write(path, serde_json::to_string_pretty(&output)?)?;
-    }
-
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+    if !emit(&output, config.json_output(), args.json_out.as_deref())? {
+        println!("encodedBundleHex: {}", output.encoded_bundle_hex);
+        println!("bundleHash: {}", output.bundle_hash);
+        print_bundle_tree(&output.bundle);
     }
 
     Ok(())
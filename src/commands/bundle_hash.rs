@@ -0,0 +1,31 @@
+use crate::abi::{decode_bundle_strict, interop_bundle_hash};
+use crate::cli::BundleHashArgs;
+use crate::commands::bundle_decode::load_hex_or_path;
+use crate::config::Config;
+use crate::output::emit;
+use crate::types::{u256_to_string, BundleHashOutput};
+use anyhow::{Context, Result};
+
+/// Compute the interopBundleHash of an encoded bundle offline.
+///
+/// The interop center emits `keccak256(abi.encode(block.chainid, bundleBytes))`,
+/// where `bundleBytes` is `abi.encode(bundle)`. The chain id is the bundle's
+/// own `sourceChainId`, so the hash needs nothing but the bundle. Trailing or
+/// prefixed bytes would change the hash, so the bundle is decoded strictly.
+#[tracing::instrument(name = "bundle hash", skip_all)]
+pub fn run(args: BundleHashArgs, config: Config) -> Result<()> {
+    let bytes = load_hex_or_path(&args.bundle)?;
+    let bundle = decode_bundle_strict(&bytes).context("invalid encoded bundle")?;
+    let bundle_hash = interop_bundle_hash(bundle.sourceChainId, &bytes);
+
+    let output = BundleHashOutput {
+        bundle_hash: format!("{bundle_hash:#x}"),
+        source_chain_id: u256_to_string(bundle.sourceChainId),
+        destination_chain_id: u256_to_string(bundle.destinationChainId),
+    };
+    if emit(&output, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+    println!("{bundle_hash:#x}");
+    Ok(())
+}
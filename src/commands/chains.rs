@@ -1,11 +1,17 @@
-use crate::cli::{ChainsAddArgs, ChainsListArgs, ChainsRemoveArgs};
-use crate::config::{ChainConfig, Config};
+use crate::cli::{ChainsAddArgs, ChainsListArgs, ChainsRemoveArgs, ChainsUpdateArgs};
+use crate::config::{expand_env, ChainConfig, Config};
+use crate::output::emit;
 use crate::rpc::RpcClient;
 use crate::types::AddressBook;
 use alloy_provider::Provider;
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result};
+use reqwest::header::HeaderMap;
 use serde::Serialize;
 use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Upper bound for the optional RPC cross-check of `chains add --chain-id`.
+const OFFLINE_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -13,9 +19,13 @@ struct ChainListItem {
     alias: String,
     rpc: String,
     chain_id: Option<String>,
+    /// Whether the RPC answered eth_chainId; unset when it was not probed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reachable: Option<bool>,
 }
 
 /// List configured chain aliases and their RPC URLs.
+#[tracing::instrument(name = "chains list", skip_all)]
 pub async fn run_list(args: ChainsListArgs, config: Config, _addresses: AddressBook) -> Result<()> {
     let mut items = Vec::new();
 
@@ -25,16 +35,24 @@ pub async fn run_list(args: ChainsListArgs, config: Config, _addresses: AddressB
     }
 
     for (alias, cfg) in chains {
-        let chain_id = probe_chain_id(&cfg).await.ok().or(cfg.chain_id);
+        let probed = probe_chain_id(&cfg, config.rpc_timeout, &config.rpc_headers).await;
+        if let Err(err) = &probed {
+            eprintln!("warning: rpc for {alias} is unreachable: {err:#}");
+        }
         items.push(ChainListItem {
-            alias,
             rpc: redact_url(&cfg.rpc),
-            chain_id: chain_id.map(|id| id.to_string()),
+            chain_id: probed
+                .as_ref()
+                .ok()
+                .copied()
+                .or(cfg.chain_id)
+                .map(|id| id.to_string()),
+            reachable: Some(probed.is_ok()),
+            alias,
         });
     }
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&items)?);
+    if emit(&items, config.json_output(), args.out.as_deref())? {
         return Ok(());
     }
 
@@ -46,26 +64,40 @@ pub async fn run_list(args: ChainsListArgs, config: Config, _addresses: AddressB
     println!("{:<12} {:<10} {}", "alias", "chainId", "rpc");
     for item in items {
         let chain_id = item.chain_id.unwrap_or_else(|| "unknown".to_string());
-        println!("{:<12} {:<10} {}", item.alias, chain_id, item.rpc);
+        let marker = if item.reachable == Some(false) {
+            "  (!) unreachable"
+        } else {
+            ""
+        };
+        println!("{:<12} {:<10} {}{marker}", item.alias, chain_id, item.rpc);
     }
 
     Ok(())
 }
 
 /// Add a chain alias by probing the chain ID from the RPC URL.
+#[tracing::instrument(name = "chains add", skip_all, fields(chain = %args.alias))]
 pub async fn run_add(
     args: ChainsAddArgs,
     mut config: Config,
     _addresses: AddressBook,
 ) -> Result<()> {
     let rpc = args.rpc.trim();
-    let client = RpcClient::new(rpc).await?;
-    let chain_id = client
-        .provider
-        .get_chain_id()
-        .await
-        .context("failed to fetch eth_chainId")?;
-    let chain_id = u64::try_from(chain_id).map_err(|_| anyhow!("chainId too large"))?;
+    let chain_id = match args.chain_id {
+        Some(chain_id) => {
+            // Best-effort cross-check only; the RPC may not be up yet.
+            let timeout = config.rpc_timeout.min(OFFLINE_CHECK_TIMEOUT);
+            if let Ok(probed) = probe_rpc_chain_id(rpc, timeout, &config.rpc_headers).await {
+                if probed != chain_id {
+                    eprintln!(
+                        "warning: --chain-id {chain_id} differs from the RPC's chainId {probed}; storing {chain_id}"
+                    );
+                }
+            }
+            chain_id
+        }
+        None => probe_rpc_chain_id(rpc, config.rpc_timeout, &config.rpc_headers).await?,
+    };
 
     config.set_chain(args.alias.clone(), rpc.to_string(), chain_id);
     config.save()?;
@@ -77,7 +109,52 @@ pub async fn run_add(
     Ok(())
 }
 
+/// Change the RPC URL of an existing chain alias.
+///
+/// The chain ID is re-probed from the new RPC unless --no-probe is set.
+#[tracing::instrument(name = "chains update", skip_all, fields(chain = %args.alias))]
+pub async fn run_update(
+    args: ChainsUpdateArgs,
+    mut config: Config,
+    _addresses: AddressBook,
+) -> Result<()> {
+    if config.chain(&args.alias).is_none() {
+        anyhow::bail!(
+            "chain alias not found: {} (use chains add to create it)",
+            args.alias
+        );
+    }
+    let rpc = args.rpc.trim();
+    let chain_id = if args.no_probe {
+        None
+    } else {
+        Some(probe_rpc_chain_id(rpc, config.rpc_timeout, &config.rpc_headers).await?)
+    };
+
+    let chain = config
+        .update_chain(&args.alias, rpc.to_string(), chain_id)
+        .expect("alias checked above");
+    let item = ChainListItem {
+        alias: args.alias.clone(),
+        rpc: redact_url(&chain.rpc),
+        chain_id: chain.chain_id.map(|id| id.to_string()),
+        reachable: chain_id.map(|_| true),
+    };
+    config.save()?;
+
+    if emit(&item, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+    let chain_id = item.chain_id.unwrap_or_else(|| "unknown".to_string());
+    println!(
+        "updated chain {alias} (chainId {chain_id})",
+        alias = item.alias
+    );
+    Ok(())
+}
+
 /// Remove a chain alias from the configuration file.
+#[tracing::instrument(name = "chains rm", skip_all, fields(chain = %args.alias))]
 pub async fn run_remove(
     args: ChainsRemoveArgs,
     mut config: Config,
@@ -101,6 +178,7 @@ fn legacy_chains(config: &Config) -> BTreeMap<String, ChainConfig> {
                 ChainConfig {
                     rpc: url.clone(),
                     chain_id: None,
+                    fallback_rpcs: Vec::new(),
                 },
             );
         }
@@ -110,6 +188,7 @@ fn legacy_chains(config: &Config) -> BTreeMap<String, ChainConfig> {
                 ChainConfig {
                     rpc: url.clone(),
                     chain_id: None,
+                    fallback_rpcs: Vec::new(),
                 },
             );
         }
@@ -119,6 +198,7 @@ fn legacy_chains(config: &Config) -> BTreeMap<String, ChainConfig> {
                 ChainConfig {
                     rpc: url.clone(),
                     chain_id: None,
+                    fallback_rpcs: Vec::new(),
                 },
             );
         }
@@ -127,14 +207,29 @@ fn legacy_chains(config: &Config) -> BTreeMap<String, ChainConfig> {
 }
 
 /// Probe the chain ID from an RPC URL for display purposes.
-async fn probe_chain_id(cfg: &ChainConfig) -> Result<u64> {
-    let client = RpcClient::new(&cfg.rpc).await?;
-    let chain = client.provider.get_chain_id().await?;
+async fn probe_chain_id(cfg: &ChainConfig, timeout: Duration, headers: &HeaderMap) -> Result<u64> {
+    let client = RpcClient::new(&cfg.urls()?, timeout, headers).await?;
+    let chain = client.chain_id().await?;
     Ok(chain)
 }
 
+/// Fetch the chain ID behind a single RPC URL (after `${VAR}` expansion).
+async fn probe_rpc_chain_id(rpc: &str, timeout: Duration, headers: &HeaderMap) -> Result<u64> {
+    let client = RpcClient::new(&[expand_env(rpc)?], timeout, headers).await?;
+    client
+        .provider
+        .get_chain_id()
+        .await
+        .context("failed to fetch eth_chainId")
+}
+
 /// Redact credentials from a URL string for display.
 fn redact_url(value: &str) -> String {
+    // Unexpanded `${VAR}` templates carry no secrets and would be mangled
+    // by URL parsing.
+    if value.contains("${") {
+        return value.to_string();
+    }
     match url::Url::parse(value) {
         Ok(mut parsed) => {
             let has_user = !parsed.username().is_empty();
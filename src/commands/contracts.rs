@@ -1,9 +1,11 @@
 use crate::cli::ContractsArgs;
 use crate::config::Config;
-use crate::rpc::RpcClient;
+use crate::output::emit;
+use crate::rpc::{parse_block_id, RpcClient};
 use crate::types::{address_to_hex, AddressBook};
 use alloy_primitives::Address;
 use alloy_provider::Provider;
+use alloy_rpc_types::BlockId;
 use anyhow::Result;
 use serde::Serialize;
 use std::path::PathBuf;
@@ -21,10 +23,12 @@ struct ContractRow {
 /// Inspect interop contract addresses and deployment status.
 ///
 /// Reports deployed bytecode length and ABI availability.
+#[tracing::instrument(name = "debug contracts", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run(args: ContractsArgs, config: Config, addresses: AddressBook) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
 
+    let block = args.block.as_deref().map(parse_block_id).transpose()?;
     let abi_dir = config.abi_dir();
     let mut rows = Vec::new();
     rows.push(
@@ -32,6 +36,7 @@ pub async fn run(args: ContractsArgs, config: Config, addresses: AddressBook) ->
             "interop_center",
             addresses.interop_center,
             &client,
+            block,
             &abi_dir,
             "InteropCenter.json",
         )
@@ -42,6 +47,7 @@ pub async fn run(args: ContractsArgs, config: Config, addresses: AddressBook) ->
             "interop_handler",
             addresses.interop_handler,
             &client,
+            block,
             &abi_dir,
             "InteropHandler.json",
         )
@@ -52,14 +58,14 @@ pub async fn run(args: ContractsArgs, config: Config, addresses: AddressBook) ->
             "interop_root_storage",
             addresses.interop_root_storage,
             &client,
+            block,
             &abi_dir,
             "MessageVerification.json",
         )
         .await?,
     );
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&rows)?);
+    if emit(&rows, config.json_output(), args.out.as_deref())? {
         return Ok(());
     }
 
@@ -91,10 +97,15 @@ async fn build_row(
     name: &str,
     address: Address,
     client: &RpcClient,
+    block: Option<BlockId>,
     abi_dir: &PathBuf,
     abi_file: &str,
 ) -> Result<ContractRow> {
-    let code = client.provider.get_code_at(address).await?;
+    let code = client
+        .provider
+        .get_code_at(address)
+        .block_id(block.unwrap_or_default())
+        .await?;
     let code_len = code.len() as u64;
     let deployed = code_len > 0;
     let abi_found = abi_dir.join(abi_file).exists();
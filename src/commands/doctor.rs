@@ -1,7 +1,12 @@
+use crate::abi::{decode_bytes32, encode_interop_roots_call};
 use crate::cli::DoctorArgs;
+use crate::commands::token::format_units;
 use crate::config::Config;
-use crate::rpc::{get_finalized_block_number, raw_rpc, RpcClient};
-use crate::types::{address_to_hex, AddressBook};
+use crate::output::{emit, status_icon};
+use crate::rpc::{eth_call, get_finalized_block_number, raw_rpc, RpcClient};
+use crate::signer::{load_signer, signer_address, SignerOptions};
+use crate::types::{address_to_hex, AddressBook, ChecksFailed};
+use alloy_primitives::U256;
 use alloy_provider::Provider;
 use anyhow::Result;
 use serde::Serialize;
@@ -14,22 +19,35 @@ struct DoctorCheck {
     status: String,
     details: String,
     hint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signer: Option<SignerBalance>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignerBalance {
+    address: String,
+    balance_wei: String,
 }
 
 /// Run a diagnostic checklist for RPC and contract readiness.
 ///
-/// Reports RPC reachability, log proof support, and ABI availability.
+/// Reports RPC reachability, log proof support, ABI availability, and the
+/// signer's gas balance when a signer is configured.
+#[tracing::instrument(name = "debug doctor", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run(args: DoctorArgs, config: Config, addresses: AddressBook) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
 
     let mut checks = Vec::new();
-    let client = match RpcClient::new(&resolved.url).await {
+    let client = match RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await
+    {
         Ok(client) => {
             checks.push(DoctorCheck {
                 name: "rpc_reachable".to_string(),
                 status: "ok".to_string(),
                 details: "RPC reachable".to_string(),
                 hint: None,
+                signer: None,
             });
             client
         }
@@ -39,23 +57,26 @@ pub async fn run(args: DoctorArgs, config: Config, addresses: AddressBook) -> Re
                 status: "fail".to_string(),
                 details: format!("RPC not reachable: {err}"),
                 hint: Some("Check the RPC URL or network connectivity.".to_string()),
+                signer: None,
             });
-            return output_checks(args.json, checks);
+            return output_checks(&config, &args, checks);
         }
     };
 
-    match client.provider.get_chain_id().await {
+    match client.chain_id().await {
         Ok(chain_id) => checks.push(DoctorCheck {
             name: "eth_chainId".to_string(),
             status: "ok".to_string(),
             details: format!("chainId {chain_id}"),
             hint: None,
+            signer: None,
         }),
         Err(err) => checks.push(DoctorCheck {
             name: "eth_chainId".to_string(),
             status: "fail".to_string(),
             details: format!("eth_chainId failed: {err}"),
             hint: Some("Ensure the RPC URL points to an EVM-compatible endpoint.".to_string()),
+            signer: None,
         }),
     };
 
@@ -65,12 +86,14 @@ pub async fn run(args: DoctorArgs, config: Config, addresses: AddressBook) -> Re
             status: "ok".to_string(),
             details: format!("finalized block {block}"),
             hint: None,
+            signer: None,
         }),
         Err(err) => checks.push(DoctorCheck {
             name: "finalized_block".to_string(),
             status: "warn".to_string(),
             details: format!("finalized block not supported: {err}"),
             hint: Some("Use a zkSync RPC or one that supports finalized blocks.".to_string()),
+            signer: None,
         }),
     };
 
@@ -89,20 +112,16 @@ pub async fn run(args: DoctorArgs, config: Config, addresses: AddressBook) -> Re
             status: "ok".to_string(),
             details: "zks_getL2ToL1LogProof reachable".to_string(),
             hint: None,
+            signer: None,
         }),
         Err(err) => {
             let message = err.to_string();
-            let status =
-                if message.contains("Method not found") || message.contains("method not found") {
-                    "warn"
-                } else {
-                    "warn"
-                };
             checks.push(DoctorCheck {
                 name: "get_log_proof".to_string(),
-                status: status.to_string(),
+                status: "warn".to_string(),
                 details: format!("log proof call failed: {message}"),
                 hint: Some("RPC must support zks_getL2ToL1LogProof to fetch proofs.".to_string()),
+                signer: None,
             });
         }
     }
@@ -128,7 +147,112 @@ pub async fn run(args: DoctorArgs, config: Config, addresses: AddressBook) -> Re
         .await,
     );
 
-    output_checks(args.json, checks)
+    checks.push(check_interop_roots(addresses.interop_root_storage, &client).await);
+
+    if let Some(check) = check_signer_balance(&args, &config, &client).await {
+        checks.push(check);
+    }
+
+    output_checks(&config, &args, checks)
+}
+
+/// Call `interopRoots(0, 0)` on root storage to confirm it answers.
+///
+/// A zero root is fine; a revert or undecodable result usually means the
+/// address points at the wrong contract.
+async fn check_interop_roots(
+    root_storage: alloy_primitives::Address,
+    client: &RpcClient,
+) -> DoctorCheck {
+    let data = encode_interop_roots_call(U256::ZERO, U256::ZERO);
+    match eth_call(client, root_storage, data)
+        .await
+        .and_then(decode_bytes32)
+    {
+        Ok(_) => DoctorCheck {
+            name: "interop_root_storage_call".to_string(),
+            status: "ok".to_string(),
+            details: format!(
+                "interopRoots(0, 0) answered at {}",
+                address_to_hex(root_storage)
+            ),
+            hint: None,
+            signer: None,
+        },
+        Err(err) => DoctorCheck {
+            name: "interop_root_storage_call".to_string(),
+            status: "warn".to_string(),
+            details: format!("interopRoots(0, 0) failed: {err}"),
+            hint: Some(
+                "Check addresses.interop_root_storage; relays will stall waiting for the root."
+                    .to_string(),
+            ),
+            signer: None,
+        },
+    }
+}
+
+/// Report the signer's native balance on the chain, warning when it is zero.
+///
+/// Returns None when no signer is configured.
+async fn check_signer_balance(
+    args: &DoctorArgs,
+    config: &Config,
+    client: &RpcClient,
+) -> Option<DoctorCheck> {
+    let signer = load_signer(
+        SignerOptions {
+            private_key: args.signer.private_key.as_deref(),
+            private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
+        },
+        config,
+    )
+    .await;
+    let address = match signer.and_then(|signer| signer.as_ref().map(signer_address).transpose()) {
+        Ok(Some(address)) => address,
+        Ok(None) => return None,
+        Err(err) => {
+            return Some(DoctorCheck {
+                name: "signer_balance".to_string(),
+                status: "fail".to_string(),
+                details: format!("failed to load signer: {err}"),
+                hint: Some("Check --private-key, --private-key-env, or --ledger.".to_string()),
+                signer: None,
+            })
+        }
+    };
+
+    let check = match client.provider.get_balance(address).await {
+        Ok(balance) => DoctorCheck {
+            name: "signer_balance".to_string(),
+            status: if balance.is_zero() { "warn" } else { "ok" }.to_string(),
+            details: format!(
+                "signer {} has {} ETH",
+                address_to_hex(address),
+                format_units(balance, 18)
+            ),
+            hint: balance
+                .is_zero()
+                .then(|| "Fund the signer before sending transactions on this chain.".to_string()),
+            signer: Some(SignerBalance {
+                address: address_to_hex(address),
+                balance_wei: balance.to_string(),
+            }),
+        },
+        Err(err) => DoctorCheck {
+            name: "signer_balance".to_string(),
+            status: "warn".to_string(),
+            details: format!(
+                "failed to fetch balance for {}: {err}",
+                address_to_hex(address)
+            ),
+            hint: None,
+            signer: None,
+        },
+    };
+    Some(check)
 }
 
 /// Check contract deployment and ABI presence for a named interop contract.
@@ -148,6 +272,7 @@ async fn check_contract(
                     status: "fail".to_string(),
                     details: format!("{name} not deployed at {}", address_to_hex(address)),
                     hint: Some("Check address overrides or network configuration.".to_string()),
+                    signer: None,
                 });
             } else {
                 checks.push(DoctorCheck {
@@ -155,6 +280,7 @@ async fn check_contract(
                     status: "ok".to_string(),
                     details: format!("{name} deployed at {}", address_to_hex(address)),
                     hint: None,
+                    signer: None,
                 });
             }
         }
@@ -164,6 +290,7 @@ async fn check_contract(
                 status: "warn".to_string(),
                 details: format!("failed to check code for {name}: {err}"),
                 hint: None,
+                signer: None,
             });
         }
     }
@@ -182,6 +309,7 @@ async fn check_contract(
             status: "ok".to_string(),
             details: format!("ABI found at {}", abi_path.display()),
             hint: None,
+            signer: None,
         });
     } else {
         checks.push(DoctorCheck {
@@ -189,30 +317,32 @@ async fn check_contract(
             status: "warn".to_string(),
             details: format!("ABI missing: {}", abi_path.display()),
             hint: Some("Ensure ABI files are present in the abi directory.".to_string()),
+            signer: None,
         });
     }
 
     checks
 }
 
-/// Render diagnostic checks as JSON or a readable list.
-fn output_checks(json: bool, checks: Vec<DoctorCheck>) -> Result<()> {
-    if json {
-        println!("{}", serde_json::to_string_pretty(&checks)?);
-        return Ok(());
+/// Render diagnostic checks as JSON or a readable list, then fail if any
+/// check failed (or warned, with `--fail-on-warn`).
+fn output_checks(config: &Config, args: &DoctorArgs, checks: Vec<DoctorCheck>) -> Result<()> {
+    if !emit(&checks, config.json_output(), args.out.as_deref())? {
+        print_checks(&checks, config.plain_icons);
     }
+    ChecksFailed::check(
+        checks.iter().map(|check| check.status.as_str()),
+        args.fail_on_warn,
+    )
+}
 
+/// Print each check with a status icon and its hint.
+fn print_checks(checks: &[DoctorCheck], plain: bool) {
     for check in checks {
-        let icon = match check.status.as_str() {
-            "ok" => "✅",
-            "warn" => "⚠️",
-            "fail" => "❌",
-            _ => "•",
-        };
+        let icon = status_icon(&check.status, plain);
         println!("{icon} {}: {}", check.name, check.details);
-        if let Some(hint) = check.hint {
+        if let Some(hint) = &check.hint {
             println!("  hint: {hint}");
         }
     }
-    Ok(())
 }
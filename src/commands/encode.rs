@@ -1,15 +1,28 @@
-use crate::cli::{Encode7930Args, EncodeAssetIdArgs, EncodeAttrsArgs};
+use crate::cli::{Decode7930Args, Encode7930Args, EncodeAssetIdArgs, EncodeAttrsArgs};
 use crate::config::Config;
 use crate::encode::{
-    encode_asset_id, encode_evm_v1_address_only, encode_evm_v1_chain_only,
-    encode_evm_v1_with_address, encode_execution_address, encode_indirect_call,
+    decode_attribute, decode_evm_v1_address, encode_asset_id, encode_evm_v1_address_only,
+    encode_evm_v1_chain_only, encode_evm_v1_chain_only_sized, encode_evm_v1_with_address,
+    encode_evm_v1_with_address_sized, encode_execution_address, encode_indirect_call,
     encode_interop_call_value, encode_unbundler_address, parse_permissionless_address,
-    DEFAULT_NATIVE_TOKEN_VAULT,
+    EVM_V1_ADDRESS_ONLY_HEADER,
+};
+use crate::output::emit;
+use crate::types::{
+    address_to_hex, bytes_from_hex, format_hex, parse_checked_address, parse_u256,
+    parse_value_with_units, AddressBook,
 };
-use crate::types::{format_hex, parse_address, parse_u256, AddressBook};
 use alloy_primitives::Bytes;
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use serde::Serialize;
+use std::fs;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AssetIdOutput {
+    token: String,
+    asset_id: String,
+}
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -17,9 +30,31 @@ struct EncodeAttrsOutput {
     attributes: Vec<String>,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DecodeAttrsOutput {
+    attributes: Vec<DecodedAttrOutput>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DecodedAttrOutput {
+    name: &'static str,
+    value: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Decode7930Output {
+    chain_id: Option<String>,
+    address: Option<String>,
+    form: &'static str,
+}
+
 /// Encode ERC-7930 bytes from chain and address inputs.
 ///
 /// Use --chain-id with optional --address, or --address-only.
+#[tracing::instrument(name = "encode 7930", skip_all)]
 pub async fn run_7930(
     args: Encode7930Args,
     _config: Config,
@@ -29,15 +64,21 @@ pub async fn run_7930(
         if args.chain_id.is_some() || args.address.is_some() {
             anyhow::bail!("--address-only cannot be combined with --chain-id or --address");
         }
-        let address = parse_address(&address_only)?;
+        let address = parse_checked_address(&address_only)?;
         encode_evm_v1_address_only(address)
     } else if let (Some(chain_id), Some(address)) = (args.chain_id.clone(), args.address) {
         let chain_id = parse_u256(&chain_id)?;
-        let address = parse_address(&address)?;
-        encode_evm_v1_with_address(chain_id, address)
+        let address = parse_checked_address(&address)?;
+        match args.chain_ref_len {
+            Some(len) => encode_evm_v1_with_address_sized(chain_id, address, len)?,
+            None => encode_evm_v1_with_address(chain_id, address),
+        }
     } else if let Some(chain_id) = args.chain_id {
         let chain_id = parse_u256(&chain_id)?;
-        encode_evm_v1_chain_only(chain_id)
+        match args.chain_ref_len {
+            Some(len) => encode_evm_v1_chain_only_sized(chain_id, len)?,
+            None => encode_evm_v1_chain_only(chain_id),
+        }
     } else {
         anyhow::bail!("set --chain-id (with optional --address) or --address-only");
     };
@@ -46,21 +87,57 @@ pub async fn run_7930(
     Ok(())
 }
 
+/// Decode ERC-7930 bytes into chain ID and address.
+///
+/// Reports whether the bytes carry both parts, only a chain, or only an address.
+#[tracing::instrument(name = "encode decode-7930", skip_all)]
+pub async fn run_decode_7930(
+    args: Decode7930Args,
+    config: Config,
+    _addresses: AddressBook,
+) -> Result<()> {
+    let bytes = bytes_from_hex(&args.bytes)?;
+    let (chain_id, address) = decode_evm_v1_address(&bytes)?;
+    let address_only = address.is_some() && bytes.starts_with(&EVM_V1_ADDRESS_ONLY_HEADER);
+    let output = Decode7930Output {
+        chain_id: (!address_only).then(|| chain_id.to_string()),
+        address: address.map(address_to_hex),
+        form: match (address_only, address.is_some()) {
+            (true, _) => "address-only",
+            (false, true) => "chain-and-address",
+            (false, false) => "chain-only",
+        },
+    };
+
+    if !emit(&output, config.json_output(), args.out.as_deref())? {
+        println!("form: {}", output.form);
+        println!("chainId: {}", output.chain_id.as_deref().unwrap_or("-"));
+        println!("address: {}", output.address.as_deref().unwrap_or("-"));
+    }
+    Ok(())
+}
+
 /// Encode interop attribute calldata for messages or bundles.
 ///
-/// Outputs the ABI-encoded attribute list as hex strings.
+/// Outputs the ABI-encoded attribute list as hex strings, or decodes
+/// attributes given with --decode.
+#[tracing::instrument(name = "encode attrs", skip_all)]
 pub async fn run_attrs(
     args: EncodeAttrsArgs,
-    _config: Config,
+    config: Config,
     _addresses: AddressBook,
 ) -> Result<()> {
+    if !args.decode.is_empty() {
+        return decode_attrs(&args, config.json_output());
+    }
+
     let mut attributes: Vec<Bytes> = Vec::new();
     if let Some(value) = args.interop_value {
-        let parsed = parse_u256(&value)?;
+        let parsed = parse_value_with_units(&value)?;
         attributes.push(encode_interop_call_value(parsed));
     }
     if let Some(value) = args.indirect {
-        let parsed = parse_u256(&value)?;
+        let parsed = parse_value_with_units(&value)?;
         attributes.push(encode_indirect_call(parsed));
     }
     if let Some(value) = args.execution_address {
@@ -71,7 +148,7 @@ pub async fn run_attrs(
         attributes.push(encode_execution_address(encoded));
     }
     if let Some(value) = args.unbundler {
-        let addr = parse_address(&value)?;
+        let addr = parse_checked_address(&value)?;
         attributes.push(encode_unbundler_address(encode_evm_v1_address_only(addr)));
     }
 
@@ -82,9 +159,7 @@ pub async fn run_attrs(
             .collect(),
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
-    } else {
+    if !emit(&output, config.json_output(), args.out.as_deref())? {
         for value in output.attributes {
             println!("{value}");
         }
@@ -92,23 +167,83 @@ pub async fn run_attrs(
     Ok(())
 }
 
-/// Compute the assetId hash for a token on a chain.
+/// Decode each --decode attribute and print its name and value.
+fn decode_attrs(args: &EncodeAttrsArgs, json: bool) -> Result<()> {
+    let mut attributes = Vec::new();
+    for value in &args.decode {
+        let bytes = bytes_from_hex(value)?;
+        let decoded = decode_attribute(&bytes)
+            .map_err(|err| anyhow!("failed to decode attribute {value}: {err}"))?;
+        attributes.push(DecodedAttrOutput {
+            name: decoded.name(),
+            value: decoded.value_string(),
+        });
+    }
+
+    let output = DecodeAttrsOutput { attributes };
+    if !emit(&output, json, args.out.as_deref())? {
+        for attribute in output.attributes {
+            println!("{}: {}", attribute.name, attribute.value);
+        }
+    }
+    Ok(())
+}
+
+/// Compute the assetId hash for one or more tokens on a chain.
 ///
-/// The assetId hashes chainId, vault, and token.
+/// The assetId hashes chainId, vault, and token. A single `--token` prints
+/// just the asset ID; several tokens (or `--token-file`) print a list.
+#[tracing::instrument(name = "encode asset-id", skip_all)]
 pub async fn run_asset_id(
     args: EncodeAssetIdArgs,
-    _config: Config,
-    _addresses: AddressBook,
+    config: Config,
+    addresses: AddressBook,
 ) -> Result<()> {
     let chain_id = parse_u256(&args.chain_id)?;
-    let token = parse_address(&args.token)?;
-    let vault = parse_address(
-        args.native_token_vault
-            .as_deref()
-            .unwrap_or(DEFAULT_NATIVE_TOKEN_VAULT),
-    )?;
-
-    let asset_id = encode_asset_id(chain_id, token, vault);
-    println!("{}", format_hex(asset_id.as_ref()));
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+    let mut tokens = args.token.clone();
+    if let Some(path) = args.token_file.as_deref() {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        tokens.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string),
+        );
+    }
+    if tokens.is_empty() {
+        anyhow::bail!("no tokens to encode (set --token or --token-file)");
+    }
+
+    let outputs = tokens
+        .iter()
+        .map(|token| {
+            let token = parse_checked_address(token)?;
+            Ok(AssetIdOutput {
+                token: address_to_hex(token),
+                asset_id: format_hex(encode_asset_id(chain_id, token, vault).as_ref()),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if emit(&outputs, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+    match outputs.as_slice() {
+        [output] if args.token_file.is_none() => println!("{}", output.asset_id),
+        _ => {
+            for output in &outputs {
+                println!("{} {}", output.token, output.asset_id);
+            }
+        }
+    }
     Ok(())
 }
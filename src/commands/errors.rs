@@ -0,0 +1,96 @@
+use crate::abi::{decode_error_with_params, decode_revert, known_errors};
+use crate::cli::ErrorsArgs;
+use crate::config::Config;
+use crate::output::emit;
+use crate::types::{bytes_from_hex, format_hex};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+/// Solidity's built-in `Error(string)` and `Panic(uint256)` reverts.
+const BUILTIN_ERRORS: [([u8; 4], &str); 2] = [
+    ([0x08, 0xc3, 0x79, 0xa0], "Error(string)"),
+    ([0x4e, 0x48, 0x7b, 0x71], "Panic(uint256)"),
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct KnownError {
+    selector: String,
+    name: String,
+    signature: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorLookup {
+    #[serde(flatten)]
+    error: KnownError,
+    /// The error with its parameters, when `--data` was given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decoded: Option<String>,
+}
+
+/// Print the known error selectors, or look up `--selector` and decode
+/// `--data` as its parameters.
+#[tracing::instrument(name = "debug errors", skip_all)]
+pub fn run(args: ErrorsArgs, config: Config) -> Result<()> {
+    let table: Vec<KnownError> = BUILTIN_ERRORS
+        .into_iter()
+        .chain(known_errors())
+        .map(|(selector, signature)| KnownError {
+            selector: format_hex(&selector),
+            name: signature.split('(').next().unwrap_or(signature).to_string(),
+            signature,
+        })
+        .collect();
+
+    let Some(selector) = args.selector.as_deref() else {
+        if emit(&table, config.json_output(), args.out.as_deref())? {
+            return Ok(());
+        }
+        for error in &table {
+            println!("{}  {}", error.selector, error.signature);
+        }
+        return Ok(());
+    };
+
+    let selector = bytes_from_hex(selector)?;
+    if selector.len() != 4 {
+        anyhow::bail!(
+            "--selector must be 4 bytes, got {}; pass the rest of the revert data as --data",
+            selector.len()
+        );
+    }
+    let selector_hex = format_hex(&selector);
+    let error = table
+        .into_iter()
+        .find(|error| error.selector == selector_hex)
+        .ok_or_else(|| anyhow!("unknown error selector {selector_hex}"))?;
+
+    let decoded = match args.data.as_deref() {
+        Some(data) => {
+            let revert = [selector.as_ref(), bytes_from_hex(data)?.as_ref()].concat();
+            let decoded = if BUILTIN_ERRORS
+                .iter()
+                .any(|(known, _)| known[..] == selector[..])
+            {
+                decode_revert(&revert).map(|revert| revert.reason)
+            } else {
+                decode_error_with_params(&revert)
+            };
+            Some(decoded.ok_or_else(|| anyhow!("--data does not decode as {}", error.signature))?)
+        }
+        None => None,
+    };
+
+    let output = ErrorLookup { error, decoded };
+    if emit(&output, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+    println!("selector: {}", output.error.selector);
+    println!("signature: {}", output.error.signature);
+    if let Some(decoded) = &output.decoded {
+        println!("decoded: {decoded}");
+    }
+    Ok(())
+}
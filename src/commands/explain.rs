@@ -1,12 +1,20 @@
+use crate::abi::{
+    decode_bundle, decode_bundle_strict, encode_execute_bundle_call, encode_verify_bundle_call,
+};
 use crate::cli::ExplainArgs;
+use crate::commands::bundle_action::decode_revert_reason;
 use crate::config::Config;
 use crate::encode::decode_evm_v1_address;
+use crate::output::{emit, read_stdin, status_icon};
 use crate::rpc::RpcClient;
 use crate::signer::{load_signer, signer_address, SignerOptions};
-use crate::types::{AddressBook, MessageInclusionProof};
-use alloy_dyn_abi::SolType;
+use crate::types::{
+    bundle_message, format_hex, parse_checked_address, AddressBook, ChecksFailed,
+    MessageInclusionProof,
+};
 use alloy_primitives::{Address, Bytes, U256};
 use alloy_provider::Provider;
+use alloy_rpc_types::{TransactionInput, TransactionRequest};
 use anyhow::{anyhow, Context, Result};
 use serde::Serialize;
 use std::fs;
@@ -14,54 +22,65 @@ use std::path::Path;
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ExplainItem {
-    check: String,
-    status: String,
-    details: String,
+pub(crate) struct ExplainItem {
+    pub(crate) check: String,
+    /// "ok", "warn" or "fail".
+    pub(crate) status: String,
+    pub(crate) details: String,
 }
 
 /// Explain why a bundle proof would succeed or fail.
 ///
-/// Performs checks on sender, chain IDs, and permissions for the signer.
+/// Performs checks on sender, chain IDs, and permissions for the signer,
+/// and with --simulate runs the handler call itself via eth_call.
+#[tracing::instrument(name = "bundle explain", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run(args: ExplainArgs, config: Config, addresses: AddressBook) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
-    let chain_id = client.provider.get_chain_id().await?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+    let chain_id = client.chain_id().await?;
 
     let bundle_bytes = load_hex_or_path(&args.bundle)?;
-    let bundle: crate::types::InteropBundle =
-        crate::types::InteropBundle::abi_decode(&bundle_bytes)
-            .context("failed to decode bundle")?;
+    let decode = if args.strict {
+        decode_bundle_strict
+    } else {
+        decode_bundle
+    };
+    let bundle = decode(&bundle_bytes).context("failed to decode bundle")?;
     let proof = load_proof(&args.proof)?;
+    proof.validate()?;
 
     let signer = load_signer(
         SignerOptions {
             private_key: args.signer.private_key.as_deref(),
             private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
         },
         &config,
-    )?;
+    )
+    .await?;
 
-    let mut checks = Vec::new();
-    checks.push(check_sender(&proof, addresses.interop_center));
-    checks.push(check_destination_chain(&bundle, chain_id));
-    checks.push(check_source_chain(&bundle, &proof));
+    let message = bundle_message(config.bundle_identifier, &bundle_bytes);
+    let mut checks = vec![
+        check_sender(&proof, addresses.interop_center),
+        check_message(&proof, &message),
+        check_destination_chain(&bundle, chain_id),
+        check_source_chain(&bundle, &proof),
+    ];
 
-    if let Some(signer) = signer {
-        let signer_addr = signer_address(&signer)?;
+    let signer_addr = signer.as_ref().map(signer_address).transpose()?;
+    if let Some(signer_addr) = signer_addr {
         checks.push(check_permissions(
-            &bundle,
+            &bundle.bundleAttributes.executionAddress,
             signer_addr,
             chain_id,
             "executionAddress",
-            |b| &b.bundleAttributes.executionAddress,
         ));
         checks.push(check_permissions(
-            &bundle,
+            &bundle.bundleAttributes.unbundlerAddress,
             signer_addr,
             chain_id,
             "unbundlerAddress",
-            |b| &b.bundleAttributes.unbundlerAddress,
         ));
     } else {
         checks.push(ExplainItem {
@@ -71,21 +90,39 @@ pub async fn run(args: ExplainArgs, config: Config, addresses: AddressBook) -> R
         });
     }
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&checks)?);
-        return Ok(());
+    if args.simulate {
+        let handler = match args.handler.as_deref() {
+            Some(value) => parse_checked_address(value).context("invalid handler address")?,
+            None => addresses.interop_handler,
+        };
+        // Normalize the proof the same way `bundle verify`/`bundle execute`
+        // do, so the result matches what those commands would hit on-chain.
+        let mut proof = proof;
+        proof.message.sender = format!("{:#x}", addresses.interop_center);
+        proof.message.data = format_hex(&message);
+        checks.push(
+            simulate_handler_call(
+                &client,
+                handler,
+                signer_addr,
+                &args.mode,
+                bundle_bytes,
+                proof,
+            )
+            .await?,
+        );
     }
 
-    for check in checks {
-        let icon = match check.status.as_str() {
-            "ok" => "✅",
-            "warn" => "⚠️",
-            "fail" => "❌",
-            _ => "•",
-        };
-        println!("{icon} {}: {}", check.check, check.details);
+    if !emit(&checks, config.json_output(), args.out.as_deref())? {
+        for check in &checks {
+            let icon = status_icon(&check.status, config.plain_icons);
+            println!("{icon} {}: {}", check.check, check.details);
+        }
     }
-    Ok(())
+    ChecksFailed::check(
+        checks.iter().map(|check| check.status.as_str()),
+        args.fail_on_warn,
+    )
 }
 
 /// Check whether the proof sender matches the interop center.
@@ -107,6 +144,22 @@ fn check_sender(proof: &MessageInclusionProof, center: Address) -> ExplainItem {
     }
 }
 
+/// Check that the proof was fetched for this bundle's message.
+fn check_message(proof: &MessageInclusionProof, message: &[u8]) -> ExplainItem {
+    match proof.check_message(message) {
+        Ok(()) => ExplainItem {
+            check: "proof.message".to_string(),
+            status: "ok".to_string(),
+            details: "proof message matches bundle".to_string(),
+        },
+        Err(err) => ExplainItem {
+            check: "proof.message".to_string(),
+            status: "fail".to_string(),
+            details: format!("{err:#}"),
+        },
+    }
+}
+
 /// Verify the bundle destination chain matches the current chain.
 fn check_destination_chain(bundle: &crate::types::InteropBundle, chain_id: u64) -> ExplainItem {
     if bundle.destinationChainId == U256::from(chain_id) {
@@ -151,18 +204,14 @@ fn check_source_chain(
     }
 }
 
-/// Verify execution/unbundler permissions for the signer.
-fn check_permissions<F>(
-    bundle: &crate::types::InteropBundle,
+/// Verify an ERC-7930 execution/unbundler permission value for the signer;
+/// empty means permissionless.
+pub(crate) fn check_permissions(
+    bytes: &Bytes,
     signer: Address,
     chain_id: u64,
     label: &str,
-    accessor: F,
-) -> ExplainItem
-where
-    F: Fn(&crate::types::InteropBundle) -> &Bytes,
-{
-    let bytes = accessor(bundle);
+) -> ExplainItem {
     if bytes.is_empty() {
         return ExplainItem {
             check: label.to_string(),
@@ -198,8 +247,51 @@ where
     }
 }
 
-/// Load a hex string from inline input or a file path.
+/// Run the verify/execute call against the handler with eth_call.
+async fn simulate_handler_call(
+    client: &RpcClient,
+    handler: Address,
+    from: Option<Address>,
+    mode: &str,
+    encoded_bundle: Vec<u8>,
+    proof: MessageInclusionProof,
+) -> Result<ExplainItem> {
+    let calldata = match mode {
+        "verify" => encode_verify_bundle_call(Bytes::from(encoded_bundle), proof)?,
+        "execute" => encode_execute_bundle_call(Bytes::from(encoded_bundle), proof)?,
+        other => anyhow::bail!("invalid mode {other} (expected verify or execute)"),
+    };
+
+    let request = TransactionRequest {
+        from,
+        to: Some(handler.into()),
+        input: TransactionInput::new(calldata),
+        ..Default::default()
+    };
+    let check = format!("simulate.{mode}");
+    let item = match client.provider.call(request).await {
+        Ok(_) => ExplainItem {
+            check,
+            status: "ok".to_string(),
+            details: format!("{mode} call against handler {handler:#x} succeeded"),
+        },
+        Err(err) => ExplainItem {
+            check,
+            status: "fail".to_string(),
+            details: match decode_revert_reason(err.to_string()) {
+                Some(reason) => format!("{mode} call reverted: {reason}"),
+                None => format!("{mode} call failed: {err}"),
+            },
+        },
+    };
+    Ok(item)
+}
+
+/// Load a hex string from inline input, a file path, or stdin (`-`).
 fn load_hex_or_path(value: &str) -> Result<Vec<u8>> {
+    if value == "-" {
+        return decode_hex(&read_stdin("--bundle")?);
+    }
     if Path::new(value).exists() {
         let contents = fs::read_to_string(value)?;
         return decode_hex(&contents);
@@ -214,8 +306,12 @@ fn decode_hex(value: &str) -> Result<Vec<u8>> {
     hex::decode(raw).map_err(|err| anyhow!("invalid hex {value}: {err}"))
 }
 
-/// Load a MessageInclusionProof from JSON or a file path.
+/// Load a MessageInclusionProof from JSON, a file path, or stdin (`-`).
 fn load_proof(value: &str) -> Result<MessageInclusionProof> {
+    if value == "-" {
+        let contents = read_stdin("--proof")?;
+        return serde_json::from_str(&contents).context("invalid proof json");
+    }
     if Path::new(value).exists() {
         let contents = fs::read_to_string(value)?;
         return serde_json::from_str(&contents).context("invalid proof json");
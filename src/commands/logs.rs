@@ -0,0 +1,104 @@
+use crate::abi::{
+    bundle_executed_topic, bundle_unbundled_topic, bundle_verified_topic, call_processed_topic,
+    interop_bundle_sent_topic, l1_message_sent_topic, message_sent_topic,
+};
+use crate::cli::LogsArgs;
+use crate::commands::tx_show::decode_interop_log;
+use crate::config::Config;
+use crate::output::emit;
+use crate::rpc::RpcClient;
+use crate::types::{
+    address_to_hex, b256_to_hex, format_hex, parse_checked_address, AddressBook, EventView,
+    LogEventView,
+};
+use alloy_provider::Provider;
+use alloy_rpc_types::Filter;
+use anyhow::{Context, Result};
+use serde_json::json;
+
+/// Scan a block range for interop events.
+///
+/// Queries eth_getLogs on the interop topics and decodes each log the same
+/// way `debug tx` does.
+#[tracing::instrument(name = "debug logs", skip_all, fields(chain = args.rpc.chain.as_deref()))]
+pub async fn run(args: LogsArgs, config: Config, addresses: AddressBook) -> Result<()> {
+    let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+
+    let to_block = match args.to_block {
+        Some(block) => block,
+        None => client.provider.get_block_number().await?,
+    };
+    if to_block < args.from_block {
+        anyhow::bail!(
+            "--to-block {to_block} is before --from-block {}",
+            args.from_block
+        );
+    }
+
+    let mut filter = Filter::new()
+        .from_block(args.from_block)
+        .to_block(to_block)
+        .event_signature(vec![
+            interop_bundle_sent_topic(),
+            message_sent_topic(),
+            l1_message_sent_topic(),
+            bundle_verified_topic(),
+            bundle_executed_topic(),
+            bundle_unbundled_topic(),
+            call_processed_topic(),
+        ]);
+    if let Some(address) = args.address.as_deref() {
+        let address = parse_checked_address(address)?;
+        filter = filter.address(address);
+    }
+    let logs = client
+        .provider
+        .get_logs(&filter)
+        .await
+        .context("eth_getLogs failed")?;
+
+    let mut events = Vec::new();
+    for log in &logs {
+        let event = match decode_interop_log(log, &addresses)? {
+            Some(decoded) => decoded.event,
+            None if args.interop_only => continue,
+            // Matched an interop topic, but not from the contract that owns it.
+            None => EventView {
+                name: "Unknown".to_string(),
+                address: address_to_hex(log.address()),
+                data: json!({
+                    "topics": log.topics().iter().map(|topic| b256_to_hex(*topic)).collect::<Vec<_>>(),
+                    "data": format_hex(log.data().data.as_ref()),
+                }),
+            },
+        };
+        events.push(LogEventView {
+            block_number: log.block_number,
+            tx_hash: log.transaction_hash.map(b256_to_hex),
+            event,
+        });
+    }
+
+    if emit(&events, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+
+    println!(
+        "blocks {}..={to_block}: {} interop events",
+        args.from_block,
+        events.len()
+    );
+    for event in events {
+        let block = event
+            .block_number
+            .map(|block| block.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let tx = event.tx_hash.as_deref().unwrap_or("-");
+        println!(
+            "  block {block} tx {tx}: {} @ {}",
+            event.event.name, event.event.address
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::abi::{
+    decode_bundle, decode_l1_message_sent, interop_bundle_hash, l1_message_sent_topic,
+};
+use crate::cli::MessagesArgs;
+use crate::config::Config;
+use crate::output::emit;
+use crate::rpc::{get_transaction_receipt, RpcClient};
+use crate::types::{address_to_hex, b256_to_hex, AddressBook, L2ToL1MessageView};
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_rpc_types::TransactionReceipt;
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+/// List the L2→L1 messages a transaction sends.
+///
+/// The position of each message is the index expected by `--msg-index`.
+#[tracing::instrument(name = "debug messages", skip_all, fields(chain = args.rpc.chain.as_deref(), tx = %args.tx))]
+pub async fn run(args: MessagesArgs, config: Config, addresses: AddressBook) -> Result<()> {
+    let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+    let tx_hash =
+        B256::from_str(&args.tx).with_context(|| format!("invalid tx hash {}", args.tx))?;
+    let receipt = get_transaction_receipt(&client, tx_hash).await?;
+    let messages = l2_to_l1_messages(&receipt, &addresses, config.bundle_identifier);
+
+    if emit(&messages, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+
+    if messages.is_empty() {
+        println!("no L2→L1 messages in {tx_hash:#x}");
+        return Ok(());
+    }
+    for message in messages {
+        print!(
+            "[{}] {} sender={} l2l1MsgHash={}",
+            message.index, message.kind, message.sender, message.l2l1_msg_hash
+        );
+        match message.bundle_hash {
+            Some(bundle_hash) => println!(" bundleHash={bundle_hash}"),
+            None => println!(),
+        }
+    }
+    Ok(())
+}
+
+/// An L1MessageSent log of a receipt.
+pub(crate) struct L1Message {
+    pub(crate) sender: Address,
+    /// The `_hash` topic, `keccak256(message)`.
+    pub(crate) hash: B256,
+    /// The message bytes; None if the log data does not decode.
+    pub(crate) message: Option<Bytes>,
+}
+
+/// Collect the L1MessageSent logs `l1_messenger` emitted in a receipt, in
+/// message-index order.
+pub(crate) fn l1_messages(receipt: &TransactionReceipt, l1_messenger: Address) -> Vec<L1Message> {
+    receipt
+        .logs()
+        .iter()
+        .filter(|log| {
+            log.address() == l1_messenger
+                && log.topics().first().copied() == Some(l1_message_sent_topic())
+        })
+        .map(|log| L1Message {
+            sender: log
+                .topics()
+                .get(1)
+                .map(|topic| Address::from_slice(&topic.as_slice()[12..]))
+                .unwrap_or_default(),
+            hash: log.topics().get(2).copied().unwrap_or_default(),
+            message: decode_l1_message_sent(&log.data().data).ok(),
+        })
+        .collect()
+}
+
+/// Enumerate the L2→L1 messages of a receipt in message-index order.
+///
+/// A message is a bundle when the interop center sent it and it starts with
+/// `bundle_identifier`.
+pub(crate) fn l2_to_l1_messages(
+    receipt: &TransactionReceipt,
+    addresses: &AddressBook,
+    bundle_identifier: u8,
+) -> Vec<L2ToL1MessageView> {
+    l1_messages(receipt, addresses.l1_messenger)
+        .into_iter()
+        .enumerate()
+        .map(|(index, message)| {
+            let bundle = match message
+                .message
+                .as_ref()
+                .and_then(|bytes| bytes.split_first())
+            {
+                Some((&prefix, encoded))
+                    if prefix == bundle_identifier
+                        && message.sender == addresses.interop_center =>
+                {
+                    decode_bundle(encoded)
+                        .ok()
+                        .map(|bundle| interop_bundle_hash(bundle.sourceChainId, encoded))
+                }
+                _ => None,
+            };
+            L2ToL1MessageView {
+                index: index as u32,
+                sender: address_to_hex(message.sender),
+                l2l1_msg_hash: b256_to_hex(message.hash),
+                kind: if bundle.is_some() {
+                    "interopBundle"
+                } else {
+                    "message"
+                }
+                .to_string(),
+                bundle_hash: bundle.map(b256_to_hex),
+            }
+        })
+        .collect()
+}
+
+/// Warn when a transaction sends several messages and index 0 is being used,
+/// since the proof may then be for a different message than intended.
+pub(crate) fn warn_if_ambiguous_msg_index(
+    receipt: &TransactionReceipt,
+    tx_hash: B256,
+    msg_index: u32,
+    l1_messenger: Address,
+) {
+    if msg_index != 0 {
+        return;
+    }
+    let messages = l1_messages(receipt, l1_messenger);
+    if messages.len() > 1 {
+        eprintln!(
+            "warning: {tx_hash:#x} sends {} L2→L1 messages; using message index 0 (list them with `cast-interop debug messages --tx {tx_hash:#x}`)",
+            messages.len()
+        );
+    }
+}
@@ -1,16 +1,25 @@
 pub mod bundle_action;
+pub mod bundle_decode;
+pub mod bundle_diff;
 pub mod bundle_extract;
+pub mod bundle_hash;
 pub mod chains;
 pub mod contracts;
 pub mod doctor;
 pub mod encode;
+pub mod errors;
 pub mod explain;
+pub mod logs;
+pub mod messages;
 pub mod proof;
+pub mod proof_verify;
 pub mod relay;
+pub mod relay_batch;
 pub mod root_wait;
 pub mod rpc_ping;
 pub mod send;
 pub mod status;
 pub mod token;
+pub mod token_interface;
 pub mod tx_show;
 pub mod watch;
@@ -1,49 +1,79 @@
+use crate::abi::{decode_interop_bundle_sent, interop_bundle_sent_topic};
 use crate::cli::ProofArgs;
+use crate::commands::messages::{l1_messages, warn_if_ambiguous_msg_index};
 use crate::config::Config;
+use crate::output::write_atomic;
 use crate::rpc::{
-    get_transaction_receipt, wait_for_finalized_block, wait_for_log_proof, RpcClient,
+    get_transaction_receipt, interruptible, wait_for_confirmations, wait_for_finalized_block,
+    wait_for_log_proof, RpcClient,
 };
 use crate::types::{AddressBook, MessageInclusionProof, ProofMessage};
-use alloy_primitives::B256;
-use alloy_provider::Provider;
+use alloy_primitives::{keccak256, B256};
+use alloy_rpc_types::TransactionReceipt;
 use anyhow::{Context, Result};
-use std::fs;
+use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
 
 /// Fetch the L2→L1 log proof for an interop transaction.
 ///
 /// Waits for finalization (unless disabled) and writes the proof as JSON.
+#[tracing::instrument(name = "debug proof", skip_all, fields(chain = args.rpc.chain.as_deref(), tx = %args.tx))]
 pub async fn run(args: ProofArgs, config: Config, addresses: AddressBook) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
     let tx_hash =
         B256::from_str(&args.tx).with_context(|| format!("invalid tx hash {}", args.tx))?;
     let receipt = get_transaction_receipt(&client, tx_hash).await?;
+    warn_if_ambiguous_msg_index(&receipt, tx_hash, args.msg_index, addresses.l1_messenger);
+    let l2l1_msg_hash = message_hash(&receipt, tx_hash, args.msg_index, &addresses);
 
-    let timeout = Duration::from_millis(args.timeout_ms.unwrap_or(300_000));
-    let poll_ms = args.poll_ms.unwrap_or(1_000);
+    let limit = config.wait_limit(args.timeout_ms, args.max_wait_blocks);
+    let poll = config.poll_interval(args.poll_ms);
 
-    if !args.no_wait {
-        wait_for_finalized_block(
+    let block_number = receipt.block_number.expect("missing block number");
+    let rerun = match (args.rpc.chain.as_deref(), args.rpc.rpc.as_deref()) {
+        (Some(alias), _) => format!("cast-interop debug proof --chain {alias} --tx {tx_hash:#x}"),
+        (None, Some(url)) => format!("cast-interop debug proof --rpc {url} --tx {tx_hash:#x}"),
+        (None, None) => format!("cast-interop debug proof --tx {tx_hash:#x}"),
+    };
+
+    interruptible(
+        wait_for_confirmations(
             &client,
-            receipt.block_number.expect("missing block number"),
-            timeout,
-            Duration::from_millis(100),
+            &receipt,
+            config.confirmations(args.confirmations),
+            limit,
+            poll,
+        ),
+        || format!("block {block_number} not confirmed yet; rerun `{rerun}` to keep waiting"),
+    )
+    .await?;
+    if !args.no_wait {
+        interruptible(
+            wait_for_finalized_block(&client, block_number, limit, Duration::from_millis(100)),
+            || format!("block {block_number} not finalized yet; rerun `{rerun}` to keep waiting"),
         )
         .await?;
     }
 
-    let log_proof = wait_for_log_proof(
-        &client,
-        tx_hash,
-        args.msg_index,
-        timeout,
-        Duration::from_millis(poll_ms),
+    let log_proof = interruptible(
+        wait_for_log_proof(
+            &client,
+            tx_hash,
+            args.msg_index,
+            limit,
+            poll,
+        ),
+        || {
+            format!(
+                "block {block_number} finalized but log proof not available yet; rerun `{rerun}` to keep waiting"
+            )
+        },
     )
     .await?;
 
-    let chain_id = client.provider.get_chain_id().await?.to_string();
+    let chain_id = client.chain_id().await?.to_string();
     let message = ProofMessage {
         tx_number_in_batch: receipt.transaction_index.expect("missing tx index"),
         sender: format!("{:#x}", addresses.interop_center),
@@ -56,23 +86,76 @@ pub async fn run(args: ProofArgs, config: Config, addresses: AddressBook) -> Res
         root: log_proof.root.clone(),
         message,
         proof: log_proof.proof.clone(),
+        l2l1_msg_hash: l2l1_msg_hash.map(|hash| format!("{hash:#x}")),
     };
 
-    println!(
+    // The banner goes to stderr so stdout carries only the proof JSON.
+    eprintln!(
         "Message inclusion proof obtained. Batch number is {} ",
         log_proof.batch_number
     );
 
-    if args.json || args.out.is_some() {
-        let json = serde_json::to_string_pretty(&output)?;
-        if args.json {
-            println!("{json}");
-        }
-        if let Some(path) = args.out {
-            fs::write(path, json)?;
+    let json = serde_json::to_string_pretty(&output)?;
+    match args.out.as_deref() {
+        Some(path) if path == Path::new("-") => println!("{json}"),
+        Some(path) => {
+            write_atomic(path, &json)?;
+            if config.json_output() {
+                println!("{json}");
+            }
         }
-    } else {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        None => println!("{json}"),
     }
     Ok(())
 }
+
+/// Hash the message at `msg_index` (`keccak256(message)`, as the L1 messenger
+/// does) and check it against the l2l1MsgHash of the receipt's
+/// InteropBundleSent, warning when the index points at another message.
+fn message_hash(
+    receipt: &TransactionReceipt,
+    tx_hash: B256,
+    msg_index: u32,
+    addresses: &AddressBook,
+) -> Option<B256> {
+    let messages = l1_messages(receipt, addresses.l1_messenger);
+    let Some(message) = messages.get(msg_index as usize) else {
+        eprintln!("warning: {tx_hash:#x} has no L2→L1 message at index {msg_index}");
+        return None;
+    };
+    let hash = match &message.message {
+        Some(bytes) => {
+            let hash = keccak256(bytes);
+            eprintln!(
+                "l2l1MsgHash: {hash:#x} (keccak256 of message {msg_index}, {} bytes)",
+                bytes.len()
+            );
+            hash
+        }
+        None => {
+            eprintln!(
+                "l2l1MsgHash: {:#x} (from the L1MessageSent log)",
+                message.hash
+            );
+            message.hash
+        }
+    };
+    let bundle_hashes: Vec<B256> = receipt
+        .logs()
+        .iter()
+        .filter(|log| {
+            log.address() == addresses.interop_center
+                && log.topics().first().copied() == Some(interop_bundle_sent_topic())
+        })
+        .filter_map(|log| decode_interop_bundle_sent(log.data().data.clone()).ok())
+        .map(|(l2l1_msg_hash, _, _)| l2l1_msg_hash)
+        .collect();
+    if bundle_hashes.contains(&hash) {
+        eprintln!("l2l1MsgHash matches the InteropBundleSent event");
+    } else if let Some(bundle_hash) = bundle_hashes.first() {
+        eprintln!(
+            "warning: InteropBundleSent has l2l1MsgHash {bundle_hash:#x}, not {hash:#x}; --msg-index {msg_index} may be wrong (list messages with `cast-interop debug messages --tx {tx_hash:#x}`)"
+        );
+    }
+    Some(hash)
+}
@@ -0,0 +1,323 @@
+use crate::cli::ProofVerifyArgs;
+use crate::commands::bundle_action::load_proof;
+use crate::commands::bundle_decode::load_hex_or_path;
+use crate::config::Config;
+use crate::output::emit;
+use crate::types::{bundle_message, bytes_from_hex, AddressBook, MessageInclusionProof};
+use alloy_primitives::{keccak256, Address, B256, U256};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Proof metadata version understood by the message verifier.
+const PROOF_METADATA_VERSION: u8 = 1;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofVerifyOutput {
+    chain_id: String,
+    l1_batch_number: u64,
+    l2_message_index: u64,
+    message_hash: String,
+    /// "data", "bundle" or "l2l1MsgHash": where the message hash came from.
+    message_hash_source: &'static str,
+    leaf: String,
+    /// "v1" (metadata word first) or "legacy" (plain Merkle path).
+    proof_format: &'static str,
+    computed_root: String,
+    expected_root: String,
+    matches: bool,
+    /// Settlement layers the batch root is further proven into, if any.
+    settlement_layers: Vec<SettlementHop>,
+}
+
+/// A settlement layer batch whose root commits to the proven batch.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettlementHop {
+    pub chain_id: String,
+    pub batch_number: String,
+    pub root: String,
+}
+
+/// Roots recomputed from a proof's Merkle path.
+pub struct ComputedProof {
+    pub legacy: bool,
+    /// Batch root of the source chain; compared with `proof.root`.
+    pub batch_root: B256,
+    pub settlement_layers: Vec<SettlementHop>,
+}
+
+/// Recompute a message inclusion proof's root locally and compare it with
+/// the `root` it claims.
+///
+/// The message hash comes from `message.data`, from `--bundle` when the
+/// data is empty (as in `debug proof` output), or else from `l2l1MsgHash`.
+#[tracing::instrument(name = "debug proof-verify", skip_all)]
+pub fn run(args: ProofVerifyArgs, config: Config, addresses: AddressBook) -> Result<()> {
+    let proof = load_proof(&args.proof)?;
+    proof.validate()?;
+
+    let data = bytes_from_hex(&proof.message.data).context("invalid proof message data")?;
+    let (message_hash, message_hash_source) = match args.bundle.as_deref() {
+        Some(bundle) => {
+            let bundle = load_hex_or_path(bundle)?;
+            (
+                keccak256(bundle_message(config.bundle_identifier, &bundle)),
+                "bundle",
+            )
+        }
+        None if !data.is_empty() => (keccak256(&data), "data"),
+        None => match proof.l2l1_msg_hash.as_deref() {
+            Some(hash) => (
+                B256::from_str(hash).context("invalid proof l2l1MsgHash")?,
+                "l2l1MsgHash",
+            ),
+            None => anyhow::bail!(
+                "proof has no message data or l2l1MsgHash; pass the encoded bundle with --bundle"
+            ),
+        },
+    };
+
+    let sender = Address::from_str(&proof.message.sender).context("invalid proof sender")?;
+    let tx_number_in_batch = u16::try_from(proof.message.tx_number_in_batch).map_err(|_| {
+        anyhow::anyhow!(
+            "proof message.txNumberInBatch {} does not fit in uint16",
+            proof.message.tx_number_in_batch
+        )
+    })?;
+    let leaf = message_leaf(
+        addresses.l1_messenger,
+        tx_number_in_batch,
+        sender,
+        message_hash,
+    );
+    let computed = compute_proof_roots(&proof, leaf)?;
+    let expected = B256::from_str(&proof.root)?;
+
+    let output = ProofVerifyOutput {
+        chain_id: proof.chain_id.clone(),
+        l1_batch_number: proof.l1_batch_number,
+        l2_message_index: proof.l2_message_index,
+        message_hash: format!("{message_hash:#x}"),
+        message_hash_source,
+        leaf: format!("{leaf:#x}"),
+        proof_format: if computed.legacy { "legacy" } else { "v1" },
+        computed_root: format!("{:#x}", computed.batch_root),
+        expected_root: format!("{expected:#x}"),
+        matches: computed.batch_root == expected,
+        settlement_layers: computed.settlement_layers,
+    };
+
+    if !emit(&output, config.json_output(), args.out.as_deref())? {
+        println!(
+            "message hash: {} (from {})",
+            output.message_hash, output.message_hash_source
+        );
+        println!("leaf: {}", output.leaf);
+        println!("proof format: {}", output.proof_format);
+        println!("computed root: {}", output.computed_root);
+        println!("proof root:    {}", output.expected_root);
+        for hop in &output.settlement_layers {
+            println!(
+                "settled on chain {} in batch {} (root {})",
+                hop.chain_id, hop.batch_number, hop.root
+            );
+        }
+        if output.matches {
+            println!("proof matches its root");
+        }
+    }
+    if !output.matches {
+        anyhow::bail!(
+            "proof does not hash to its root: computed {}, proof has {}",
+            output.computed_root,
+            output.expected_root
+        );
+    }
+    Ok(())
+}
+
+/// Hash the L2 log the L1 messenger emits for a message, as
+/// `MessageHashing.getLeafHashFromLog` does: shard 0, service log from the
+/// messenger, keyed by the sender, valued by `keccak256(data)`.
+pub fn message_leaf(
+    l1_messenger: Address,
+    tx_number_in_batch: u16,
+    sender: Address,
+    message_hash: B256,
+) -> B256 {
+    let mut packed = Vec::with_capacity(88);
+    packed.push(0u8);
+    packed.push(1u8);
+    packed.extend_from_slice(&tx_number_in_batch.to_be_bytes());
+    packed.extend_from_slice(l1_messenger.as_slice());
+    packed.extend_from_slice(B256::left_padding_from(sender.as_slice()).as_slice());
+    packed.extend_from_slice(message_hash.as_slice());
+    keccak256(packed)
+}
+
+/// Walk the proof nodes from `leaf` up to the source batch root, then
+/// through any settlement layers the proof continues into.
+///
+/// Follows the layout `MessageVerification` parses: an optional metadata
+/// word (version, log leaf proof length, batch leaf proof length, final
+/// node flag), the log leaf path, and for non-final proofs the batch leaf
+/// mask and path, the packed settlement batch number and mask, the
+/// settlement chain ID, then the next proof.
+pub fn compute_proof_roots(proof: &MessageInclusionProof, leaf: B256) -> Result<ComputedProof> {
+    let nodes = proof
+        .proof
+        .iter()
+        .map(|node| B256::from_str(node).with_context(|| format!("invalid proof node {node}")))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut leaf = leaf;
+    let mut mask = U256::from(proof.l2_message_index);
+    let mut batch_number = U256::from(proof.l1_batch_number);
+    let mut nodes = nodes.as_slice();
+    let mut legacy = false;
+    let mut batch_root = B256::ZERO;
+    let mut settlement_layers: Vec<SettlementHop> = Vec::new();
+    loop {
+        let metadata = parse_metadata(nodes)?;
+        legacy |= metadata.legacy;
+        let path = take(
+            nodes,
+            metadata.start,
+            metadata.log_leaf_len,
+            "log leaf path",
+        )?;
+        let root = merkle_root(path, mask, leaf)?;
+        match settlement_layers.last_mut() {
+            Some(hop) => hop.root = format!("{root:#x}"),
+            None => batch_root = root,
+        }
+        if metadata.final_node {
+            break;
+        }
+
+        let mut ptr = metadata.start + metadata.log_leaf_len;
+        let batch_leaf_mask = U256::from_be_bytes(take(nodes, ptr, 1, "batch leaf mask")?[0].0);
+        ptr += 1;
+        let batch_path = take(nodes, ptr, metadata.batch_leaf_len, "batch leaf path")?;
+        ptr += metadata.batch_leaf_len;
+        let chain_id_root = merkle_root(
+            batch_path,
+            batch_leaf_mask,
+            padded_leaf("zkSync:BatchLeaf", root, batch_number),
+        )?;
+        let chain_id = settlement_layers
+            .last()
+            .map_or(proof.chain_id.as_str(), |hop| hop.chain_id.as_str());
+        let chain_id = U256::from_str(chain_id)?;
+
+        let packed = take(nodes, ptr, 2, "settlement layer batch info")?;
+        let packed_batch = U256::from_be_bytes(packed[0].0);
+        batch_number = packed_batch >> 128;
+        mask = packed_batch & ((U256::from(1) << 128) - U256::from(1));
+        let settlement_chain_id = U256::from_be_bytes(packed[1].0);
+        settlement_layers.push(SettlementHop {
+            chain_id: settlement_chain_id.to_string(),
+            batch_number: batch_number.to_string(),
+            root: String::new(),
+        });
+        leaf = padded_leaf("zkSync:ChainIdLeaf", chain_id_root, chain_id);
+        nodes = &nodes[ptr + 2..];
+    }
+
+    Ok(ComputedProof {
+        legacy,
+        batch_root,
+        settlement_layers,
+    })
+}
+
+struct ProofMetadata {
+    legacy: bool,
+    start: usize,
+    log_leaf_len: usize,
+    batch_leaf_len: usize,
+    final_node: bool,
+}
+
+/// Parse the leading metadata word; a first node whose last 28 bytes are
+/// not all zero means the whole proof is a plain (legacy) Merkle path.
+fn parse_metadata(nodes: &[B256]) -> Result<ProofMetadata> {
+    let first = nodes
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("proof ends before its metadata"))?;
+    if first[4..].iter().any(|byte| *byte != 0) {
+        return Ok(ProofMetadata {
+            legacy: true,
+            start: 0,
+            log_leaf_len: nodes.len(),
+            batch_leaf_len: 0,
+            final_node: true,
+        });
+    }
+    if first[0] != PROOF_METADATA_VERSION {
+        anyhow::bail!(
+            "unsupported proof metadata version {} (expected {PROOF_METADATA_VERSION})",
+            first[0]
+        );
+    }
+    let metadata = ProofMetadata {
+        legacy: false,
+        start: 1,
+        log_leaf_len: first[1] as usize,
+        batch_leaf_len: first[2] as usize,
+        final_node: first[3] != 0,
+    };
+    if metadata.final_node && metadata.batch_leaf_len != 0 {
+        anyhow::bail!("final proof node must not have a batch leaf proof");
+    }
+    Ok(metadata)
+}
+
+/// `len` nodes starting at `start`, or an error naming the missing part.
+fn take<'a>(nodes: &'a [B256], start: usize, len: usize, what: &str) -> Result<&'a [B256]> {
+    nodes.get(start..start + len).ok_or_else(|| {
+        anyhow::anyhow!(
+            "proof too short for {what}: needs nodes {start}..{}, has {}",
+            start + len,
+            nodes.len()
+        )
+    })
+}
+
+/// Fold a Merkle path as `Merkle.calculateRoot` does: each bit of `index`,
+/// lowest first, says whether the current hash is the right child.
+fn merkle_root(path: &[B256], index: U256, leaf: B256) -> Result<B256> {
+    if path.len() >= 256 {
+        anyhow::bail!("invalid Merkle path length {}", path.len());
+    }
+    if index >> path.len() != U256::ZERO {
+        anyhow::bail!(
+            "leaf index {index} out of range for a path of {} nodes",
+            path.len()
+        );
+    }
+    let mut hash = leaf;
+    for (level, node) in path.iter().enumerate() {
+        hash = if index.bit(level) {
+            keccak256([node.as_slice(), hash.as_slice()].concat())
+        } else {
+            keccak256([hash.as_slice(), node.as_slice()].concat())
+        };
+    }
+    Ok(hash)
+}
+
+/// `keccak256(abi.encodePacked(keccak256(padding), root, value))`, the batch
+/// and chain ID leaves of the settlement layer trees.
+fn padded_leaf(padding: &str, root: B256, value: U256) -> B256 {
+    keccak256(
+        [
+            keccak256(padding.as_bytes()).as_slice(),
+            root.as_slice(),
+            &value.to_be_bytes::<32>(),
+        ]
+        .concat(),
+    )
+}
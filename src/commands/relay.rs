@@ -1,226 +1,507 @@
 use crate::abi::{
-    decode_interop_bundle_sent, encode_execute_bundle_call, encode_interop_bundle,
-    encode_interop_roots_call, encode_verify_bundle_call, interop_bundle_sent_topic,
+    decode_bundle, encode_execute_bundle_call, encode_verify_bundle_call, interop_bundle_hash,
 };
 use crate::cli::RelayArgs;
-use crate::commands::bundle_action::decode_send_transaction;
+use crate::commands::bundle_action::{decode_send_transaction, describe_dry_run_failure};
 use crate::config::Config;
-use crate::rpc::{
-    eth_call, get_transaction_receipt, wait_for_finalized_block, wait_for_log_proof, RpcClient,
-};
-use crate::signer::{load_signer, SignerOptions};
+use crate::interop::{prepare_proof, PreparedProof, SourceProver};
+use crate::output::emit;
+use crate::rpc::{estimate_gas, eth_call, RpcClient, TxOverrides, WaitLimit};
+use crate::signer::{load_signer, signer_address, SignerOptions};
 use crate::types::{
-    format_hex, require_signer_or_dry_run, AddressBook, MessageInclusionProof, ProofMessage,
-    RelaySummary, BUNDLE_IDENTIFIER,
+    bundle_message, bytes_from_hex, format_hex, parse_checked_address, require_signer_or_dry_run,
+    AddressBook, MessageInclusionProof, RelayResult, RelaySummary,
 };
 use alloy_primitives::{Address, Bytes, B256, U256};
-use alloy_provider::{Provider, ProviderBuilder};
+use alloy_provider::network::EthereumWallet;
+use alloy_provider::{DynProvider, Provider};
 use anyhow::{anyhow, Context, Result};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 
+/// Inputs of [`relay`] as plain values, for callers outside the CLI.
+pub struct RelayParams {
+    /// Source chain RPC URL; use instead of `chain_src`.
+    pub rpc_src: Option<String>,
+    /// Source chain alias from the config.
+    pub chain_src: Option<String>,
+    /// Destination chain RPC URL; use instead of `chain_dest`.
+    pub rpc_dest: Option<String>,
+    /// Destination chain alias from the config.
+    pub chain_dest: Option<String>,
+    /// Source transaction whose bundle is relayed; required unless `resume` is set.
+    pub tx: Option<B256>,
+    /// Directory of an earlier relay's artifacts to continue from.
+    pub resume: Option<PathBuf>,
+    pub msg_index: u32,
+    /// Which InteropBundleSent event of `tx` to relay; the first when None.
+    pub bundle_index: Option<usize>,
+    /// `verify` or `execute`.
+    pub mode: String,
+    /// Where to write bundle.hex, proof.json and relay_summary.json.
+    pub out_dir: Option<PathBuf>,
+    pub dry_run: bool,
+    pub gas_estimate_only: bool,
+    /// Signer for the handler transaction; only optional for dry runs and estimates.
+    pub wallet: Option<EthereumWallet>,
+    pub overrides: TxOverrides,
+    pub handler: Address,
+    pub center: Address,
+    /// Emitter of the source chain's `L1MessageSent` events.
+    pub l1_messenger: Address,
+    pub root_storage: Address,
+    /// How long to wait for the proof and root.
+    pub limit: WaitLimit,
+    pub poll: Duration,
+    /// Blocks the source receipt must be buried under before proving.
+    pub confirmations: u64,
+    pub proof_cache: Option<PathBuf>,
+}
+
 /// Relay a bundle end-to-end across chains.
 ///
-/// Fetches the source proof, waits for the root, then verifies/executes on the destination.
+/// Translates the command-line flags into [`RelayParams`] and prints the
+/// [`RelayResult`]: as JSON under `--output json`, else the handler step.
+#[tracing::instrument(name = "bundle relay", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref(), tx = args.tx.as_deref()))]
 pub async fn run(args: RelayArgs, config: Config, addresses: AddressBook) -> Result<()> {
     let handler = args
         .handler
         .as_deref()
-        .map(|value| Address::from_str(value))
+        .map(parse_checked_address)
         .transpose()
         .context("invalid handler address")?
         .unwrap_or(addresses.interop_handler);
     let center = args
         .center
         .as_deref()
-        .map(|value| Address::from_str(value))
+        .map(parse_checked_address)
         .transpose()
         .context("invalid center address")?
         .unwrap_or(addresses.interop_center);
     let root_storage = args
         .root_storage
         .as_deref()
-        .map(|value| Address::from_str(value))
+        .map(parse_checked_address)
         .transpose()
         .context("invalid root storage address")?
         .unwrap_or(addresses.interop_root_storage);
+    let tx = args
+        .tx
+        .as_deref()
+        .map(|tx| B256::from_str(tx).with_context(|| format!("invalid tx hash {tx}")))
+        .transpose()?;
 
     let wallet = load_signer(
         SignerOptions {
             private_key: args.signer.private_key.as_deref(),
             private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
         },
         &config,
-    )?;
+    )
+    .await?;
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
 
-    require_signer_or_dry_run(wallet.is_some(), args.dry_run, "relay")?;
-
-    let source_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
-    let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
-
-    let source_client = RpcClient::new(&source_rpc.url).await?;
-    let dest_client = RpcClient::new(&dest_rpc.url).await?;
-
-    let tx_hash =
-        B256::from_str(&args.tx).with_context(|| format!("invalid tx hash {}", args.tx))?;
-    let receipt = get_transaction_receipt(&source_client, tx_hash).await?;
-    let mut bundle = None;
-    let mut bundle_hash = None;
-    for log in receipt.logs().iter() {
-        if log.topics().first().copied() == Some(interop_bundle_sent_topic()) {
-            let (_, hash, interop_bundle) = decode_interop_bundle_sent(log.data().data.clone())?;
-            bundle = Some(interop_bundle);
-            bundle_hash = Some(hash);
-            break;
-        }
+    let out = args.out;
+    let params = RelayParams {
+        rpc_src: args.rpc_src,
+        chain_src: args.chain_src,
+        rpc_dest: args.rpc_dest,
+        chain_dest: args.chain_dest,
+        tx,
+        resume: args.resume,
+        msg_index: args.msg_index,
+        bundle_index: args.bundle_index,
+        mode: args.mode,
+        out_dir: args.out_dir,
+        dry_run: config.dry_run,
+        gas_estimate_only: args.gas_estimate_only,
+        wallet,
+        overrides,
+        handler,
+        center,
+        l1_messenger: addresses.l1_messenger,
+        root_storage,
+        limit: config.wait_limit(args.timeout_ms, args.max_wait_blocks),
+        poll: config.poll_interval(args.poll_ms),
+        confirmations: config.confirmations(args.confirmations),
+        proof_cache: args.proof_cache,
+    };
+    let json = config.json_output();
+    let result = relay(params, config).await?;
+    if !emit(&result, json, out.as_deref())? {
+        print_handler_result(&result);
     }
-    let bundle = bundle.ok_or_else(|| anyhow!("InteropBundleSent not found in receipt"))?;
-    let bundle_hash = bundle_hash.expect("bundle hash");
-    let encoded_bundle = encode_interop_bundle(&bundle);
+    Ok(())
+}
 
-    let timeout = Duration::from_millis(args.timeout_ms.unwrap_or(300_000));
-    let poll_ms = args.poll_ms.unwrap_or(1_000);
+/// Print the handler step of a relay, as `bundle verify`/`execute` do.
+fn print_handler_result(result: &RelayResult) {
+    if result.dry_run {
+        match &result.dry_run_error {
+            Some(failure) => println!("{failure}"),
+            None => println!("dry-run success"),
+        }
+        return;
+    }
+    if let Some(gas) = result.estimated_gas {
+        println!("estimated gas: {gas}");
+    }
+    if let Some(tx_hash) = &result.handler_tx_hash {
+        println!("sent tx: {tx_hash}");
+    }
+}
 
-    wait_for_finalized_block(
-        &source_client,
-        receipt.block_number.expect("missing block number"),
-        timeout,
-        Duration::from_millis(100),
-    )
-    .await?;
-    let log_proof = wait_for_log_proof(
-        &source_client,
-        tx_hash,
-        args.msg_index,
-        timeout,
-        Duration::from_millis(poll_ms),
-    )
-    .await?;
+/// Fetch the source proof, wait for the root, then verify/execute the bundle
+/// on the destination.
+///
+/// Nothing is printed to stdout; the outcome is returned for the caller to
+/// render. Warnings still go to stderr.
+pub async fn relay(params: RelayParams, config: Config) -> Result<RelayResult> {
+    require_signer_or_dry_run(
+        params.wallet.is_some(),
+        params.dry_run || params.gas_estimate_only,
+        "relay",
+    )?;
 
-    let source_chain_id = source_client.provider.get_chain_id().await?;
-    let expected_root = log_proof.root.clone();
+    let dest_rpc = config.resolve_rpc(params.rpc_dest.as_deref(), params.chain_dest.as_deref())?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let destination_chain_id = dest_client.chain_id().await?;
 
-    wait_for_root(
-        &dest_client,
-        root_storage,
+    let PreparedProof {
+        encoded_bundle,
+        proof,
+        bundle_hash,
+        source_tx_hash,
         source_chain_id,
-        log_proof.batch_number,
-        expected_root.clone(),
-        timeout,
-        Duration::from_millis(poll_ms),
-    )
-    .await?;
-
-    let message = ProofMessage {
-        tx_number_in_batch: receipt.transaction_index.expect("missing tx index"),
-        sender: format!("{center:#x}"),
-        data: format!(
-            "0x{}{}",
-            hex::encode([BUNDLE_IDENTIFIER]),
-            hex::encode(encoded_bundle.as_ref())
-        ),
-    };
-    let proof = MessageInclusionProof {
-        chain_id: source_chain_id.to_string(),
-        l1_batch_number: log_proof.batch_number,
-        l2_message_index: log_proof.id,
-        root: log_proof.root.clone(),
-        message,
-        proof: log_proof.proof.clone(),
+    } = match params.resume.as_deref() {
+        Some(dir) => load_relay_artifacts(
+            dir,
+            params.center,
+            config.bundle_identifier,
+            destination_chain_id,
+        )?,
+        None => {
+            let source_rpc =
+                config.resolve_rpc(params.rpc_src.as_deref(), params.chain_src.as_deref())?;
+            let prover = SourceProver {
+                source_client: RpcClient::for_rpc(
+                    &source_rpc,
+                    config.rpc_timeout,
+                    &config.rpc_headers,
+                )
+                .await?,
+                dest_client: dest_client.clone(),
+                center: params.center,
+                l1_messenger: params.l1_messenger,
+                root_storage: params.root_storage,
+                msg_index: params.msg_index,
+                bundle_index: params.bundle_index,
+                bundle_identifier: config.bundle_identifier,
+                limit: params.limit,
+                poll: params.poll,
+                confirmations: params.confirmations,
+                cache_dir: params.proof_cache.clone(),
+            };
+            let tx_hash = params.tx.ok_or_else(|| anyhow!("set --tx or --resume"))?;
+            let rerun = relay_rerun_command(
+                RelayEndpoints::of_relay(&params),
+                tx_hash,
+                &params.mode,
+                params.msg_index,
+                params.bundle_index,
+            );
+            prepare_proof(&prover, tx_hash, &rerun).await?
+        }
     };
 
-    let calldata = match args.mode.as_str() {
-        "verify" => encode_verify_bundle_call(encoded_bundle.clone(), proof.clone())?,
-        "execute" => encode_execute_bundle_call(encoded_bundle.clone(), proof.clone())?,
-        other => anyhow::bail!("invalid mode {other} (expected verify or execute)"),
-    };
+    let calldata = encode_handler_call(&params.mode, &encoded_bundle, &proof)?;
 
-    let mut handler_tx_hash = None;
-    if args.dry_run {
-        match eth_call(&dest_client, handler, calldata.clone()).await {
-            Ok(_) => println!("dry-run success"),
-            Err(err) => println!("dry-run failed: {err}"),
-        }
-    } else {
-        let wallet = wallet.expect("wallet required");
-        let chain_id = dest_client.provider.get_chain_id().await?;
-
-        let provider = ProviderBuilder::new()
-            .wallet(wallet)
-            .with_chain_id(chain_id)
-            .connect(&dest_rpc.url)
-            .await?;
-        let request = alloy_rpc_types::TransactionRequest {
-            to: Some(alloy_primitives::TxKind::Call(handler)),
-            input: alloy_rpc_types::TransactionInput::new(calldata),
-            ..Default::default()
-        };
-
-        let pending = decode_send_transaction(provider.send_transaction(request).await)?;
+    if let Some(dir) = params.resume.as_deref() {
+        tracing::info!(dir = %dir.display(), "resuming relay from saved artifacts");
+    }
 
-        let tx_hash = pending.tx_hash();
-        handler_tx_hash = Some(format!("{tx_hash:#x}"));
-        println!("sent tx: {tx_hash:#x}");
+    let from = params.wallet.as_ref().map(signer_address).transpose()?;
+    let provider = match params.wallet {
+        Some(wallet) if !params.dry_run && !params.gas_estimate_only => {
+            Some(dest_client.signer_provider(wallet).await?)
+        }
+        _ => None,
+    };
+    let HandlerOutcome {
+        handler_tx_hash,
+        estimated_gas,
+        dry_run_error,
+    } = HandlerStep {
+        client: &dest_client,
+        handler: params.handler,
+        from,
+        provider: provider.as_ref(),
+        overrides: &params.overrides,
+        dry_run: params.dry_run,
+        gas_estimate_only: params.gas_estimate_only,
+        human: false,
     }
+    .submit(calldata, None)
+    .await?;
 
     let summary = RelaySummary {
         source_chain_id: source_chain_id.to_string(),
-        destination_chain_id: dest_client.provider.get_chain_id().await?.to_string(),
+        destination_chain_id: destination_chain_id.to_string(),
         l1_batch_number: proof.l1_batch_number,
         l2_message_index: proof.l2_message_index,
         bundle_hash: format!("{bundle_hash:#x}"),
-        source_tx_hash: format!("{tx_hash:#x}"),
+        source_tx_hash,
         handler_tx_hash: handler_tx_hash.clone(),
+        estimated_gas,
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&summary)?);
+    // A resumed relay refreshes its own artifacts unless told otherwise.
+    if let Some(dir) = params.out_dir.or(params.resume) {
+        write_relay_outputs(dir, &encoded_bundle, &proof, &summary).await?;
     }
 
-    if let Some(dir) = args.out_dir {
-        write_relay_outputs(dir, &encoded_bundle, &proof, &summary).await?;
+    Ok(RelayResult {
+        source_chain_id: summary.source_chain_id,
+        destination_chain_id: summary.destination_chain_id,
+        source_tx_hash: summary.source_tx_hash,
+        bundle_hash: summary.bundle_hash,
+        l1_batch_number: summary.l1_batch_number,
+        l2_message_index: summary.l2_message_index,
+        mode: params.mode,
+        dry_run: params.dry_run,
+        dry_run_error,
+        handler_tx_hash,
+        estimated_gas,
+    })
+}
+
+/// Encode the handler call for a relay mode (verify or execute).
+pub(crate) fn encode_handler_call(
+    mode: &str,
+    encoded_bundle: &Bytes,
+    proof: &MessageInclusionProof,
+) -> Result<Bytes> {
+    match mode {
+        "verify" => encode_verify_bundle_call(encoded_bundle.clone(), proof.clone()),
+        "execute" => encode_execute_bundle_call(encoded_bundle.clone(), proof.clone()),
+        other => anyhow::bail!("invalid mode {other} (expected verify or execute)"),
     }
+}
 
-    Ok(())
+/// Result of the destination handler step.
+pub(crate) struct HandlerOutcome {
+    pub(crate) handler_tx_hash: Option<String>,
+    pub(crate) estimated_gas: Option<u64>,
+    pub(crate) dry_run_error: Option<String>,
 }
 
-/// Wait for the expected interop root to appear on the destination chain.
-async fn wait_for_root(
-    client: &RpcClient,
-    root_storage: Address,
-    chain_id: u64,
-    batch_number: u64,
-    expected_root: String,
-    timeout: Duration,
-    poll: Duration,
-) -> Result<()> {
-    let expected = B256::from_str(&expected_root)?;
-    let start = tokio::time::Instant::now();
-    let mut first_run = true;
-    loop {
-        let data = encode_interop_roots_call(U256::from(chain_id), U256::from(batch_number));
-        let result = eth_call(client, root_storage, data).await?;
-        let root = crate::abi::decode_bytes32(result)?;
-        if root != B256::ZERO {
-            if root == expected {
-                println!("interop root available: {root:#x}");
-                return Ok(());
+/// Destination handler step: dry-run, estimate, or send the handler call.
+pub(crate) struct HandlerStep<'a> {
+    pub(crate) client: &'a RpcClient,
+    pub(crate) handler: Address,
+    pub(crate) from: Option<Address>,
+    /// Signing provider; required unless `dry_run` or `gas_estimate_only`.
+    pub(crate) provider: Option<&'a DynProvider>,
+    pub(crate) overrides: &'a TxOverrides,
+    pub(crate) dry_run: bool,
+    pub(crate) gas_estimate_only: bool,
+    pub(crate) human: bool,
+}
+
+impl HandlerStep<'_> {
+    /// Run the step for `calldata`; `nonce` pins the nonce over `--nonce`.
+    pub(crate) async fn submit(
+        &self,
+        calldata: Bytes,
+        nonce: Option<u64>,
+    ) -> Result<HandlerOutcome> {
+        let mut outcome = HandlerOutcome {
+            handler_tx_hash: None,
+            estimated_gas: None,
+            dry_run_error: None,
+        };
+        if self.dry_run {
+            match eth_call(self.client, self.handler, calldata).await {
+                Ok(_) if self.human => println!("dry-run success"),
+                Ok(_) => {}
+                Err(err) => {
+                    let failure = describe_dry_run_failure(&err);
+                    if self.human {
+                        println!("{failure}");
+                    }
+                    outcome.dry_run_error = Some(failure);
+                }
             }
-            anyhow::bail!("interop root mismatch: expected {expected:#x}, got {root:#x}");
+            return Ok(outcome);
         }
-        if start.elapsed() > timeout {
-            anyhow::bail!("interop root did not become available in time");
+        match estimate_gas(self.client, self.from, self.handler, calldata.clone()).await {
+            Ok(gas) => {
+                if self.human {
+                    println!("estimated gas: {gas}");
+                }
+                outcome.estimated_gas = Some(gas);
+            }
+            // An explicit --gas-limit still lets the transaction go out.
+            Err(err) if self.overrides.gas_limit.is_some() && !self.gas_estimate_only => {
+                eprintln!("warning: {err}");
+            }
+            Err(err) => return Err(err),
         }
-        if first_run {
-            println!("waiting for interop root to become available for {timeout:?}...");
-            first_run = false;
+        if self.gas_estimate_only {
+            return Ok(outcome);
         }
-        tokio::time::sleep(poll).await;
+        let provider = self.provider.expect("wallet required");
+        let mut request = alloy_rpc_types::TransactionRequest {
+            to: Some(alloy_primitives::TxKind::Call(self.handler)),
+            input: alloy_rpc_types::TransactionInput::new(calldata),
+            ..Default::default()
+        };
+
+        self.overrides.apply(self.client, &mut request).await?;
+        if nonce.is_some() {
+            request.nonce = nonce;
+        }
+
+        let pending = decode_send_transaction(provider.send_transaction(request).await)?;
+
+        let tx_hash = pending.tx_hash();
+        outcome.handler_tx_hash = Some(format!("{tx_hash:#x}"));
+        if self.human {
+            println!("sent tx: {tx_hash:#x}");
+        }
+        Ok(outcome)
     }
 }
 
+/// Chain selection flags as given on the command line.
+pub(crate) struct RelayEndpoints<'a> {
+    pub(crate) rpc_src: Option<&'a str>,
+    pub(crate) chain_src: Option<&'a str>,
+    pub(crate) rpc_dest: Option<&'a str>,
+    pub(crate) chain_dest: Option<&'a str>,
+}
+
+impl<'a> RelayEndpoints<'a> {
+    fn of_relay(params: &'a RelayParams) -> Self {
+        Self {
+            rpc_src: params.rpc_src.as_deref(),
+            chain_src: params.chain_src.as_deref(),
+            rpc_dest: params.rpc_dest.as_deref(),
+            chain_dest: params.chain_dest.as_deref(),
+        }
+    }
+}
+
+/// Rebuild the `bundle relay` invocation that restarts the relay of `tx_hash`.
+pub(crate) fn relay_rerun_command(
+    endpoints: RelayEndpoints<'_>,
+    tx_hash: B256,
+    mode: &str,
+    msg_index: u32,
+    bundle_index: Option<usize>,
+) -> String {
+    let mut command = "cast-interop bundle relay".to_string();
+    if let Some(alias) = endpoints.chain_src {
+        command.push_str(&format!(" --chain-src {alias}"));
+    } else if let Some(url) = endpoints.rpc_src {
+        command.push_str(&format!(" --rpc-src {url}"));
+    }
+    if let Some(alias) = endpoints.chain_dest {
+        command.push_str(&format!(" --chain-dest {alias}"));
+    } else if let Some(url) = endpoints.rpc_dest {
+        command.push_str(&format!(" --rpc-dest {url}"));
+    }
+    command.push_str(&format!(" --tx {tx_hash:#x} --mode {mode}"));
+    if msg_index != 0 {
+        command.push_str(&format!(" --msg-index {msg_index}"));
+    }
+    if let Some(bundle_index) = bundle_index {
+        command.push_str(&format!(" --bundle-index {bundle_index}"));
+    }
+    command
+}
+
+/// Load the artifacts of an earlier relay `--out-dir` and check that they
+/// describe the same bundle, so the proof waits can be skipped.
+fn load_relay_artifacts(
+    dir: &Path,
+    center: Address,
+    bundle_identifier: u8,
+    destination_chain_id: u64,
+) -> Result<PreparedProof> {
+    let read = |name: &str| {
+        let path = dir.join(name);
+        fs::read_to_string(&path)
+            .with_context(|| format!("missing {} (expected a relay --out-dir)", path.display()))
+    };
+    let encoded_bundle = bytes_from_hex(&read("bundle.hex")?).context("invalid bundle.hex")?;
+    let proof: MessageInclusionProof =
+        serde_json::from_str(&read("proof.json")?).context("invalid proof.json")?;
+    proof.validate().context("invalid proof.json")?;
+    let summary: RelaySummary =
+        serde_json::from_str(&read("relay_summary.json")?).context("invalid relay_summary.json")?;
+
+    let bundle = decode_bundle(&encoded_bundle).context("invalid bundle.hex")?;
+    let source_chain_id: u64 = summary
+        .source_chain_id
+        .parse()
+        .context("invalid sourceChainId in relay_summary.json")?;
+    if bundle.sourceChainId != U256::from(source_chain_id)
+        || proof.chain_id != summary.source_chain_id
+    {
+        anyhow::bail!("bundle, proof, and summary disagree on the source chain");
+    }
+    if bundle.destinationChainId != U256::from(destination_chain_id) {
+        anyhow::bail!(
+            "bundle targets chain {} but the destination RPC is chain {destination_chain_id}",
+            bundle.destinationChainId
+        );
+    }
+    if proof.l1_batch_number != summary.l1_batch_number
+        || proof.l2_message_index != summary.l2_message_index
+    {
+        anyhow::bail!("proof.json does not match the batch/message index in relay_summary.json");
+    }
+    let expected_data = format_hex(&bundle_message(bundle_identifier, &encoded_bundle));
+    if !proof.message.data.eq_ignore_ascii_case(&expected_data) {
+        anyhow::bail!("proof.json message does not carry bundle.hex");
+    }
+    if !proof
+        .message
+        .sender
+        .eq_ignore_ascii_case(&format!("{center:#x}"))
+    {
+        anyhow::bail!(
+            "proof.json sender {} is not the interop center {center:#x}",
+            proof.message.sender
+        );
+    }
+    let bundle_hash = interop_bundle_hash(bundle.sourceChainId, &encoded_bundle);
+    if !summary
+        .bundle_hash
+        .eq_ignore_ascii_case(&format!("{bundle_hash:#x}"))
+    {
+        anyhow::bail!(
+            "bundle.hex hashes to {bundle_hash:#x}, but relay_summary.json has {}",
+            summary.bundle_hash
+        );
+    }
+
+    Ok(PreparedProof {
+        encoded_bundle,
+        proof,
+        bundle_hash,
+        source_tx_hash: summary.source_tx_hash,
+        source_chain_id,
+    })
+}
+
 /// Write relay artifacts (bundle, proof, summary) to a directory.
 async fn write_relay_outputs(
     dir: PathBuf,
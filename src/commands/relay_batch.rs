@@ -0,0 +1,236 @@
+use crate::cli::RelayBatchArgs;
+use crate::commands::relay::{
+    encode_handler_call, relay_rerun_command, HandlerOutcome, HandlerStep, RelayEndpoints,
+};
+use crate::commands::watch::load_tx_file;
+use crate::config::Config;
+use crate::interop::{prepare_proof, PreparedProof, SourceProver};
+use crate::output::emit;
+use crate::rpc::{RpcClient, TxOverrides};
+use crate::signer::{load_signer, signer_address, SignerOptions};
+use crate::types::{parse_checked_address, require_signer_or_dry_run, AddressBook, RelayResult};
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use tracing::Instrument;
+
+/// Relay the bundles of many source transactions.
+///
+/// Source proofs are fetched concurrently (up to `--concurrency`); handler
+/// transactions are sent one at a time from a locally tracked nonce.
+#[tracing::instrument(name = "bundle relay-batch", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref()))]
+pub async fn run(args: RelayBatchArgs, config: Config, addresses: AddressBook) -> Result<()> {
+    let handler = args
+        .handler
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid handler address")?
+        .unwrap_or(addresses.interop_handler);
+    let center = args
+        .center
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid center address")?
+        .unwrap_or(addresses.interop_center);
+    let root_storage = args
+        .root_storage
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid root storage address")?
+        .unwrap_or(addresses.interop_root_storage);
+    if !matches!(args.mode.as_str(), "verify" | "execute") {
+        anyhow::bail!("invalid mode {} (expected verify or execute)", args.mode);
+    }
+
+    let mut tx_hashes = load_tx_file(&args.tx_file)?;
+    let mut seen = HashSet::new();
+    tx_hashes.retain(|tx_hash| seen.insert(*tx_hash));
+    if tx_hashes.is_empty() {
+        anyhow::bail!("no transactions in {}", args.tx_file.display());
+    }
+
+    let wallet = load_signer(
+        SignerOptions {
+            private_key: args.signer.private_key.as_deref(),
+            private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
+        },
+        &config,
+    )
+    .await?;
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
+
+    require_signer_or_dry_run(
+        wallet.is_some(),
+        config.dry_run || args.gas_estimate_only,
+        "relay-batch",
+    )?;
+
+    let source_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
+    let source_client =
+        RpcClient::for_rpc(&source_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let destination_chain_id = dest_client.chain_id().await?;
+
+    let from = wallet.as_ref().map(signer_address).transpose()?;
+    let sending = !config.dry_run && !args.gas_estimate_only;
+    let provider = match wallet {
+        Some(wallet) if sending => Some(dest_client.signer_provider(wallet).await?),
+        _ => None,
+    };
+    // Nonces are assigned here rather than by the provider so that sends
+    // interleaved with other proofs still go out in a gap-free sequence.
+    let mut next_nonce = match (overrides.nonce, from) {
+        (Some(nonce), _) => Some(nonce),
+        (None, Some(from)) if sending => Some(
+            dest_client
+                .provider
+                .get_transaction_count(from)
+                .pending()
+                .await
+                .context("failed to fetch signer nonce")?,
+        ),
+        _ => None,
+    };
+    let step = HandlerStep {
+        client: &dest_client,
+        handler,
+        from,
+        provider: provider.as_ref(),
+        overrides: &overrides,
+        dry_run: config.dry_run,
+        gas_estimate_only: args.gas_estimate_only,
+        human: false,
+    };
+
+    let prover = SourceProver {
+        source_client,
+        dest_client: dest_client.clone(),
+        center,
+        l1_messenger: addresses.l1_messenger,
+        root_storage,
+        msg_index: args.msg_index,
+        bundle_index: None,
+        bundle_identifier: config.bundle_identifier,
+        limit: config.wait_limit(args.timeout_ms, args.max_wait_blocks),
+        poll: config.poll_interval(args.poll_ms),
+        confirmations: config.confirmations(args.confirmations),
+        cache_dir: args.proof_cache.clone(),
+    };
+    let endpoints = || RelayEndpoints {
+        rpc_src: args.rpc_src.as_deref(),
+        chain_src: args.chain_src.as_deref(),
+        rpc_dest: args.rpc_dest.as_deref(),
+        chain_dest: args.chain_dest.as_deref(),
+    };
+
+    let human = !config.json_output();
+    if human {
+        println!(
+            "relaying {} transactions ({} proofs at a time)",
+            tx_hashes.len(),
+            args.concurrency
+        );
+    }
+
+    let mut pending = tx_hashes.iter().copied().enumerate();
+    let mut tasks = tokio::task::JoinSet::new();
+    let mut spawn_next = |tasks: &mut tokio::task::JoinSet<_>| {
+        if let Some((index, tx_hash)) = pending.next() {
+            let prover = prover.clone();
+            let rerun = relay_rerun_command(endpoints(), tx_hash, &args.mode, args.msg_index, None);
+            // Proofs run concurrently, so tag each one's log lines with its tx.
+            let span = tracing::info_span!("proof", tx = %tx_hash);
+            tasks.spawn(
+                async move {
+                    let source = prepare_proof(&prover, tx_hash, &rerun).await;
+                    (index, tx_hash, source)
+                }
+                .instrument(span),
+            );
+        }
+    };
+    for _ in 0..args.concurrency {
+        spawn_next(&mut tasks);
+    }
+
+    let mut results = Vec::new();
+    let mut failed = 0usize;
+    while let Some(joined) = tasks.join_next().await {
+        let (index, tx_hash, source) = joined?;
+        spawn_next(&mut tasks);
+        let outcome = match source {
+            Ok(source) => relay_one(&step, &args.mode, source, next_nonce).await,
+            Err(err) => Err(err),
+        };
+        match outcome {
+            Ok((source, outcome)) => {
+                if outcome.handler_tx_hash.is_some() {
+                    next_nonce = next_nonce.map(|nonce| nonce + 1);
+                }
+                if human {
+                    print_outcome(tx_hash, &outcome);
+                }
+                let result = RelayResult {
+                    source_chain_id: source.source_chain_id.to_string(),
+                    destination_chain_id: destination_chain_id.to_string(),
+                    source_tx_hash: source.source_tx_hash,
+                    bundle_hash: format!("{:#x}", source.bundle_hash),
+                    l1_batch_number: source.proof.l1_batch_number,
+                    l2_message_index: source.proof.l2_message_index,
+                    mode: args.mode.clone(),
+                    dry_run: config.dry_run,
+                    dry_run_error: outcome.dry_run_error,
+                    handler_tx_hash: outcome.handler_tx_hash,
+                    estimated_gas: outcome.estimated_gas,
+                };
+                results.push((index, result));
+            }
+            Err(err) => {
+                failed += 1;
+                eprintln!("error: relay of {tx_hash:#x} failed: {err:#}");
+            }
+        }
+    }
+
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<RelayResult> = results.into_iter().map(|(_, result)| result).collect();
+    emit(&results, config.json_output(), args.out.as_deref())?;
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} relays failed", tx_hashes.len());
+    }
+    Ok(())
+}
+
+/// Run the destination handler step for one proven bundle.
+async fn relay_one(
+    step: &HandlerStep<'_>,
+    mode: &str,
+    source: PreparedProof,
+    nonce: Option<u64>,
+) -> Result<(PreparedProof, HandlerOutcome)> {
+    let calldata = encode_handler_call(mode, &source.encoded_bundle, &source.proof)?;
+    let outcome = step.submit(calldata, nonce).await?;
+    Ok((source, outcome))
+}
+
+/// Print a one-line summary of a finished relay.
+fn print_outcome(tx_hash: B256, outcome: &HandlerOutcome) {
+    if let Some(handler_tx_hash) = &outcome.handler_tx_hash {
+        println!("{tx_hash:#x}: sent tx {handler_tx_hash}");
+    } else if let Some(err) = &outcome.dry_run_error {
+        println!("{tx_hash:#x}: {err}");
+    } else if let Some(gas) = outcome.estimated_gas {
+        println!("{tx_hash:#x}: estimated gas {gas}");
+    } else {
+        println!("{tx_hash:#x}: dry-run success");
+    }
+}
@@ -1,44 +1,83 @@
 use crate::abi::{decode_bytes32, encode_interop_roots_call};
 use crate::cli::RootWaitArgs;
 use crate::config::Config;
-use crate::rpc::{eth_call, RpcClient};
+use crate::rpc::{eth_call, eth_call_at, interruptible, parse_block_id, RpcClient};
 use crate::types::{parse_b256, parse_u256, AddressBook};
 use alloy_primitives::{B256, U256};
 use anyhow::Result;
-use std::time::Duration;
 
 /// Wait until an expected interop root appears on the destination chain.
 ///
-/// Polls interopRoots(chainId, batchNumber) until timeout or match.
+/// Polls interopRoots(chainId, batchNumber) until timeout or match; with
+/// --block, reads it once as of that block instead.
+#[tracing::instrument(name = "debug root", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run(args: RootWaitArgs, config: Config, addresses: AddressBook) -> Result<()> {
-    let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
+    let resolved = args.rpc.resolve(&config)?;
+    let mut client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
     let chain_id = parse_u256(&args.source_chain)?;
     let expected_root = args.expected_root.as_ref().map(|x| parse_b256(x).unwrap());
-    let timeout = Duration::from_millis(args.timeout_ms.unwrap_or(300_000));
-    let poll = Duration::from_millis(args.poll_ms.unwrap_or(1_000));
-    let start = tokio::time::Instant::now();
-    let mut first_run = true;
-
-    loop {
+    if let Some(block) = args.block.as_deref() {
         let data = encode_interop_roots_call(chain_id, U256::from(args.batch));
-        let result = eth_call(&client, addresses.interop_root_storage, data).await?;
-        let root = decode_bytes32(result)?;
-        if root != B256::ZERO {
-            if expected_root.is_none_or(|x| root == *x) {
-                println!("interop root available: {root:#x}");
-                return Ok(());
-            }
-            let expected_root = expected_root.unwrap();
-            anyhow::bail!("interop root mismatch: expected {expected_root:#x}, got {root:#x}");
-        }
-        if start.elapsed() > timeout {
-            anyhow::bail!("interop root did not become available in time");
+        let root = decode_bytes32(
+            eth_call_at(
+                &client,
+                addresses.interop_root_storage,
+                data,
+                Some(parse_block_id(block)?),
+            )
+            .await?,
+        )?;
+        if root == B256::ZERO {
+            anyhow::bail!(
+                "interop root for chain {chain_id} batch {} not available at block {block}",
+                args.batch
+            );
         }
-        if first_run {
-            println!("waiting for interop root... up to {timeout:?}");
-            first_run = false;
+        if let Some(expected_root) = expected_root.filter(|expected| *expected != root) {
+            anyhow::bail!("interop root mismatch: expected {expected_root:#x}, got {root:#x}");
         }
-        tokio::time::sleep(poll).await;
+        println!("interop root at block {block}: {root:#x}");
+        return Ok(());
     }
+    let limit = config.wait_limit(args.timeout_ms, args.max_wait_blocks);
+    let poll = config.poll_interval(args.poll_ms);
+    let mut clock = limit.start();
+    let mut first_run = true;
+
+    let wait = async {
+        loop {
+            let data = encode_interop_roots_call(chain_id, U256::from(args.batch));
+            let result = match eth_call(&client, addresses.interop_root_storage, data).await {
+                Ok(result) => result,
+                Err(err) => {
+                    client = client.failover(err).await?;
+                    continue;
+                }
+            };
+            let root = decode_bytes32(result)?;
+            if root != B256::ZERO {
+                if expected_root.is_none_or(|x| root == *x) {
+                    println!("interop root available: {root:#x}");
+                    return Ok(());
+                }
+                let expected_root = expected_root.unwrap();
+                anyhow::bail!("interop root mismatch: expected {expected_root:#x}, got {root:#x}");
+            }
+            if clock.expired(&client).await {
+                anyhow::bail!("interop root did not become available in time");
+            }
+            if first_run {
+                tracing::info!(limit = %limit, "waiting for interop root");
+                first_run = false;
+            }
+            tokio::time::sleep(poll).await;
+        }
+    };
+    interruptible(wait, || {
+        format!(
+            "interop root for chain {chain_id} batch {} not available yet; rerun the same `debug root` command to keep waiting",
+            args.batch
+        )
+    })
+    .await
 }
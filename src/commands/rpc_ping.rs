@@ -1,11 +1,18 @@
 use crate::cli::RpcPingArgs;
+use crate::commands::token::format_units;
 use crate::config::Config;
-use crate::rpc::{get_finalized_block_number, raw_rpc, RpcClient};
+use crate::output::emit;
+use crate::rpc::{get_finalized_block_number, is_method_not_found, raw_rpc, RpcClient};
 use crate::types::AddressBook;
+use alloy_primitives::U256;
 use alloy_provider::Provider;
+use alloy_rpc_types::BlockNumberOrTag;
 use anyhow::Result;
 use serde::Serialize;
 use serde_json::json;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,14 +21,137 @@ struct RpcPingOutput {
     latest_block: Option<u64>,
     finalized_block: Option<String>,
     client_version: Option<String>,
+    /// `eth_gasPrice` in wei.
+    gas_price: Option<String>,
+    /// Latest block base fee in wei; unset before EIP-1559.
+    base_fee: Option<String>,
+    /// `eth_maxPriorityFeePerGas` in wei.
+    priority_fee: Option<String>,
+    methods: Vec<MethodSupport>,
+    /// Finalized blocks and log proofs are available, so proofs can be fetched.
+    interop_ready: bool,
+}
+
+/// Whether the RPC implements one zkSync-specific method.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MethodSupport {
+    method: String,
+    supported: bool,
+    /// Error from a supported method, e.g. rejected probe parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// One `--watch` sample.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcSample {
+    /// Probe number, from 1.
+    seq: u64,
+    /// Unix time in milliseconds when the probe started.
+    timestamp: u64,
+    chain_id: Probe,
+    finalized_block: Probe,
+}
+
+/// The timing of one call in a sample, with running stats over every
+/// successful call so far.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Probe {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    /// Round-trip time; unset when the call failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rtt_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avg_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_ms: Option<f64>,
+}
+
+/// Running latency stats for one call.
+#[derive(Default)]
+struct Latency {
+    count: u64,
+    total_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+}
+
+impl Latency {
+    /// Time `call`, folding a successful round trip into the stats.
+    async fn probe<T: Display, E: Display>(
+        &mut self,
+        call: impl Future<Output = std::result::Result<T, E>>,
+    ) -> Probe {
+        let started = Instant::now();
+        let result = call.await;
+        // Tenths of a millisecond; local nodes answer in well under 1 ms.
+        let rtt_ms = (started.elapsed().as_secs_f64() * 10_000.0).round() / 10.0;
+        let (value, rtt_ms, error) = match result {
+            Ok(value) => {
+                if self.count == 0 {
+                    self.min_ms = rtt_ms;
+                    self.max_ms = rtt_ms;
+                }
+                self.count += 1;
+                self.total_ms += rtt_ms;
+                self.min_ms = self.min_ms.min(rtt_ms);
+                self.max_ms = self.max_ms.max(rtt_ms);
+                (Some(value.to_string()), Some(rtt_ms), None)
+            }
+            Err(err) => (None, None, Some(err.to_string())),
+        };
+        let seen = self.count > 0;
+        Probe {
+            value,
+            rtt_ms,
+            error,
+            min_ms: seen.then_some(self.min_ms),
+            avg_ms: seen.then(|| (self.total_ms / self.count as f64 * 10.0).round() / 10.0),
+            max_ms: seen.then_some(self.max_ms),
+        }
+    }
+}
+
+/// zkSync methods other commands rely on, with harmless probe parameters.
+fn probed_methods() -> [(&'static str, serde_json::Value); 3] {
+    [
+        (
+            "zks_getL2ToL1LogProof",
+            json!([
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                0
+            ]),
+        ),
+        ("zks_getBridgehubContract", json!([])),
+        ("zks_L1BatchNumber", json!([])),
+    ]
 }
 
 /// Check RPC connectivity and feature support.
 ///
-/// Reports chain ID, latest/finalized blocks, and client version.
+/// Reports chain ID, latest/finalized blocks, client version, current fees,
+/// and which of the zkSync methods used for proofs and token sends the node
+/// implements.
+#[tracing::instrument(name = "debug rpc", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run(args: RpcPingArgs, config: Config, _addresses: AddressBook) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+
+    if args.watch {
+        return tokio::select! {
+            result = watch(&args, &config, &client) => result,
+            // Ctrl-C is how an open-ended watch normally ends.
+            _ = tokio::signal::ctrl_c() => Ok(()),
+        };
+    }
 
     let chain_id = client
         .provider
@@ -38,15 +168,53 @@ pub async fn run(args: RpcPingArgs, config: Config, _addresses: AddressBook) ->
         .await
         .ok();
 
+    let gas_price = client.provider.get_gas_price().await.ok();
+    let base_fee = client
+        .provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|block| block.header.base_fee_per_gas);
+    let priority_fee = client.provider.get_max_priority_fee_per_gas().await.ok();
+
+    let mut methods = Vec::new();
+    for (method, params) in probed_methods() {
+        let support = match raw_rpc::<serde_json::Value>(&client, method, params).await {
+            Ok(_) => MethodSupport {
+                method: method.to_string(),
+                supported: true,
+                error: None,
+            },
+            Err(err) => {
+                let supported = !is_method_not_found(&err);
+                MethodSupport {
+                    method: method.to_string(),
+                    supported,
+                    error: supported.then(|| err.to_string()),
+                }
+            }
+        };
+        methods.push(support);
+    }
+    let interop_ready = finalized_block.is_some()
+        && methods
+            .iter()
+            .any(|support| support.method == "zks_getL2ToL1LogProof" && support.supported);
+
     let output = RpcPingOutput {
         chain_id,
         latest_block,
         finalized_block,
         client_version,
+        gas_price: gas_price.map(|wei| wei.to_string()),
+        base_fee: base_fee.map(|wei| wei.to_string()),
+        priority_fee: priority_fee.map(|wei| wei.to_string()),
+        methods,
+        interop_ready,
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+    if emit(&output, config.json_output(), args.out.as_deref())? {
         return Ok(());
     }
 
@@ -78,6 +246,87 @@ pub async fn run(args: RpcPingArgs, config: Config, _addresses: AddressBook) ->
             .clone()
             .unwrap_or_else(|| "n/a".to_string())
     );
+    println!("gas price: {}", format_gwei(gas_price.map(U256::from)));
+    println!("base fee: {}", format_gwei(base_fee.map(U256::from)));
+    println!(
+        "priority fee: {}",
+        format_gwei(priority_fee.map(U256::from))
+    );
+    for support in &output.methods {
+        match (&support.error, support.supported) {
+            (_, false) => println!("{}: unsupported", support.method),
+            (Some(err), true) => println!("{}: supported ({err})", support.method),
+            (None, true) => println!("{}: supported", support.method),
+        }
+    }
+    println!(
+        "interop ready: {}",
+        if output.interop_ready { "yes" } else { "no" }
+    );
+
+    Ok(())
+}
 
+/// Probe `eth_chainId` and the finalized block every `--interval-ms`, printing
+/// one sample per probe (a compact JSON line under `--output json`).
+///
+/// Failed calls are reported and the watch carries on, so an outage shows up
+/// as a run of errors rather than ending the monitor.
+async fn watch(args: &RpcPingArgs, config: &Config, client: &RpcClient) -> Result<()> {
+    let interval = config.poll_interval(args.interval_ms);
+    let mut chain_id = Latency::default();
+    let mut finalized_block = Latency::default();
+    for seq in 1.. {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+        let sample = RpcSample {
+            seq,
+            timestamp,
+            chain_id: chain_id.probe(client.provider.get_chain_id()).await,
+            finalized_block: finalized_block
+                .probe(get_finalized_block_number(client))
+                .await,
+        };
+        if config.json_output() {
+            println!("{}", serde_json::to_string(&sample)?);
+        } else {
+            println!(
+                "[{seq}] {} | {}",
+                describe_probe("eth_chainId", &sample.chain_id),
+                describe_probe("finalized", &sample.finalized_block)
+            );
+        }
+        if args.count.is_some_and(|count| seq >= count) {
+            break;
+        }
+        tokio::time::sleep(interval).await;
+    }
     Ok(())
 }
+
+/// Render a probe as `name value: rtt ms (min/avg/max ...)`.
+fn describe_probe(name: &str, probe: &Probe) -> String {
+    let stats = match (probe.min_ms, probe.avg_ms, probe.max_ms) {
+        (Some(min), Some(avg), Some(max)) => format!(" (min/avg/max {min}/{avg}/{max} ms)"),
+        _ => String::new(),
+    };
+    match (&probe.value, probe.rtt_ms, &probe.error) {
+        (Some(value), Some(rtt), _) => format!("{name} {value}: {rtt} ms{stats}"),
+        // Decode errors carry the raw response on later lines.
+        (_, _, Some(err)) => format!(
+            "{name}: error: {}{stats}",
+            err.lines().next().unwrap_or_default()
+        ),
+        _ => format!("{name}: n/a{stats}"),
+    }
+}
+
+/// Render a fee in gwei with its wei value, or "n/a" when the RPC lacks it.
+fn format_gwei(wei: Option<U256>) -> String {
+    match wei {
+        Some(wei) => format!("{} gwei ({wei} wei)", format_units(wei, 9)),
+        None => "n/a".to_string(),
+    }
+}
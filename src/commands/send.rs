@@ -1,17 +1,23 @@
 use crate::abi::{decode_bytes32, encode_send_bundle_call, encode_send_message_call};
 use crate::cli::{SendBundleArgs, SendMessageArgs};
 use crate::commands::bundle_action::decode_send_transaction;
+use crate::commands::token::format_units;
 use crate::config::Config;
 use crate::encode::{
-    encode_evm_v1_address_only, encode_evm_v1_chain_only, encode_evm_v1_with_address,
-    encode_execution_address, encode_indirect_call, encode_interop_call_value,
-    encode_unbundler_address, parse_payload, parse_permissionless_address,
+    attribute_info, decode_attribute, decode_evm_v1_address, encode_evm_v1_address_only,
+    encode_evm_v1_chain_only, encode_evm_v1_with_address, encode_execution_address,
+    encode_indirect_call, encode_interop_call_value, encode_unbundler_address, parse_payload,
+    parse_permissionless_address, validate_attributes, AttributeScope,
 };
-use crate::rpc::{eth_call_with_value, RpcClient};
+use crate::output::{emit, read_stdin};
+use crate::rpc::{eth_call_with_value, RpcClient, TxOverrides};
 use crate::signer::{load_signer, SignerOptions};
-use crate::types::{parse_address, parse_u256, require_signer_or_dry_run, AddressBook};
+use crate::types::{
+    address_to_hex, check_distinct_chains, confirm_broadcast, parse_checked_address,
+    parse_value_with_units, require_signer_or_dry_run, AddressBook, RawCall,
+};
 use alloy_primitives::{Address, Bytes, B256, U256};
-use alloy_provider::{Provider, ProviderBuilder};
+use alloy_provider::Provider;
 use alloy_rpc_types::{TransactionInput, TransactionRequest};
 use anyhow::{Context, Result};
 use serde::Deserialize;
@@ -27,6 +33,26 @@ struct SendOutput {
     bundle_hash: Option<String>,
 }
 
+/// Decoded view of a sendMessage call for `--preview`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MessagePreview {
+    interop_center: String,
+    destination_chain_id: String,
+    recipient: String,
+    payload: String,
+    payload_len: usize,
+    attributes: Vec<PreviewAttribute>,
+    total_value: String,
+    total_value_ether: String,
+}
+
+#[derive(Debug, Serialize)]
+struct PreviewAttribute {
+    name: &'static str,
+    value: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct CallFile {
     calls: Vec<CallEntry>,
@@ -44,11 +70,15 @@ struct CallAttributesEntry {
     #[serde(rename = "interopValue")]
     interop_value: Option<String>,
     indirect: Option<String>,
+    #[serde(rename = "executionAddress")]
+    execution_address: Option<String>,
+    unbundler: Option<String>,
 }
 
 /// Send a single interop message from the source chain.
 ///
 /// Builds call attributes, sends the transaction, and prints the sendId.
+#[tracing::instrument(name = "send message", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run_message(
     args: SendMessageArgs,
     config: Config,
@@ -56,26 +86,57 @@ pub async fn run_message(
 ) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
     let dest_chain_id = config.resolve_chain_id(&args.to_chain)?;
-    let to = parse_address(&args.to)?;
-    let payload = parse_payload(args.payload.as_deref(), args.payload_file.as_deref())?;
+    check_destination_rpc(&config, args.rpc_dest.as_deref(), dest_chain_id).await?;
+    let to = parse_checked_address(&args.to)?;
+    let payload = parse_payload(
+        args.payload.as_deref(),
+        args.payload_file.as_deref(),
+        args.interop_value.is_some() || args.indirect.is_some(),
+    )?;
 
     let attributes = build_message_attributes(&args, dest_chain_id)?;
+    validate_attributes(&attributes, AttributeScope::CallAndBundle)?;
     let msg_value = message_value(&args)?;
     let recipient = encode_evm_v1_with_address(dest_chain_id, to);
-    let calldata = encode_send_message_call(recipient, payload, attributes.clone())?;
+    let calldata =
+        encode_send_message_call(recipient.clone(), payload.clone(), attributes.clone())?;
 
-    let client = RpcClient::new(&resolved.url).await?;
+    if args.preview {
+        let preview = message_preview(
+            addresses.interop_center,
+            &recipient,
+            &payload,
+            &attributes,
+            msg_value,
+        )?;
+        return print_message_preview(&preview, &config);
+    }
+    if args.calldata_only {
+        let call = RawCall::new(None, addresses.interop_center, msg_value, &calldata);
+        return print_raw_call(&call, &config);
+    }
 
-    if args.dry_run {
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
+
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+    check_distinct_chains(
+        client.chain_id().await?,
+        dest_chain_id,
+        args.allow_same_chain,
+    )?;
+
+    if config.dry_run {
         let result = eth_call_with_value(
             &client,
             addresses.interop_center,
             calldata.clone(),
             Some(msg_value),
+            None,
         )
         .await?;
         let send_id = decode_bytes32(result)?;
-        if args.json {
+        if config.json_output() {
             println!(
                 "{}",
                 serde_json::to_string_pretty(&serde_json::json!({
@@ -93,26 +154,38 @@ pub async fn run_message(
         SignerOptions {
             private_key: args.signer.private_key.as_deref(),
             private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
         },
         &config,
+    )
+    .await?;
+    require_signer_or_dry_run(wallet.is_some(), config.dry_run, "send message")?;
+    confirm_broadcast(
+        "send a message",
+        &[
+            ("destination chain", dest_chain_id.to_string()),
+            ("recipient", address_to_hex(to)),
+            (
+                "total value",
+                format!("{} ({msg_value} wei)", format_units(msg_value, 18)),
+            ),
+        ],
+        config.yes,
     )?;
-    require_signer_or_dry_run(wallet.is_some(), args.dry_run, "send message")?;
 
     let wallet = wallet.expect("wallet required");
-    let chain_id = client.provider.get_chain_id().await?;
-    let provider = ProviderBuilder::new()
-        .wallet(wallet)
-        .with_chain_id(chain_id)
-        .connect(&resolved.url)
-        .await?;
+    let provider = client.signer_provider(wallet).await?;
 
-    let request = TransactionRequest {
+    let mut request = TransactionRequest {
         to: Some(addresses.interop_center.into()),
         input: TransactionInput::new(calldata),
         value: Some(msg_value),
         ..Default::default()
     };
 
+    overrides.apply(&client, &mut request).await?;
+
     let pending = decode_send_transaction(provider.send_transaction(request).await)?;
 
     let tx_hash = pending.tx_hash().clone();
@@ -127,9 +200,7 @@ pub async fn run_message(
         bundle_hash: None,
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
-    } else {
+    if !emit(&output, config.json_output(), args.out.as_deref())? {
         println!("tx hash: {}", output.tx_hash);
         println!("status: {}", output.status);
         if let Some(send_id) = output.send_id {
@@ -143,6 +214,7 @@ pub async fn run_message(
 /// Send a bundle of interop calls from the source chain.
 ///
 /// Reads calls.json, builds call starters, and prints the bundle hash.
+#[tracing::instrument(name = "send bundle", skip_all, fields(chain = args.rpc.chain.as_deref()))]
 pub async fn run_bundle(
     args: SendBundleArgs,
     config: Config,
@@ -150,24 +222,45 @@ pub async fn run_bundle(
 ) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
     let dest_chain_id = config.resolve_chain_id(&args.to_chain)?;
+    check_destination_rpc(&config, args.rpc_dest.as_deref(), dest_chain_id).await?;
     let file = load_calls(&args.calls)?;
 
-    let (call_starters, total_value) = build_call_starters(&file.calls)?;
-    let bundle_attributes = build_bundle_attributes(&args, dest_chain_id)?;
+    let CallStarters {
+        starters: call_starters,
+        total_value,
+        bundle_attributes: call_bundle_attributes,
+    } = build_call_starters(&file.calls, dest_chain_id)?;
+    let mut bundle_attributes = build_bundle_attributes(&args, dest_chain_id)?;
+    merge_call_bundle_attributes(&mut bundle_attributes, call_bundle_attributes)?;
+    validate_attributes(&bundle_attributes, AttributeScope::Bundle)?;
     let destination_chain = encode_evm_v1_chain_only(dest_chain_id);
     let calldata = encode_send_bundle_call(destination_chain, call_starters, bundle_attributes)?;
 
-    let client = RpcClient::new(&resolved.url).await?;
-    if args.dry_run {
+    if args.calldata_only {
+        let call = RawCall::new(None, addresses.interop_center, total_value, &calldata);
+        return print_raw_call(&call, &config);
+    }
+
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
+
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+    check_distinct_chains(
+        client.chain_id().await?,
+        dest_chain_id,
+        args.allow_same_chain,
+    )?;
+    if config.dry_run {
         let result = eth_call_with_value(
             &client,
             addresses.interop_center,
             calldata.clone(),
             Some(total_value),
+            None,
         )
         .await?;
         let bundle_hash = decode_bytes32(result)?;
-        if args.json {
+        if config.json_output() {
             println!(
                 "{}",
                 serde_json::to_string_pretty(&serde_json::json!({
@@ -185,26 +278,38 @@ pub async fn run_bundle(
         SignerOptions {
             private_key: args.signer.private_key.as_deref(),
             private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
         },
         &config,
+    )
+    .await?;
+    require_signer_or_dry_run(wallet.is_some(), config.dry_run, "send bundle")?;
+    confirm_broadcast(
+        "send a bundle",
+        &[
+            ("destination chain", dest_chain_id.to_string()),
+            ("calls", file.calls.len().to_string()),
+            (
+                "total value",
+                format!("{} ({total_value} wei)", format_units(total_value, 18)),
+            ),
+        ],
+        config.yes,
     )?;
-    require_signer_or_dry_run(wallet.is_some(), args.dry_run, "send bundle")?;
 
     let wallet = wallet.expect("wallet required");
-    let chain_id = client.provider.get_chain_id().await?;
-    let provider = ProviderBuilder::new()
-        .wallet(wallet)
-        .with_chain_id(chain_id)
-        .connect(&resolved.url)
-        .await?;
+    let provider = client.signer_provider(wallet).await?;
 
-    let request = TransactionRequest {
+    let mut request = TransactionRequest {
         to: Some(addresses.interop_center.into()),
         input: TransactionInput::new(calldata),
         value: Some(total_value),
         ..Default::default()
     };
 
+    overrides.apply(&client, &mut request).await?;
+
     let pending = decode_send_transaction(provider.send_transaction(request).await)?;
 
     let tx_hash = pending.tx_hash().clone();
@@ -219,9 +324,7 @@ pub async fn run_bundle(
         bundle_hash: bundle_hash.map(|hash| format!("{hash:#x}")),
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
-    } else {
+    if !emit(&output, config.json_output(), args.out.as_deref())? {
         println!("tx hash: {}", output.tx_hash);
         println!("status: {}", output.status);
         if let Some(bundle_hash) = output.bundle_hash {
@@ -231,31 +334,92 @@ pub async fn run_bundle(
     Ok(())
 }
 
+/// Decode the encoded sendMessage arguments back into a readable preview,
+/// so what is shown is exactly what would be sent.
+fn message_preview(
+    interop_center: Address,
+    recipient: &Bytes,
+    payload: &Bytes,
+    attributes: &[Bytes],
+    total_value: U256,
+) -> Result<MessagePreview> {
+    let (destination_chain_id, recipient_address) = decode_evm_v1_address(recipient)?;
+    let recipient_address =
+        recipient_address.ok_or_else(|| anyhow::anyhow!("recipient carries no address"))?;
+    let attributes = attributes
+        .iter()
+        .map(|attribute| {
+            let decoded = decode_attribute(attribute)?;
+            Ok(PreviewAttribute {
+                name: decoded.name(),
+                value: decoded.value_string(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(MessagePreview {
+        interop_center: format!("{interop_center:#x}"),
+        destination_chain_id: destination_chain_id.to_string(),
+        recipient: format!("{recipient_address:#x}"),
+        payload: crate::types::format_hex(payload),
+        payload_len: payload.len(),
+        attributes,
+        total_value: total_value.to_string(),
+        total_value_ether: format_units(total_value, 18),
+    })
+}
+
+/// Print a `--preview` of a message.
+fn print_message_preview(preview: &MessagePreview, config: &Config) -> Result<()> {
+    if emit(preview, config.json_output(), None)? {
+        return Ok(());
+    }
+    println!("sendMessage preview");
+    println!("  interopCenter: {}", preview.interop_center);
+    println!("  destinationChainId: {}", preview.destination_chain_id);
+    println!("  recipient: {}", preview.recipient);
+    println!(
+        "  payload ({} bytes): {}",
+        preview.payload_len, preview.payload
+    );
+    if preview.attributes.is_empty() {
+        println!("  attributes: none");
+    } else {
+        println!("  attributes:");
+        for attribute in &preview.attributes {
+            println!("    {}: {}", attribute.name, attribute.value);
+        }
+    }
+    println!(
+        "  totalValue: {} wei ({} ether)",
+        preview.total_value, preview.total_value_ether
+    );
+    Ok(())
+}
+
+/// Print an unsigned interop center call for `--calldata-only`.
+fn print_raw_call(call: &RawCall, config: &Config) -> Result<()> {
+    if !emit(call, config.json_output(), None)? {
+        println!("to: {}", call.to);
+        println!("value: {}", call.value);
+        println!("data: {}", call.data);
+    }
+    Ok(())
+}
+
 /// Build attribute bytes for an interop message.
 fn build_message_attributes(args: &SendMessageArgs, dest_chain_id: U256) -> Result<Vec<Bytes>> {
     let mut attributes: Vec<Bytes> = Vec::new();
     if let Some(value) = args.interop_value.as_deref() {
-        attributes.push(encode_interop_call_value(parse_u256(value)?));
+        attributes.push(encode_interop_call_value(parse_value_with_units(value)?));
     }
     if let Some(value) = args.indirect.as_deref() {
-        attributes.push(encode_indirect_call(parse_u256(value)?));
+        attributes.push(encode_indirect_call(parse_value_with_units(value)?));
     }
     if let Some(value) = args.execution_address.as_deref() {
-        let encoded = match parse_permissionless_address(value)? {
-            None => Bytes::new(),
-            Some(addr) => encode_evm_v1_with_address(dest_chain_id, addr),
-        };
-        attributes.push(encode_execution_address(encoded));
+        attributes.push(execution_address_attribute(value, dest_chain_id)?);
     }
     if let Some(value) = args.unbundler.as_deref() {
-        if value == "permissionless" {
-            anyhow::bail!("unbundler cannot be permissionless");
-        }
-        let addr = parse_address(value)?;
-        attributes.push(encode_unbundler_address(encode_evm_v1_with_address(
-            dest_chain_id,
-            addr,
-        )));
+        attributes.push(unbundler_attribute(value, dest_chain_id, "unbundler")?);
     }
     Ok(attributes)
 }
@@ -264,52 +428,86 @@ fn build_message_attributes(args: &SendMessageArgs, dest_chain_id: U256) -> Resu
 fn message_value(args: &SendMessageArgs) -> Result<U256> {
     let mut total = U256::ZERO;
     if let Some(value) = args.interop_value.as_deref() {
-        total += parse_u256(value)?;
+        total += parse_value_with_units(value)?;
     }
     if let Some(value) = args.indirect.as_deref() {
-        total += parse_u256(value)?;
+        total += parse_value_with_units(value)?;
     }
     Ok(total)
 }
 
+/// Interop call starters built from calls.json.
+struct CallStarters {
+    starters: Vec<crate::abi::InteropCallStarter>,
+    total_value: U256,
+    /// Bundle-level attributes set on individual calls, with their call index.
+    bundle_attributes: Vec<(usize, Bytes)>,
+}
+
 /// Build interop call starters from a calls.json payload.
-fn build_call_starters(calls: &[CallEntry]) -> Result<(Vec<crate::abi::InteropCallStarter>, U256)> {
+fn build_call_starters(calls: &[CallEntry], dest_chain_id: U256) -> Result<CallStarters> {
     let mut starters = Vec::new();
     let mut total_value = U256::ZERO;
+    let mut bundle_attributes = Vec::new();
 
-    for call in calls {
-        let to = parse_address(&call.to)?;
+    for (idx, call) in calls.iter().enumerate() {
+        let to = parse_checked_address(&call.to)?;
         let data = crate::types::bytes_from_hex(&call.data)?;
-        let (attributes, value) = build_call_attributes(call.attributes.as_ref())?;
+        let (attributes, value, bundle) =
+            build_call_attributes(call.attributes.as_ref(), dest_chain_id)
+                .with_context(|| format!("invalid attributes for call[{idx}]"))?;
+        validate_attributes(&attributes, AttributeScope::Call)
+            .with_context(|| format!("invalid attributes for call[{idx}]"))?;
         total_value += value;
+        bundle_attributes.extend(bundle.into_iter().map(|attribute| (idx, attribute)));
         starters.push(crate::abi::InteropCallStarter {
             to: encode_evm_v1_address_only(to),
             data,
             callAttributes: attributes,
         });
     }
-    Ok((starters, total_value))
+    Ok(CallStarters {
+        starters,
+        total_value,
+        bundle_attributes,
+    })
 }
 
 /// Build per-call attributes and aggregate their value.
-fn build_call_attributes(attributes: Option<&CallAttributesEntry>) -> Result<(Vec<Bytes>, U256)> {
+///
+/// `executionAddress` and `unbundler` are bundle attributes in the interop
+/// center, so they are returned separately for the bundle-level list.
+fn build_call_attributes(
+    attributes: Option<&CallAttributesEntry>,
+    dest_chain_id: U256,
+) -> Result<(Vec<Bytes>, U256, Vec<Bytes>)> {
     let mut output = Vec::new();
     let mut value = U256::ZERO;
+    let mut bundle = Vec::new();
 
     if let Some(attributes) = attributes {
         if let Some(interop_value) = attributes.interop_value.as_deref() {
-            let parsed = parse_u256(interop_value)?;
+            let parsed = parse_value_with_units(interop_value)?;
             value += parsed;
             output.push(encode_interop_call_value(parsed));
         }
         if let Some(indirect) = attributes.indirect.as_deref() {
-            let parsed = parse_u256(indirect)?;
+            let parsed = parse_value_with_units(indirect)?;
             value += parsed;
             output.push(encode_indirect_call(parsed));
         }
+        if let Some(execution_address) = attributes.execution_address.as_deref() {
+            bundle.push(execution_address_attribute(
+                execution_address,
+                dest_chain_id,
+            )?);
+        }
+        if let Some(unbundler) = attributes.unbundler.as_deref() {
+            bundle.push(unbundler_attribute(unbundler, dest_chain_id, "unbundler")?);
+        }
     }
 
-    Ok((output, value))
+    Ok((output, value, bundle))
 }
 
 /// Build bundle-level attributes for execution and unbundler permissions.
@@ -317,29 +515,102 @@ fn build_bundle_attributes(args: &SendBundleArgs, dest_chain_id: U256) -> Result
     let mut attributes = Vec::new();
 
     if let Some(value) = args.bundle_execution_address.as_deref() {
-        let encoded = match parse_permissionless_address(value)? {
-            None => Bytes::new(),
-            Some(addr) => encode_evm_v1_with_address(dest_chain_id, addr),
-        };
-        attributes.push(encode_execution_address(encoded));
+        attributes.push(execution_address_attribute(value, dest_chain_id)?);
     }
     if let Some(value) = args.bundle_unbundler.as_deref() {
-        if value == "permissionless" {
-            anyhow::bail!("bundle unbundler cannot be permissionless");
-        }
-        let addr = parse_address(value)?;
-        attributes.push(encode_unbundler_address(encode_evm_v1_with_address(
+        attributes.push(unbundler_attribute(
+            value,
             dest_chain_id,
-            addr,
-        )));
+            "bundle unbundler",
+        )?);
     }
 
     Ok(attributes)
 }
 
-/// Load and validate a calls.json payload.
+/// Merge the bundle-level attributes set in calls.json into `bundle`.
+///
+/// Calls may repeat an attribute, but every setting (including the
+/// `--bundle-*` flags) must agree.
+fn merge_call_bundle_attributes(
+    bundle: &mut Vec<Bytes>,
+    from_calls: Vec<(usize, Bytes)>,
+) -> Result<()> {
+    for (idx, attribute) in from_calls {
+        match bundle
+            .iter()
+            .find(|existing| existing.get(..4) == attribute.get(..4))
+        {
+            Some(existing) if *existing == attribute => {}
+            Some(_) => {
+                let name = attribute
+                    .get(..4)
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .and_then(attribute_info)
+                    .map(|(name, _)| name)
+                    .unwrap_or("attribute");
+                anyhow::bail!(
+                    "call[{idx}] sets {name} differently from an earlier call or --bundle-* flag (it applies to the whole bundle)"
+                );
+            }
+            None => bundle.push(attribute),
+        }
+    }
+    Ok(())
+}
+
+/// Encode an executionAddress attribute from an address or "permissionless".
+fn execution_address_attribute(value: &str, dest_chain_id: U256) -> Result<Bytes> {
+    let encoded = match parse_permissionless_address(value)? {
+        None => Bytes::new(),
+        Some(addr) => encode_evm_v1_with_address(dest_chain_id, addr),
+    };
+    Ok(encode_execution_address(encoded))
+}
+
+/// Encode an unbundlerAddress attribute; unlike execution, it cannot be
+/// permissionless.
+fn unbundler_attribute(value: &str, dest_chain_id: U256, label: &str) -> Result<Bytes> {
+    if value == "permissionless" {
+        anyhow::bail!("{label} cannot be permissionless");
+    }
+    let addr = parse_checked_address(value)?;
+    Ok(encode_unbundler_address(encode_evm_v1_with_address(
+        dest_chain_id,
+        addr,
+    )))
+}
+
+/// Compare the resolved `--to-chain` with the chain behind `--rpc-dest`,
+/// warning when they differ.
+async fn check_destination_rpc(
+    config: &Config,
+    rpc_dest: Option<&str>,
+    dest_chain_id: U256,
+) -> Result<()> {
+    let Some(url) = rpc_dest else {
+        return Ok(());
+    };
+    let resolved = config.resolve_rpc(Some(url), None)?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers)
+        .await
+        .context("failed to connect to --rpc-dest")?;
+    let rpc_chain_id = client.chain_id().await?;
+    if U256::from(rpc_chain_id) != dest_chain_id {
+        eprintln!(
+            "warning: --to-chain resolves to chain {dest_chain_id}, but --rpc-dest is chain {rpc_chain_id}"
+        );
+    }
+    Ok(())
+}
+
+/// Load and validate a calls.json payload; a path of `-` reads stdin.
 fn load_calls(path: &std::path::Path) -> Result<CallFile> {
-    let contents = fs::read_to_string(path).context("failed to read calls.json")?;
+    let contents = if path == std::path::Path::new("-") {
+        read_stdin("--calls")?
+    } else {
+        fs::read_to_string(path).context("failed to read calls.json")?
+    };
     let file: CallFile = serde_json::from_str(&contents).context("invalid calls.json")?;
     if file.calls.is_empty() {
         anyhow::bail!("calls.json must include at least one call");
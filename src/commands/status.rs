@@ -1,34 +1,38 @@
 use crate::abi::{
-    decode_bundle_status, decode_call_status, encode_bundle_status_call, encode_call_status_call,
+    decode_bundle, decode_bundle_status, decode_call_status, encode_bundle_status_call,
+    encode_call_status_call,
 };
 use crate::cli::StatusArgs;
 use crate::config::Config;
-use crate::rpc::{eth_call, RpcClient};
+use crate::output::{emit, read_stdin};
+use crate::rpc::{eth_call_at, parse_block_id, RpcClient};
 use crate::types::{bytes_from_hex, parse_b256, AddressBook, CallStatusView, StatusOutput};
 use alloy_primitives::U256;
-use alloy_sol_types::SolValue;
 use anyhow::Result;
 
 /// Fetch bundle status (and optional per-call status) from the handler.
 ///
-/// Use this to verify whether a bundle has been verified or executed.
+/// Use this to verify whether a bundle has been verified or executed, now or
+/// as of an earlier --block.
+#[tracing::instrument(name = "bundle status", skip_all, fields(chain = args.rpc.chain.as_deref(), bundle_hash = %args.bundle_hash))]
 pub async fn run(args: StatusArgs, config: Config, addresses: AddressBook) -> Result<()> {
-    let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
+    let resolved = args.rpc.resolve(&config)?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
     let bundle_hash = parse_b256(&args.bundle_hash)?;
+    let block = args.block.as_deref().map(parse_block_id).transpose()?;
     let call = encode_bundle_status_call(bundle_hash);
-    let result = eth_call(&client, addresses.interop_handler, call).await?;
+    let result = eth_call_at(&client, addresses.interop_handler, call, block).await?;
     let status_value = decode_bundle_status(result)?;
     let bundle_status = bundle_status_string(status_value);
 
     let calls = if let Some(bundle_hex) = args.bundle.as_deref() {
         let bytes = load_hex_or_path(bundle_hex)?;
-        let bundle: crate::types::InteropBundle = crate::types::InteropBundle::abi_decode(&bytes)?;
+        let bundle = decode_bundle(&bytes)?;
 
         let mut statuses = Vec::new();
         for (idx, _) in bundle.calls.iter().enumerate() {
             let call = encode_call_status_call(bundle_hash, U256::from(idx));
-            let data = eth_call(&client, addresses.interop_handler, call).await?;
+            let data = eth_call_at(&client, addresses.interop_handler, call, block).await?;
             let status = decode_call_status(data)?;
             statuses.push(CallStatusView {
                 index: idx as u64,
@@ -44,14 +48,17 @@ pub async fn run(args: StatusArgs, config: Config, addresses: AddressBook) -> Re
         bundle_hash: format!("{bundle_hash:#x}"),
         bundle_status: bundle_status.clone(),
         calls: calls.clone(),
+        block: args.block.clone(),
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+    if emit(&output, config.json_output(), args.out.as_deref())? {
         return Ok(());
     }
 
     println!("bundleHash: {bundle_hash:#x}");
+    if let Some(block) = args.block.as_deref() {
+        println!("block: {block}");
+    }
     println!("bundleStatus: {bundle_status}");
     if let Some(call_statuses) = calls {
         for call in call_statuses {
@@ -65,8 +72,11 @@ pub async fn run(args: StatusArgs, config: Config, addresses: AddressBook) -> Re
     Ok(())
 }
 
-/// Load a bundle hex string from inline text or file.
+/// Load a bundle hex string from inline text, a file, or stdin (`-`).
 fn load_hex_or_path(value: &str) -> Result<Vec<u8>> {
+    if value == "-" {
+        return bytes_from_hex(&read_stdin("--bundle")?).map(|bytes| bytes.0.to_vec());
+    }
     if std::path::Path::new(value).exists() {
         let contents = std::fs::read_to_string(value)?;
         return bytes_from_hex(&contents).map(|bytes| bytes.0.to_vec());
@@ -1,102 +1,314 @@
 use crate::abi::{
-    decode_interop_bundle_sent, encode_bundle_status_call, encode_execute_bundle_call,
-    encode_interop_bundle, encode_send_bundle_call, encode_verify_bundle_call,
-    interop_bundle_sent_topic,
+    encode_bundle_status_call, encode_execute_bundle_call, encode_send_bundle_call,
+    encode_verify_bundle_call,
 };
-use crate::cli::{TokenBalanceArgs, TokenInfoArgs, TokenSendArgs};
-use crate::commands::bundle_action::decode_send_transaction;
+use crate::cli::{
+    TokenAllowanceArgs, TokenBalanceArgs, TokenFromAssetIdArgs, TokenInfoArgs, TokenRegisterArgs,
+    TokenSendArgs, TokenSendBatchArgs, TokenSendContinueArgs,
+};
+use crate::commands::bundle_action::{decode_send_transaction, describe_dry_run_failure};
+use crate::commands::explain::check_permissions;
+use crate::commands::token_interface::{print_interface, probe_token, TokenInterface};
 use crate::config::{Config, ResolvedRpc};
 use crate::encode::{
-    encode_asset_id, encode_evm_v1_address_only, encode_evm_v1_chain_only, encode_indirect_call,
-    encode_interop_call_value, encode_unbundler_address, DEFAULT_NATIVE_TOKEN_VAULT,
+    decode_attribute, encode_asset_id, encode_evm_v1_address_only, encode_evm_v1_chain_only,
+    encode_indirect_call, encode_interop_call_value, encode_unbundler_address, DecodedAttr,
 };
+use crate::interop::{prepare_proof, select_sent_bundle, PreparedProof, SourceProver};
+use crate::output::{emit, write_atomic};
 use crate::rpc::{
-    eth_call, eth_call_with_value, get_transaction_receipt, wait_for_finalized_block,
-    wait_for_log_proof, RpcClient,
+    eth_call, eth_call_at, eth_call_with_value, get_transaction_receipt, parse_block_id, RpcClient,
+    TxOverrides, WaitLimit,
 };
-use crate::signer::{load_signer, SignerOptions};
+use crate::signer::{load_signer, signer_address, SignerOptions};
 use crate::types::{
-    address_to_hex, format_hex, parse_address, parse_u256, require_signer_or_dry_run, AddressBook,
-    MessageInclusionProof, ProofMessage, BUNDLE_IDENTIFIER, DEFAULT_ASSET_ROUTER,
+    address_to_hex, check_distinct_chains, confirm_broadcast, format_hex, parse_b256,
+    parse_checked_address, parse_decimal_amount, parse_u256, parse_value_with_units,
+    require_signer_or_dry_run, AddressBook, InteropBundle, RawCall, TokenSendBatchResult,
+    TokenSendResult, L2_BRIDGEHUB_ADDRESS,
 };
 use alloy_primitives::{Address, Bytes, B256, U256};
-use alloy_provider::{Provider, ProviderBuilder};
-use alloy_rpc_types::{TransactionInput, TransactionRequest};
+use alloy_provider::network::EthereumWallet;
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, TransactionInput, TransactionReceipt, TransactionRequest};
 use alloy_sol_types::{SolCall, SolValue};
-use anyhow::{anyhow, Result};
-use serde::Serialize;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
 
 alloy_sol_types::sol! {
     function balanceOf(address account) view returns (uint256);
     function approve(address spender, uint256 value) returns (bool);
+    function allowance(address owner, address spender) view returns (uint256);
     function decimals() view returns (uint8);
     function symbol() view returns (string);
     function name() view returns (string);
 
     function ensureTokenIsRegistered(address _token) returns (bytes32);
     function tokenAddress(bytes32 _assetId) view returns (address);
+
+    function baseTokenAssetId(uint256 _chainId) view returns (bytes32);
 }
 
 const NEW_ENCODING_VERSION: u8 = 0x01;
 
+#[derive(Debug, Deserialize)]
+struct RecipientFile {
+    recipients: Vec<RecipientEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecipientEntry {
+    to: String,
+    amount: Option<String>,
+    #[serde(rename = "amountWei")]
+    amount_wei: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenFromAssetIdOutput {
+    chain_id: String,
+    native_token_vault: String,
+    asset_id: String,
+    token: String,
+    symbol: Option<String>,
+    name: Option<String>,
+    decimals: Option<u8>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct TokenInfoOutput {
-    src_chain_id: String,
+    src_chain_id: Option<String>,
     dest_chain_id: String,
-    token_on_src: String,
+    token_on_src: Option<String>,
     native_token_vault: String,
     asset_id: String,
+    /// "provided" (--asset-id) or "computed" (from --token).
+    asset_id_source: String,
     wrapped_token_on_dest: String,
     symbol: Option<String>,
     name: Option<String>,
     decimals: Option<u8>,
+    /// Per-function status of the wrapped token; unset until it is deployed.
+    wrapped_token_interface: Option<TokenInterface>,
+    /// Per-function status of `--token` on the source chain.
+    source_token_interface: Option<TokenInterface>,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct TokenBalanceOutput {
-    src_chain_id: String,
+    src_chain_id: Option<String>,
     dest_chain_id: String,
-    token_on_src: String,
+    token_on_src: Option<String>,
     native_token_vault: String,
     asset_id: String,
+    /// "provided" (--asset-id) or "computed" (from --token).
+    asset_id_source: String,
     wrapped_token_on_dest: String,
     balance: Option<String>,
     balance_raw: Option<String>,
     decimals: Option<u8>,
+    symbol: Option<String>,
+    /// The --block the balance was read at, when not the latest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenAllowanceOutput {
+    chain_id: String,
+    token: String,
+    owner: String,
+    spender: String,
+    allowance: String,
+    allowance_formatted: Option<String>,
+}
+
+/// How `token info`/`token balance` pick the asset.
+struct AssetSelection<'a> {
+    token: Option<&'a str>,
+    asset_id: Option<&'a str>,
+    rpc_src: Option<&'a str>,
+    chain_src: Option<&'a str>,
+    vault: Address,
+}
+
+/// The asset ID to look up, with the source token it was derived from.
+struct ResolvedAsset {
+    asset_id: Bytes,
+    src_chain_id: Option<u64>,
+    token: Option<Address>,
+    /// Source chain client, connected only when the asset ID was derived.
+    src_client: Option<RpcClient>,
+}
+
+impl ResolvedAsset {
+    fn source(&self) -> &'static str {
+        if self.token.is_some() {
+            "computed"
+        } else {
+            "provided"
+        }
+    }
+}
+
+/// Take `--asset-id` as given, or derive it from `--token`, the vault, and
+/// the source chain ID (the only case that needs the source RPC).
+async fn resolve_asset(selection: AssetSelection<'_>, config: &Config) -> Result<ResolvedAsset> {
+    if let Some(asset_id) = selection.asset_id {
+        return Ok(ResolvedAsset {
+            asset_id: Bytes::from(parse_b256(asset_id)?.to_vec()),
+            src_chain_id: None,
+            token: None,
+            src_client: None,
+        });
+    }
+    let token = selection
+        .token
+        .ok_or_else(|| anyhow!("set --token or --asset-id"))?;
+    let token = parse_checked_address(token)?;
+    let src_rpc = config.resolve_rpc(selection.rpc_src, selection.chain_src)?;
+    let src_client = RpcClient::for_rpc(&src_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let src_chain_id = src_client.chain_id().await?;
+    Ok(ResolvedAsset {
+        asset_id: encode_asset_id(U256::from(src_chain_id), token, selection.vault),
+        src_chain_id: Some(src_chain_id),
+        token: Some(token),
+        src_client: Some(src_client),
+    })
 }
 
 /// Resolve wrapped token metadata on the destination chain.
 ///
 /// Returns the asset ID plus optional symbol/name/decimals if the wrapped
-/// token has been deployed.
-pub async fn run_info(args: TokenInfoArgs, config: Config, _addresses: AddressBook) -> Result<()> {
-    let src_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
+/// token has been deployed, and for it and the source token which of those
+/// functions are implemented.
+#[tracing::instrument(name = "token info", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref()))]
+pub async fn run_info(args: TokenInfoArgs, config: Config, addresses: AddressBook) -> Result<()> {
     let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
-    let src_client = RpcClient::new(&src_rpc.url).await?;
-    let dest_client = RpcClient::new(&dest_rpc.url).await?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let dest_chain_id = dest_client.chain_id().await?;
 
-    let src_chain_id = src_client.provider.get_chain_id().await?;
-    let dest_chain_id = dest_client.provider.get_chain_id().await?;
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+    let asset = resolve_asset(
+        AssetSelection {
+            token: args.token.as_deref(),
+            asset_id: args.asset_id.as_deref(),
+            rpc_src: args.rpc_src.as_deref(),
+            chain_src: args.chain_src.as_deref(),
+            vault,
+        },
+        &config,
+    )
+    .await?;
+    let asset_id = asset.asset_id.clone();
+    let wrapped_token = fetch_wrapped_token(&dest_client, vault, &asset_id, None).await?;
 
-    let token = parse_address(&args.token)?;
-    let vault = parse_address(
-        args.native_token_vault
-            .as_deref()
-            .unwrap_or(DEFAULT_NATIVE_TOKEN_VAULT),
-    )?;
+    let wrapped_token_interface = if wrapped_token != Address::ZERO {
+        Some(probe_token(&dest_client, wrapped_token).await?)
+    } else {
+        None
+    };
+    let source_token_interface = match (&asset.src_client, asset.token) {
+        (Some(src_client), Some(token)) => Some(probe_token(src_client, token).await?),
+        _ => None,
+    };
+    let (symbol, name, decimals) = match &wrapped_token_interface {
+        Some(interface) => (
+            interface.symbol.value.clone(),
+            interface.name.value.clone(),
+            interface
+                .decimals
+                .value
+                .as_deref()
+                .and_then(|value| value.parse().ok()),
+        ),
+        None => (None, None, None),
+    };
 
-    let asset_id = encode_asset_id(U256::from(src_chain_id), token, vault);
-    let asset_id_hex = format_hex(asset_id.as_ref());
-    let wrapped_token = fetch_wrapped_token(&dest_client, vault, &asset_id).await?;
+    let output = TokenInfoOutput {
+        src_chain_id: asset.src_chain_id.map(|id| id.to_string()),
+        dest_chain_id: dest_chain_id.to_string(),
+        token_on_src: asset.token.map(address_to_hex),
+        native_token_vault: address_to_hex(vault),
+        asset_id: format_hex(asset_id.as_ref()),
+        asset_id_source: asset.source().to_string(),
+        wrapped_token_on_dest: address_to_hex(wrapped_token),
+        symbol,
+        name,
+        decimals,
+        wrapped_token_interface,
+        source_token_interface,
+    };
+
+    if emit(&output, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
 
-    let (symbol, name, decimals) = if wrapped_token != Address::ZERO {
-        let symbol = fetch_symbol(&dest_client, wrapped_token).await;
-        let name = fetch_name(&dest_client, wrapped_token).await;
-        let decimals = fetch_decimals(&dest_client, wrapped_token)
+    if let Some(src_chain_id) = output.src_chain_id.as_deref() {
+        println!("source chainId: {src_chain_id}");
+    }
+    println!("destination chainId: {}", output.dest_chain_id);
+    if let Some(token) = output.token_on_src.as_deref() {
+        println!("token (source): {token}");
+    }
+    if let Some(interface) = &output.source_token_interface {
+        print_interface("source ", interface);
+    }
+    println!("native token vault: {}", output.native_token_vault);
+    println!("assetId: {} ({})", output.asset_id, output.asset_id_source);
+    println!("wrapped token (dest): {}", output.wrapped_token_on_dest);
+    if let Some(interface) = &output.wrapped_token_interface {
+        print_interface("", interface);
+    }
+
+    Ok(())
+}
+
+/// Resolve the token the native token vault holds for an asset ID.
+///
+/// On the origin chain this is the native token; elsewhere it is the wrapped
+/// token, or zero if nothing has been bridged there yet.
+#[tracing::instrument(name = "token from-asset-id", skip_all, fields(chain_dest = args.chain_dest.as_deref()))]
+pub async fn run_from_asset_id(
+    args: TokenFromAssetIdArgs,
+    config: Config,
+    addresses: AddressBook,
+) -> Result<()> {
+    let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let chain_id = dest_client.chain_id().await?;
+
+    let asset_id = parse_b256(&args.asset_id)?;
+    let asset_id = Bytes::from(asset_id.to_vec());
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+    let token = fetch_wrapped_token(&dest_client, vault, &asset_id, None).await?;
+
+    let (symbol, name, decimals) = if token != Address::ZERO {
+        let symbol = fetch_symbol(&dest_client, token).await;
+        let name = fetch_name(&dest_client, token).await;
+        let decimals = fetch_decimals(&dest_client, token)
             .await
             .and_then(|value| u8::try_from(value).ok());
         (symbol, name, decimals)
@@ -104,29 +316,31 @@ pub async fn run_info(args: TokenInfoArgs, config: Config, _addresses: AddressBo
         (None, None, None)
     };
 
-    let output = TokenInfoOutput {
-        src_chain_id: src_chain_id.to_string(),
-        dest_chain_id: dest_chain_id.to_string(),
-        token_on_src: address_to_hex(token),
+    let output = TokenFromAssetIdOutput {
+        chain_id: chain_id.to_string(),
         native_token_vault: address_to_hex(vault),
-        asset_id: asset_id_hex,
-        wrapped_token_on_dest: address_to_hex(wrapped_token),
+        asset_id: format_hex(asset_id.as_ref()),
+        token: address_to_hex(token),
         symbol,
         name,
         decimals,
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+    if emit(&output, config.json_output(), args.out.as_deref())? {
         return Ok(());
     }
 
-    println!("source chainId: {}", output.src_chain_id);
-    println!("destination chainId: {}", output.dest_chain_id);
-    println!("token (source): {}", output.token_on_src);
+    println!("chainId: {}", output.chain_id);
     println!("native token vault: {}", output.native_token_vault);
     println!("assetId: {}", output.asset_id);
-    println!("wrapped token (dest): {}", output.wrapped_token_on_dest);
+    if token == Address::ZERO {
+        println!(
+            "token: {} (no token for this asset on this chain yet)",
+            output.token
+        );
+    } else {
+        println!("token: {}", output.token);
+    }
     if let Some(symbol) = output.symbol.as_deref() {
         println!("symbol: {symbol}");
     }
@@ -144,394 +358,1591 @@ pub async fn run_info(args: TokenInfoArgs, config: Config, _addresses: AddressBo
 ///
 /// This command also reports the wrapped token address and decimals when
 /// available.
+#[tracing::instrument(name = "token balance", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref()))]
 pub async fn run_balance(
     args: TokenBalanceArgs,
     config: Config,
-    _addresses: AddressBook,
+    addresses: AddressBook,
 ) -> Result<()> {
-    let src_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
     let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
-    let src_client = RpcClient::new(&src_rpc.url).await?;
-    let dest_client = RpcClient::new(&dest_rpc.url).await?;
-
-    let src_chain_id = src_client.provider.get_chain_id().await?;
-    let dest_chain_id = dest_client.provider.get_chain_id().await?;
-
-    let token = parse_address(&args.token)?;
-    let to = parse_address(&args.to)?;
-    let vault = parse_address(
-        args.native_token_vault
-            .as_deref()
-            .unwrap_or(DEFAULT_NATIVE_TOKEN_VAULT),
-    )?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let dest_chain_id = dest_client.chain_id().await?;
+    let block = args.block.as_deref().map(parse_block_id).transpose()?;
+
+    let to = parse_checked_address(&args.to)?;
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+    // One selection per --token, or a single one for --asset-id.
+    let tokens: Vec<Option<&str>> = if args.token.is_empty() {
+        vec![None]
+    } else {
+        args.token
+            .iter()
+            .map(|token| Some(token.as_str()))
+            .collect()
+    };
 
-    let asset_id = encode_asset_id(U256::from(src_chain_id), token, vault);
-    let asset_id_hex = format_hex(asset_id.as_ref());
-    let wrapped_token = fetch_wrapped_token(&dest_client, vault, &asset_id).await?;
+    let mut outputs = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let asset = resolve_asset(
+            AssetSelection {
+                token,
+                asset_id: args.asset_id.as_deref(),
+                rpc_src: args.rpc_src.as_deref(),
+                chain_src: args.chain_src.as_deref(),
+                vault,
+            },
+            &config,
+        )
+        .await?;
+        let mut output =
+            fetch_balance_output(&dest_client, dest_chain_id, vault, asset, to, block).await?;
+        output.block = args.block.clone();
+        outputs.push(output);
+    }
 
-    let (balance, balance_raw, decimals) = if wrapped_token == Address::ZERO {
-        (None, None, None)
+    let emitted = if let [output] = outputs.as_slice() {
+        emit(output, config.json_output(), args.out.as_deref())?
+    } else {
+        emit(&outputs, config.json_output(), args.out.as_deref())?
+    };
+    if emitted {
+        return Ok(());
+    }
+
+    for (index, output) in outputs.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        print_balance(output);
+    }
+    Ok(())
+}
+
+/// Look up the wrapped token and the recipient's balance of it.
+async fn fetch_balance_output(
+    dest_client: &RpcClient,
+    dest_chain_id: u64,
+    vault: Address,
+    asset: ResolvedAsset,
+    to: Address,
+    block: Option<BlockId>,
+) -> Result<TokenBalanceOutput> {
+    let wrapped_token = fetch_wrapped_token(dest_client, vault, &asset.asset_id, block).await?;
+
+    let (balance, balance_raw, decimals, symbol) = if wrapped_token == Address::ZERO {
+        (None, None, None, None)
     } else {
-        let balance = fetch_balance(&dest_client, wrapped_token, to).await?;
-        let decimals = fetch_decimals(&dest_client, wrapped_token)
+        let balance = fetch_balance(dest_client, wrapped_token, to, block).await?;
+        let decimals = fetch_decimals(dest_client, wrapped_token)
             .await
             .and_then(|value| u8::try_from(value).ok());
+        let symbol = fetch_symbol(dest_client, wrapped_token).await;
         let balance_raw = Some(balance.to_string());
         let formatted = decimals
             .map(|value| format_units(balance, value as u32))
             .unwrap_or_else(|| balance.to_string());
-        (Some(formatted), balance_raw, decimals)
+        (Some(formatted), balance_raw, decimals, symbol)
     };
 
-    let output = TokenBalanceOutput {
-        src_chain_id: src_chain_id.to_string(),
+    Ok(TokenBalanceOutput {
+        src_chain_id: asset.src_chain_id.map(|id| id.to_string()),
         dest_chain_id: dest_chain_id.to_string(),
-        token_on_src: address_to_hex(token),
+        token_on_src: asset.token.map(address_to_hex),
         native_token_vault: address_to_hex(vault),
-        asset_id: asset_id_hex,
+        asset_id: format_hex(asset.asset_id.as_ref()),
+        asset_id_source: asset.source().to_string(),
         wrapped_token_on_dest: address_to_hex(wrapped_token),
         balance,
         balance_raw,
         decimals,
+        symbol,
+        block: None,
+    })
+}
+
+/// Print one balance lookup in text form.
+fn print_balance(output: &TokenBalanceOutput) {
+    if let Some(src_chain_id) = output.src_chain_id.as_deref() {
+        println!("source chainId: {src_chain_id}");
+    }
+    println!("destination chainId: {}", output.dest_chain_id);
+    if let Some(token) = output.token_on_src.as_deref() {
+        println!("token (source): {token}");
+    }
+    println!("native token vault: {}", output.native_token_vault);
+    println!("assetId: {} ({})", output.asset_id, output.asset_id_source);
+    println!("wrapped token (dest): {}", output.wrapped_token_on_dest);
+    if let Some(block) = output.block.as_deref() {
+        println!("block: {block}");
+    }
+    if output.balance_raw.is_none() {
+        println!("Wrapped token not registered on destination yet");
+        return;
+    }
+
+    if let Some(symbol) = output.symbol.as_deref() {
+        println!("symbol: {symbol}");
+    }
+    if let Some(balance) = output.balance.as_deref() {
+        println!("balance: {balance}");
+    }
+    if let Some(balance_raw) = output.balance_raw.as_deref() {
+        println!("balance (raw): {balance_raw}");
+    }
+    if let Some(decimals) = output.decimals {
+        println!("decimals: {decimals}");
+    }
+}
+
+/// Read the allowance an owner has granted a spender (the vault by default).
+#[tracing::instrument(name = "token allowance", skip_all, fields(chain = args.rpc.chain.as_deref()))]
+pub async fn run_allowance(
+    args: TokenAllowanceArgs,
+    config: Config,
+    addresses: AddressBook,
+) -> Result<()> {
+    let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+    let chain_id = client.chain_id().await?;
+
+    let token = parse_checked_address(&args.token)?;
+    let owner = parse_checked_address(&args.owner)?;
+    let spender = args
+        .spender
+        .as_deref()
+        .or(args.native_token_vault.as_deref())
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid spender address")?
+        .unwrap_or(addresses.native_token_vault);
+
+    let allowance = fetch_allowance(&client, token, owner, spender).await?;
+    let decimals = fetch_decimals(&client, token).await;
+
+    let output = TokenAllowanceOutput {
+        chain_id: chain_id.to_string(),
+        token: address_to_hex(token),
+        owner: address_to_hex(owner),
+        spender: address_to_hex(spender),
+        allowance: allowance.to_string(),
+        allowance_formatted: decimals.map(|value| format_allowance(allowance, value)),
+    };
+
+    if emit(&output, config.json_output(), args.out.as_deref())? {
+        return Ok(());
+    }
+
+    println!("chainId: {}", output.chain_id);
+    println!("token: {}", output.token);
+    println!("owner: {}", output.owner);
+    println!("spender: {}", output.spender);
+    if let Some(formatted) = output.allowance_formatted.as_deref() {
+        println!("allowance: {formatted}");
+    }
+    println!("allowance (raw): {}", output.allowance);
+
+    Ok(())
+}
+
+/// Register a token with the native token vault ahead of a send.
+///
+/// The asset ID is read from an eth_call of `ensureTokenIsRegistered`, so it
+/// is printed even under --dry-run.
+#[tracing::instrument(name = "token register", skip_all, fields(chain = args.rpc.chain.as_deref()))]
+pub async fn run_register(
+    args: TokenRegisterArgs,
+    config: Config,
+    addresses: AddressBook,
+) -> Result<()> {
+    let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
+
+    let token = parse_checked_address(&args.token)?;
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+
+    let wallet = load_signer(
+        SignerOptions {
+            private_key: args.signer.private_key.as_deref(),
+            private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
+        },
+        &config,
+    )
+    .await?;
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
+
+    require_signer_or_dry_run(wallet.is_some(), config.dry_run, "token register")?;
+
+    let call = ensureTokenIsRegisteredCall { _token: token };
+    let result = eth_call(&client, vault, Bytes::from(call.abi_encode()))
+        .await
+        .context("ensureTokenIsRegistered reverted")?;
+    let asset_id = crate::abi::decode_bytes32(result)?;
+
+    println!("token: {}", address_to_hex(token));
+    println!("native token vault: {}", address_to_hex(vault));
+    println!("assetId: {asset_id:#x}");
+
+    if config.dry_run {
+        println!("registerTx: dry-run (eth_call)");
+        return Ok(());
+    }
+    let mut sender = SourceSender {
+        client: &client,
+        rpc: &resolved,
+        wallet: wallet.as_ref(),
+        overrides,
+        dry_run: false,
+        calldata_only: false,
+        human: true,
+        hints: !config.quiet,
+        raw_calls: Vec::new(),
+    };
+    sender.register(vault, token).await?;
+    Ok(())
+}
+
+/// Send an ERC20 across chains via the interop asset router.
+///
+/// The flow registers the token, approves allowance, sends the bundle, and can
+/// optionally watch for proof/root propagation. With --native the base token is
+/// sent instead, carried as the interop value of a plain call to the recipient.
+#[tracing::instrument(name = "token send", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref()))]
+pub async fn run_send(args: TokenSendArgs, config: Config, addresses: AddressBook) -> Result<()> {
+    let src_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
+    let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
+
+    let source_client =
+        RpcClient::for_rpc(&src_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+
+    let src_chain_id = source_client.chain_id().await?;
+    let dest_chain_id = dest_client.chain_id().await?;
+    check_distinct_chains(
+        src_chain_id,
+        U256::from(dest_chain_id),
+        args.allow_same_chain,
+    )?;
+
+    let token = args
+        .token
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()?;
+    let to = parse_checked_address(&args.to)?;
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+    let asset_router = args
+        .asset_router
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid asset router address")?
+        .unwrap_or(addresses.asset_router);
+    let unbundler = parse_checked_address(args.unbundler.as_deref().unwrap_or(&args.to))?;
+
+    // --calldata-only never signs, so don't open a Ledger or read a key.
+    let wallet = if args.calldata_only {
+        None
+    } else {
+        load_signer(
+            SignerOptions {
+                private_key: args.signer.private_key.as_deref(),
+                private_key_env: args.signer.private_key_env.as_deref(),
+                ledger: args.signer.ledger,
+                ledger_account: args.signer.ledger_account,
+            },
+            &config,
+        )
+        .await?
+    };
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
+
+    require_signer_or_dry_run(
+        wallet.is_some(),
+        config.dry_run || args.calldata_only,
+        "token send",
+    )?;
+
+    let asset_id = match token {
+        Some(token) => encode_asset_id(U256::from(src_chain_id), token, vault),
+        None => fetch_base_token_asset_id(&source_client, src_chain_id).await?,
+    };
+    let asset_id_hex = format_hex(asset_id.as_ref());
+    let state = args
+        .state_file
+        .as_deref()
+        .map(load_send_state)
+        .transpose()?
+        .flatten();
+
+    let decimals = match (args.decimals, token) {
+        (Some(value), _) => Some(value),
+        (None, Some(token)) => fetch_decimals(&source_client, token).await,
+        (None, None) => Some(18),
+    };
+
+    // A resumed --amount-all send keeps the amount it started with; the
+    // balance has usually moved by then.
+    let amount_wei = match (&state, args.amount_all, token) {
+        (Some(state), true, _) => U256::from_str(&state.amount)
+            .with_context(|| format!("invalid amount {} in state file", state.amount))?,
+        (None, true, Some(token)) => {
+            let wallet = wallet
+                .as_ref()
+                .ok_or_else(|| anyhow!("--amount-all sends the signer's balance; set a signer"))?;
+            let balance =
+                fetch_balance(&source_client, token, signer_address(wallet)?, None).await?;
+            if balance.is_zero() {
+                anyhow::bail!("--amount-all: signer has no balance of {token:#x}");
+            }
+            balance
+        }
+        _ => resolve_amount_wei(&args, decimals).await?,
+    };
+
+    let human = !config.json_output();
+    // The preflight summary and debug hints are chatter; tx hashes and
+    // balances below are the result.
+    let chatter = human && !config.quiet;
+    progress(chatter, format_args!("=== token send preflight ==="));
+    progress(
+        chatter,
+        format_args!(
+            "source: {} (chainId {})",
+            format_rpc(&src_rpc),
+            src_chain_id
+        ),
+    );
+    progress(
+        chatter,
+        format_args!(
+            "destination: {} (chainId {})",
+            format_rpc(&dest_rpc),
+            dest_chain_id
+        ),
+    );
+    match token {
+        Some(token) => progress(
+            chatter,
+            format_args!("token (source): {}", address_to_hex(token)),
+        ),
+        None => progress(chatter, format_args!("token (source): native token")),
+    }
+    progress(
+        chatter,
+        format_args!("recipient (dest): {}", address_to_hex(to)),
+    );
+    // Under --calldata-only another account sends, so the local signer is
+    // not the one that matters.
+    let signer = match wallet.as_ref() {
+        Some(wallet) if !args.calldata_only => Some(signer_address(wallet)?),
+        _ => None,
+    };
+    if let Some(signer) = signer {
+        progress(chatter, format_args!("signer: {}", address_to_hex(signer)));
+    }
+    // Fail before register/approve spend gas on a send that cannot go
+    // through. A resumed send has already moved the tokens.
+    let resumed = state
+        .as_ref()
+        .is_some_and(|state| state.source_tx_hash.is_some());
+    if let (Some(token), Some(signer)) = (token, signer) {
+        if !config.dry_run && !args.skip_balance_check && !args.amount_all && !resumed {
+            let balance = fetch_balance(&source_client, token, signer, None).await?;
+            if balance < amount_wei {
+                let format = |value: U256| match decimals {
+                    Some(decimals) => format_units(value, decimals),
+                    None => value.to_string(),
+                };
+                anyhow::bail!(
+                    "insufficient balance: have {} need {} (--skip-balance-check to send anyway)",
+                    format(balance),
+                    format(amount_wei)
+                );
+            }
+        }
+    }
+    progress(chatter, format_args!("assetId: {asset_id_hex}"));
+    progress(
+        chatter,
+        format_args!("asset router: {}", address_to_hex(asset_router)),
+    );
+    progress(
+        chatter,
+        format_args!("native token vault: {}", address_to_hex(vault)),
+    );
+    progress(
+        chatter,
+        format_args!(
+            "interop center: {}",
+            address_to_hex(addresses.interop_center)
+        ),
+    );
+    progress(
+        chatter,
+        format_args!(
+            "interop handler: {}",
+            address_to_hex(addresses.interop_handler)
+        ),
+    );
+    progress(
+        chatter,
+        format_args!(
+            "interop root storage: {}",
+            address_to_hex(addresses.interop_root_storage)
+        ),
+    );
+    if args.amount_all {
+        progress(chatter, format_args!("amount: all (signer balance)"));
+    }
+    progress(chatter, format_args!("amount (wei): {amount_wei}"));
+    if let Some(decimals) = decimals {
+        progress(
+            chatter,
+            format_args!("amount (formatted): {}", format_units(amount_wei, decimals)),
+        );
+    }
+    // Under --calldata-only another account sends, so the local signer's
+    // allowance says nothing.
+    let allowance = match (token.filter(|_| !args.skip_approve), wallet.as_ref()) {
+        (Some(token), Some(wallet)) if !args.calldata_only => {
+            let owner = signer_address(wallet)?;
+            let allowance = fetch_allowance(&source_client, token, owner, vault).await?;
+            progress(chatter, format_args!("current allowance: {allowance}"));
+            Some(allowance)
+        }
+        _ => None,
+    };
+    if args.watch {
+        progress(chatter, format_args!("watch: enabled"));
+    }
+
+    let bundle_attributes = vec![encode_unbundler_address(encode_evm_v1_address_only(
+        unbundler,
+    ))];
+    if let Some(signer) = signer {
+        check_signer_permissions(
+            &bundle_attributes,
+            signer,
+            dest_chain_id,
+            &args.mode,
+            chatter,
+        )?;
+    }
+
+    let dest_chain_id_u256 = U256::from(dest_chain_id);
+
+    let mut sender = SourceSender {
+        client: &source_client,
+        rpc: &src_rpc,
+        wallet: wallet.as_ref(),
+        overrides,
+        dry_run: config.dry_run,
+        calldata_only: args.calldata_only,
+        human,
+        hints: chatter,
+        raw_calls: Vec::new(),
+    };
+    let fresh = TokenSendResult {
+        source_chain_id: src_chain_id.to_string(),
+        destination_chain_id: dest_chain_id.to_string(),
+        asset_id: asset_id_hex.clone(),
+        token: token.map(address_to_hex),
+        recipient: address_to_hex(to),
+        amount: amount_wei.to_string(),
+        mode: args.mode.clone(),
+        dry_run: config.dry_run,
+        register_tx_hash: None,
+        approve_tx_hash: None,
+        source_tx_hash: None,
+        bundle_hash: String::new(),
+        l1_batch_number: None,
+        l2_message_index: None,
+        handler_tx_hash: None,
+        bundle_status: None,
+        wrapped_token: None,
+        balance_before: None,
+        balance_after: None,
+        expected_delta: None,
+    };
+    let mut result = match state {
+        Some(state) => {
+            check_send_state(&state, &fresh)?;
+            TokenSendResult {
+                mode: fresh.mode,
+                ..state
+            }
+        }
+        None => fresh,
+    };
+    if let Some(handler_tx_hash) = &result.handler_tx_hash {
+        progress(
+            human,
+            format_args!("transfer already complete (from state file)"),
+        );
+        if let Some(send_tx_hash) = &result.source_tx_hash {
+            progress(human, format_args!("sendBundleTx: {send_tx_hash}"));
+        }
+        progress(human, format_args!("bundleHash: {}", result.bundle_hash));
+        progress(human, format_args!("handlerTx: {handler_tx_hash}"));
+        emit(&result, !human, args.out.as_deref())?;
+        return Ok(());
+    }
+    let save_state = |result: &TokenSendResult| -> Result<()> {
+        match args.state_file.as_deref() {
+            Some(path) => write_atomic(path, &serde_json::to_string_pretty(result)?),
+            None => Ok(()),
+        }
+    };
+
+    let (call_starter, total_value) = if token.is_some() {
+        let indirect_msg_value = parse_value_with_units(&args.indirect_msg_value)?;
+        let mut call_attributes = vec![encode_indirect_call(indirect_msg_value)];
+        let mut total_value = indirect_msg_value;
+        if let Some(interop_value) = args.interop_value.as_deref() {
+            let parsed = parse_value_with_units(interop_value)?;
+            total_value += parsed;
+            call_attributes.push(encode_interop_call_value(parsed));
+        }
+        let call_data = build_second_bridge_calldata(&asset_id, amount_wei, to, Address::ZERO)?;
+        let call_starter = crate::abi::InteropCallStarter {
+            to: encode_evm_v1_address_only(asset_router),
+            data: call_data,
+            callAttributes: call_attributes,
+        };
+        (call_starter, total_value)
+    } else {
+        // The base token needs no asset router hop: the interop center burns
+        // the call value here and the handler mints it to the recipient.
+        let call_starter = crate::abi::InteropCallStarter {
+            to: encode_evm_v1_address_only(to),
+            data: Bytes::new(),
+            callAttributes: vec![encode_interop_call_value(amount_wei)],
+        };
+        (call_starter, amount_wei)
+    };
+
+    if !config.dry_run && !args.calldata_only && result.source_tx_hash.is_none() {
+        let amount = match (decimals, token) {
+            (Some(decimals), Some(token)) => format!(
+                "{} of {}",
+                format_units(amount_wei, decimals),
+                address_to_hex(token)
+            ),
+            (_, Some(token)) => format!("{amount_wei} (raw) of {}", address_to_hex(token)),
+            (_, None) => format!("{} (native)", format_units(amount_wei, 18)),
+        };
+        confirm_broadcast(
+            "send tokens",
+            &[
+                ("destination chain", dest_chain_id.to_string()),
+                ("recipient", address_to_hex(to)),
+                ("amount", amount),
+                (
+                    "total value",
+                    format!("{} ({total_value} wei)", format_units(total_value, 18)),
+                ),
+            ],
+            config.yes,
+        )?;
+    }
+
+    // Once the bundle is sent, register and approve have served their purpose.
+    let sent = result.source_tx_hash.is_some();
+    if let Some(token) = token.filter(|_| !args.skip_register && !sent) {
+        match &result.register_tx_hash {
+            Some(tx_hash) => progress(
+                human,
+                format_args!("registerTx: {tx_hash} (from state file)"),
+            ),
+            None => {
+                result.register_tx_hash = sender.register(vault, token).await?;
+                save_state(&result)?;
+            }
+        }
+    }
+    if let Some(token) = token.filter(|_| !args.skip_approve && !sent) {
+        if let Some(tx_hash) = &result.approve_tx_hash {
+            progress(
+                human,
+                format_args!("approveTx: {tx_hash} (from state file)"),
+            );
+        } else if allowance.is_some_and(|allowance| allowance >= amount_wei) {
+            progress(
+                human,
+                format_args!("approveTx: skipped (allowance covers amount)"),
+            );
+        } else {
+            let approve_amount = resolve_approve_amount(&args, amount_wei)?;
+            result.approve_tx_hash = sender.approve(token, vault, approve_amount).await?;
+            save_state(&result)?;
+        }
+    }
+
+    let destination_chain = encode_evm_v1_chain_only(dest_chain_id_u256);
+    let calldata =
+        encode_send_bundle_call(destination_chain, vec![call_starter], bundle_attributes)?;
+
+    if args.calldata_only {
+        let call = RawCall::new(
+            Some("sendBundle"),
+            addresses.interop_center,
+            total_value,
+            &calldata,
+        );
+        if human {
+            print_raw_call(&call);
+        } else {
+            sender.raw_calls.push(call);
+            println!("{}", serde_json::to_string_pretty(&sender.raw_calls)?);
+        }
+        return Ok(());
+    }
+    if config.dry_run {
+        let returned = eth_call_with_value(
+            &source_client,
+            addresses.interop_center,
+            calldata.clone(),
+            Some(total_value),
+            None,
+        )
+        .await?;
+        let bundle_hash = crate::abi::decode_bytes32(returned)?;
+        progress(human, format_args!("sendBundleTx: dry-run (eth_call)"));
+        let result = TokenSendResult {
+            bundle_hash: format!("{bundle_hash:#x}"),
+            ..result
+        };
+        if emit(&result, !human, args.out.as_deref())? {
+            return Ok(());
+        }
+        println!("bundleHash: {bundle_hash:#x}");
+        if chatter {
+            print_next_steps(&src_rpc, &dest_rpc, src_chain_id, "<txHash>");
+        }
+        return Ok(());
+    }
+
+    let send_tx_hash = match &result.source_tx_hash {
+        Some(tx_hash) => {
+            progress(
+                human,
+                format_args!("sendBundleTx: {tx_hash} (from state file)"),
+            );
+            tx_hash.clone()
+        }
+        None => {
+            let tx_hash = send_tx(
+                &source_client,
+                wallet.as_ref(),
+                &mut sender.overrides,
+                addresses.interop_center,
+                calldata,
+                Some(total_value),
+            )
+            .await?;
+            progress(human, format_args!("sendBundleTx: {tx_hash}"));
+            if chatter {
+                print_tx_debug("sendBundle", &src_rpc, &tx_hash);
+            }
+            result.source_tx_hash = Some(tx_hash.clone());
+            save_state(&result)?;
+            tx_hash
+        }
+    };
+
+    let send_tx_hash = B256::from_str(&send_tx_hash).context("invalid sendBundle tx hash")?;
+    let receipt = get_transaction_receipt(&source_client, send_tx_hash).await?;
+    if result.bundle_hash.is_empty() {
+        let (bundle_hash, _) = find_sent_bundle(&receipt)?;
+        result.bundle_hash = format!("{bundle_hash:#x}");
+        save_state(&result)?;
+    }
+    // A pinned nonce only applies to the source chain transactions.
+    let dest_overrides = TxOverrides {
+        nonce: None,
+        ..sender.overrides
+    };
+    let step = DestinationStep {
+        source_client: &source_client,
+        dest_client: &dest_client,
+        src_rpc: &src_rpc,
+        dest_rpc: &dest_rpc,
+        wallet: wallet.as_ref(),
+        overrides: &dest_overrides,
+        addresses: &addresses,
+        vault,
+        asset_id: &asset_id,
+        native: token.is_none(),
+        recipient: to,
+        amount: amount_wei,
+        mode: &args.mode,
+        bundle_identifier: config.bundle_identifier,
+        timeout: config.wait_timeout(args.timeout_ms),
+        poll: config.poll_interval(args.poll_ms),
+        confirmations: config.confirmations(args.confirmations),
+        proof_cache: args
+            .proof_cache
+            .clone()
+            .or_else(|| args.state_file.as_deref().map(state_proof_cache)),
+        human,
+        hints: chatter,
+    };
+    let outcome = complete_on_destination(step, send_tx_hash, receipt).await?;
+    let result = TokenSendResult {
+        source_tx_hash: Some(format!("{send_tx_hash:#x}")),
+        bundle_hash: format!("{:#x}", outcome.bundle_hash),
+        l1_batch_number: Some(outcome.l1_batch_number),
+        l2_message_index: Some(outcome.l2_message_index),
+        handler_tx_hash: Some(outcome.handler_tx_hash),
+        bundle_status: outcome
+            .bundle_status
+            .map(|status| status_string(status).to_string()),
+        wrapped_token: outcome.wrapped_token.map(address_to_hex),
+        balance_before: outcome.balance_before.map(|amount| amount.to_string()),
+        balance_after: outcome.balance_after.map(|amount| amount.to_string()),
+        expected_delta: outcome.balance_after.map(|_| amount_wei.to_string()),
+        ..result
+    };
+    save_state(&result)?;
+    emit(&result, !human, args.out.as_deref())?;
+    Ok(())
+}
+
+/// Check the bundle's permission attributes against the signer that runs the
+/// destination step, the way `bundle explain` does, before anything is sent.
+///
+/// Only an `executionAddress` that excludes the signer aborts, and only for
+/// `--mode execute`; the unbundler (the recipient by default) is needed just
+/// to unbundle later, so it is reported but not enforced.
+fn check_signer_permissions(
+    attributes: &[Bytes],
+    signer: Address,
+    dest_chain_id: u64,
+    mode: &str,
+    human: bool,
+) -> Result<()> {
+    let mut execution_address = Bytes::new();
+    let mut unbundler = Bytes::new();
+    for attribute in attributes {
+        match decode_attribute(attribute)? {
+            DecodedAttr::ExecutionAddress(value) => execution_address = value,
+            DecodedAttr::UnbundlerAddress(value) => unbundler = value,
+            _ => {}
+        }
+    }
+    let execution = check_permissions(
+        &execution_address,
+        signer,
+        dest_chain_id,
+        "executionAddress",
+    );
+    let unbundler = check_permissions(&unbundler, signer, dest_chain_id, "unbundlerAddress");
+    progress(human, format_args!("permissions: {}", execution.details));
+    if unbundler.status == "fail" {
+        progress(
+            human,
+            format_args!(
+                "permissions: {} (only needed to unbundle)",
+                unbundler.details
+            ),
+        );
+    } else {
+        progress(human, format_args!("permissions: {}", unbundler.details));
+    }
+    if mode == "execute" && execution.status == "fail" {
+        anyhow::bail!(
+            "{}; the destination execute would revert, so nothing was sent",
+            execution.details
+        );
+    }
+    Ok(())
+}
+
+/// Read a `--state-file`; a missing file means a fresh send.
+fn load_send_state(path: &Path) -> Result<Option<TokenSendResult>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let state = serde_json::from_str(&raw)
+        .with_context(|| format!("invalid token send state file {}", path.display()))?;
+    Ok(Some(state))
+}
+
+/// Refuse to resume a state file recorded for a different transfer.
+fn check_send_state(state: &TokenSendResult, current: &TokenSendResult) -> Result<()> {
+    let fields = [
+        (
+            "source chain",
+            &state.source_chain_id,
+            &current.source_chain_id,
+        ),
+        (
+            "destination chain",
+            &state.destination_chain_id,
+            &current.destination_chain_id,
+        ),
+        ("assetId", &state.asset_id, &current.asset_id),
+        ("recipient", &state.recipient, &current.recipient),
+        ("amount", &state.amount, &current.amount),
+    ];
+    for (name, recorded, now) in fields {
+        if !recorded.eq_ignore_ascii_case(now) {
+            anyhow::bail!(
+                "state file records a different transfer ({name} {recorded}, now {now}); remove it or pass another --state-file"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Proof cache used with `--state-file` when `--proof-cache` is unset: the
+/// state file's directory.
+fn state_proof_cache(state_file: &Path) -> PathBuf {
+    state_file
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf()
+}
+
+/// Resume a token send whose source bundle was already sent.
+///
+/// Picks up the bundle from the source transaction, waits for proof/root, and
+/// runs only the destination handler step plus the wrapped balance check.
+#[tracing::instrument(name = "token send-continue", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref(), tx = %args.source_tx))]
+pub async fn run_send_continue(
+    args: TokenSendContinueArgs,
+    config: Config,
+    addresses: AddressBook,
+) -> Result<()> {
+    if config.dry_run {
+        anyhow::bail!(
+            "token send-continue does not support --dry-run; simulate the handler step with bundle relay --dry-run"
+        );
+    }
+    let src_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
+    let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
+
+    let source_client =
+        RpcClient::for_rpc(&src_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+
+    let src_chain_id = source_client.chain_id().await?;
+    let dest_chain_id = dest_client.chain_id().await?;
+
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+
+    let wallet = load_signer(
+        SignerOptions {
+            private_key: args.signer.private_key.as_deref(),
+            private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
+        },
+        &config,
+    )
+    .await?;
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    require_signer_or_dry_run(wallet.is_some(), false, "token send-continue")?;
+
+    let send_tx_hash = B256::from_str(&args.source_tx)
+        .with_context(|| format!("invalid tx hash {}", args.source_tx))?;
+    let receipt = get_transaction_receipt(&source_client, send_tx_hash).await?;
+    let (bundle_hash, bundle) = find_sent_bundle(&receipt)?;
+    let transfer = bundle
+        .calls
+        .iter()
+        .find_map(|call| decode_second_bridge_calldata(&call.data).ok());
+    let native = transfer.is_none();
+    let (asset_id, amount, recipient) = match transfer {
+        Some(transfer) => transfer,
+        None => {
+            let call = bundle
+                .calls
+                .iter()
+                .find(|call| call.data.is_empty() && call.value > U256::ZERO)
+                .ok_or_else(|| {
+                    anyhow!("bundle does not contain an asset router or native token transfer")
+                })?;
+            let asset_id = fetch_base_token_asset_id(&source_client, src_chain_id).await?;
+            (B256::from_slice(asset_id.as_ref()), call.value, call.to)
+        }
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
-        return Ok(());
-    }
+    let chatter = !config.quiet;
+    progress(chatter, format_args!("=== token send-continue ==="));
+    progress(
+        chatter,
+        format_args!(
+            "source: {} (chainId {})",
+            format_rpc(&src_rpc),
+            src_chain_id
+        ),
+    );
+    progress(
+        chatter,
+        format_args!(
+            "destination: {} (chainId {})",
+            format_rpc(&dest_rpc),
+            dest_chain_id
+        ),
+    );
+    progress(chatter, format_args!("source tx: {send_tx_hash:#x}"));
+    progress(
+        chatter,
+        format_args!("assetId: {}", format_hex(asset_id.as_ref())),
+    );
+    progress(
+        chatter,
+        format_args!("recipient (dest): {}", address_to_hex(recipient)),
+    );
+    progress(chatter, format_args!("amount (wei): {amount}"));
 
-    println!("source chainId: {}", output.src_chain_id);
-    println!("destination chainId: {}", output.dest_chain_id);
-    println!("token (source): {}", output.token_on_src);
-    println!("native token vault: {}", output.native_token_vault);
-    println!("assetId: {}", output.asset_id);
-    println!("wrapped token (dest): {}", output.wrapped_token_on_dest);
-    if wrapped_token == Address::ZERO {
-        println!("Wrapped token not registered on destination yet");
+    let status = fetch_bundle_status(&dest_client, addresses.interop_handler, bundle_hash).await?;
+    let already_done = match args.mode.as_str() {
+        "verify" => status != 0,
+        _ => status >= 2,
+    };
+    if already_done {
+        println!("bundleHash: {bundle_hash:#x}");
+        println!(
+            "bundle status: {} (nothing to continue)",
+            status_string(status)
+        );
         return Ok(());
     }
 
-    if let Some(balance) = output.balance.as_deref() {
-        println!("balance: {balance}");
-    }
-    if let Some(balance_raw) = output.balance_raw.as_deref() {
-        println!("balance (raw): {balance_raw}");
-    }
-    if let Some(decimals) = output.decimals {
-        println!("decimals: {decimals}");
-    }
-
-    Ok(())
+    let asset_id = Bytes::from(asset_id.to_vec());
+    let step = DestinationStep {
+        source_client: &source_client,
+        dest_client: &dest_client,
+        src_rpc: &src_rpc,
+        dest_rpc: &dest_rpc,
+        wallet: wallet.as_ref(),
+        overrides: &overrides,
+        addresses: &addresses,
+        vault,
+        asset_id: &asset_id,
+        native,
+        recipient,
+        amount,
+        mode: &args.mode,
+        bundle_identifier: config.bundle_identifier,
+        timeout: config.wait_timeout(args.timeout_ms),
+        poll: config.poll_interval(args.poll_ms),
+        confirmations: config.confirmations(args.confirmations),
+        proof_cache: args.proof_cache.clone(),
+        human: true,
+        hints: chatter,
+    };
+    complete_on_destination(step, send_tx_hash, receipt)
+        .await
+        .map(|_| ())
 }
 
-/// Send an ERC20 across chains via the interop asset router.
+/// Send a token to every recipient in a file within a single bundle.
 ///
-/// The flow registers the token, approves allowance, sends the bundle, and can
-/// optionally watch for proof/root propagation.
-pub async fn run_send(args: TokenSendArgs, config: Config, addresses: AddressBook) -> Result<()> {
+/// Each recipient gets its own asset router call; the batch shares one
+/// register/approve step for the summed amount and one sendBundle tx.
+#[tracing::instrument(name = "token send-batch", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref()))]
+pub async fn run_send_batch(
+    args: TokenSendBatchArgs,
+    config: Config,
+    addresses: AddressBook,
+) -> Result<()> {
     let src_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
     let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
 
-    let source_client = RpcClient::new(&src_rpc.url).await?;
-    let dest_client = RpcClient::new(&dest_rpc.url).await?;
-
-    let src_chain_id = source_client.provider.get_chain_id().await?;
-    let dest_chain_id = dest_client.provider.get_chain_id().await?;
+    let source_client =
+        RpcClient::for_rpc(&src_rpc, config.rpc_timeout, &config.rpc_headers).await?;
+    let dest_client =
+        RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?;
 
-    let token = parse_address(&args.token)?;
-    let to = parse_address(&args.to)?;
-    let vault = parse_address(
-        args.native_token_vault
-            .as_deref()
-            .unwrap_or(DEFAULT_NATIVE_TOKEN_VAULT),
+    let src_chain_id = source_client.chain_id().await?;
+    let dest_chain_id = dest_client.chain_id().await?;
+    check_distinct_chains(
+        src_chain_id,
+        U256::from(dest_chain_id),
+        args.allow_same_chain,
     )?;
-    let asset_router = parse_address(args.asset_router.as_deref().unwrap_or(DEFAULT_ASSET_ROUTER))?;
-    let unbundler = parse_address(args.unbundler.as_deref().unwrap_or(&args.to))?;
+
+    let token = parse_checked_address(&args.token)?;
+    let vault = args
+        .native_token_vault
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid native token vault address")?
+        .unwrap_or(addresses.native_token_vault);
+    let asset_router = args
+        .asset_router
+        .as_deref()
+        .map(parse_checked_address)
+        .transpose()
+        .context("invalid asset router address")?
+        .unwrap_or(addresses.asset_router);
 
     let wallet = load_signer(
         SignerOptions {
             private_key: args.signer.private_key.as_deref(),
             private_key_env: args.signer.private_key_env.as_deref(),
+            ledger: args.signer.ledger,
+            ledger_account: args.signer.ledger_account,
         },
         &config,
-    )?;
+    )
+    .await?;
+    let overrides = TxOverrides::parse(&args.overrides)?;
+    overrides.warn_if_dry_run(config.dry_run);
 
-    require_signer_or_dry_run(wallet.is_some(), args.dry_run, "token send")?;
+    require_signer_or_dry_run(wallet.is_some(), config.dry_run, "token send-batch")?;
 
-    let asset_id = encode_asset_id(U256::from(src_chain_id), token, vault);
-    let asset_id_hex = format_hex(asset_id.as_ref());
+    let unbundler = match (args.unbundler.as_deref(), wallet.as_ref()) {
+        (Some(value), _) => parse_checked_address(value)?,
+        (None, Some(wallet)) => signer_address(wallet)?,
+        (None, None) => anyhow::bail!("set --unbundler when running without a signer"),
+    };
 
     let decimals = match args.decimals {
         Some(value) => Some(value),
         None => fetch_decimals(&source_client, token).await,
     };
+    let recipients = load_recipients(&args.recipients, decimals)?;
+    let total_amount = recipients
+        .iter()
+        .try_fold(U256::ZERO, |acc, (_, amount)| acc.checked_add(*amount))
+        .ok_or_else(|| anyhow!("total batch amount overflows uint256"))?;
 
-    let amount_wei = resolve_amount_wei(&args, decimals).await?;
+    let asset_id = encode_asset_id(U256::from(src_chain_id), token, vault);
 
-    println!("=== token send preflight ===");
-    println!(
-        "source: {} (chainId {})",
-        format_rpc(&src_rpc),
-        src_chain_id
+    let human = !config.json_output();
+    let chatter = human && !config.quiet;
+    progress(chatter, format_args!("=== token send-batch preflight ==="));
+    progress(
+        chatter,
+        format_args!(
+            "source: {} (chainId {})",
+            format_rpc(&src_rpc),
+            src_chain_id
+        ),
     );
-    println!(
-        "destination: {} (chainId {})",
-        format_rpc(&dest_rpc),
-        dest_chain_id
-    );
-    println!("token (source): {}", address_to_hex(token));
-    println!("recipient (dest): {}", address_to_hex(to));
-    println!("assetId: {asset_id_hex}");
-    println!("asset router: {}", address_to_hex(asset_router));
-    println!("native token vault: {}", address_to_hex(vault));
-    println!(
-        "interop center: {}",
-        address_to_hex(addresses.interop_center)
+    progress(
+        chatter,
+        format_args!(
+            "destination: {} (chainId {})",
+            format_rpc(&dest_rpc),
+            dest_chain_id
+        ),
     );
-    println!(
-        "interop handler: {}",
-        address_to_hex(addresses.interop_handler)
+    progress(
+        chatter,
+        format_args!("token (source): {}", address_to_hex(token)),
     );
-    println!(
-        "interop root storage: {}",
-        address_to_hex(addresses.interop_root_storage)
+    progress(
+        chatter,
+        format_args!("assetId: {}", format_hex(asset_id.as_ref())),
     );
-    println!("amount (wei): {amount_wei}");
+    progress(
+        chatter,
+        format_args!("asset router: {}", address_to_hex(asset_router)),
+    );
+    progress(
+        chatter,
+        format_args!("native token vault: {}", address_to_hex(vault)),
+    );
+    progress(
+        chatter,
+        format_args!("unbundler (dest): {}", address_to_hex(unbundler)),
+    );
+    progress(chatter, format_args!("recipients: {}", recipients.len()));
+    progress(chatter, format_args!("total amount (wei): {total_amount}"));
     if let Some(decimals) = decimals {
-        println!("amount (formatted): {}", format_units(amount_wei, decimals));
-    }
-    if args.watch {
-        println!("watch: enabled");
+        progress(
+            chatter,
+            format_args!(
+                "total amount (formatted): {}",
+                format_units(total_amount, decimals)
+            ),
+        );
     }
-
-    let dest_chain_id_u256 = U256::from(dest_chain_id);
-
-    if !args.skip_register {
-        let call = ensureTokenIsRegisteredCall { _token: token };
-        let data = Bytes::from(call.abi_encode());
-        if args.dry_run {
-            let _ = eth_call(&source_client, vault, data).await;
-            println!("registerTx: dry-run (eth_call)");
-        } else {
-            let tx_hash =
-                send_tx(&source_client, &src_rpc, wallet.as_ref(), vault, data, None).await?;
-            println!("registerTx: {tx_hash}");
-            print_tx_debug("register", &src_rpc, &tx_hash);
+    let allowance = match wallet.as_ref().filter(|_| !args.skip_approve) {
+        Some(wallet) => {
+            let owner = signer_address(wallet)?;
+            let allowance = fetch_allowance(&source_client, token, owner, vault).await?;
+            progress(chatter, format_args!("current allowance: {allowance}"));
+            Some(allowance)
         }
-    }
+        None => None,
+    };
 
-    if !args.skip_approve {
-        let approve_amount = resolve_approve_amount(&args, amount_wei)?;
-        let call = approveCall {
-            spender: vault,
-            value: approve_amount,
-        };
-        let data = Bytes::from(call.abi_encode());
-        if args.dry_run {
-            let _ = eth_call(&source_client, token, data).await;
-            println!("approveTx: dry-run (eth_call)");
-        } else {
-            let tx_hash =
-                send_tx(&source_client, &src_rpc, wallet.as_ref(), token, data, None).await?;
-            println!("approveTx: {tx_hash}");
-            print_tx_debug("approve", &src_rpc, &tx_hash);
-        }
+    let indirect_msg_value = parse_value_with_units(&args.indirect_msg_value)?;
+    let mut total_value = U256::ZERO;
+    let mut call_starters = Vec::with_capacity(recipients.len());
+    for (to, amount) in &recipients {
+        let call_data = build_second_bridge_calldata(&asset_id, *amount, *to, Address::ZERO)?;
+        call_starters.push(crate::abi::InteropCallStarter {
+            to: encode_evm_v1_address_only(asset_router),
+            data: call_data,
+            callAttributes: vec![encode_indirect_call(indirect_msg_value)],
+        });
+        total_value += indirect_msg_value;
     }
-
-    let indirect_msg_value = parse_u256(&args.indirect_msg_value)?;
-    let mut call_attributes = vec![encode_indirect_call(indirect_msg_value)];
-    let mut total_value = indirect_msg_value;
-    if let Some(interop_value) = args.interop_value.as_deref() {
-        let parsed = parse_u256(interop_value)?;
-        total_value += parsed;
-        call_attributes.push(encode_interop_call_value(parsed));
+    progress(chatter, format_args!("msg.value (wei): {total_value}"));
+    if !config.dry_run {
+        let amount = match decimals {
+            Some(decimals) => format_units(total_amount, decimals),
+            None => format!("{total_amount} (raw)"),
+        };
+        confirm_broadcast(
+            "send tokens",
+            &[
+                ("destination chain", dest_chain_id.to_string()),
+                ("recipients", recipients.len().to_string()),
+                (
+                    "total amount",
+                    format!("{amount} of {}", address_to_hex(token)),
+                ),
+                (
+                    "total value",
+                    format!("{} ({total_value} wei)", format_units(total_value, 18)),
+                ),
+            ],
+            config.yes,
+        )?;
     }
 
-    let call_data = build_second_bridge_calldata(&asset_id, amount_wei, to, Address::ZERO)?;
-    let call_starter = crate::abi::InteropCallStarter {
-        to: encode_evm_v1_address_only(asset_router),
-        data: call_data,
-        callAttributes: call_attributes,
+    let mut sender = SourceSender {
+        client: &source_client,
+        rpc: &src_rpc,
+        wallet: wallet.as_ref(),
+        overrides,
+        dry_run: config.dry_run,
+        calldata_only: false,
+        human,
+        hints: chatter,
+        raw_calls: Vec::new(),
+    };
+    let mut result = TokenSendBatchResult {
+        source_chain_id: src_chain_id.to_string(),
+        destination_chain_id: dest_chain_id.to_string(),
+        asset_id: format_hex(asset_id.as_ref()),
+        token: address_to_hex(token),
+        recipients: recipients.len(),
+        total_amount: total_amount.to_string(),
+        total_value: total_value.to_string(),
+        dry_run: config.dry_run,
+        register_tx_hash: None,
+        approve_tx_hash: None,
+        source_tx_hash: None,
+        bundle_hash: None,
+        calldata: None,
+        dry_run_error: None,
     };
+    if !args.skip_register {
+        result.register_tx_hash = sender.register(vault, token).await?;
+    }
+    if allowance.is_some_and(|allowance| allowance >= total_amount) {
+        progress(
+            chatter,
+            format_args!("approveTx: skipped (allowance covers amount)"),
+        );
+    } else if !args.skip_approve {
+        result.approve_tx_hash = sender.approve(token, vault, total_amount).await?;
+    }
 
     let bundle_attributes = vec![encode_unbundler_address(encode_evm_v1_address_only(
         unbundler,
     ))];
-
-    let destination_chain = encode_evm_v1_chain_only(dest_chain_id_u256);
-    let calldata =
-        encode_send_bundle_call(destination_chain, vec![call_starter], bundle_attributes)?;
-
-    if args.dry_run {
-        let result = eth_call_with_value(
+    let destination_chain = encode_evm_v1_chain_only(U256::from(dest_chain_id));
+    let calldata = encode_send_bundle_call(destination_chain, call_starters, bundle_attributes)?;
+
+    if config.dry_run {
+        // Print the calldata before simulating: without a `from`, and with the
+        // approve only simulated, sendBundle usually reverts here.
+        progress(human, format_args!("sendBundleTx: dry-run (eth_call)"));
+        progress(
+            human,
+            format_args!("calldata: {}", format_hex(calldata.as_ref())),
+        );
+        result.calldata = Some(format_hex(calldata.as_ref()));
+        match eth_call_with_value(
             &source_client,
             addresses.interop_center,
-            calldata.clone(),
+            calldata,
             Some(total_value),
+            None,
         )
-        .await?;
-        let bundle_hash = crate::abi::decode_bytes32(result)?;
-        println!("sendBundleTx: dry-run (eth_call)");
-        println!("bundleHash: {bundle_hash:#x}");
-        print_next_steps(&src_rpc, &dest_rpc, src_chain_id, "<txHash>");
+        .await
+        {
+            Ok(returned) => {
+                let bundle_hash = crate::abi::decode_bytes32(returned)?;
+                progress(human, format_args!("bundleHash: {bundle_hash:#x}"));
+                result.bundle_hash = Some(format!("{bundle_hash:#x}"));
+            }
+            Err(err) => {
+                let failure = describe_dry_run_failure(&err);
+                if human {
+                    eprintln!("sendBundleTx: {failure}");
+                }
+                result.dry_run_error = Some(failure);
+            }
+        }
+        emit(&result, !human, None)?;
         return Ok(());
     }
 
     let send_tx_hash = send_tx(
         &source_client,
-        &src_rpc,
         wallet.as_ref(),
+        &mut sender.overrides,
         addresses.interop_center,
         calldata,
         Some(total_value),
     )
     .await?;
-    println!("sendBundleTx: {send_tx_hash}");
-    print_tx_debug("sendBundle", &src_rpc, &send_tx_hash);
+    progress(human, format_args!("sendBundleTx: {send_tx_hash}"));
+    if chatter {
+        print_tx_debug("sendBundle", &src_rpc, &send_tx_hash);
+    }
 
     let receipt = get_transaction_receipt(&source_client, B256::from_str(&send_tx_hash)?).await?;
-    let block_number = receipt
-        .block_number
-        .ok_or_else(|| anyhow!("missing receipt block number"))?;
-    let tx_index = receipt
-        .transaction_index
-        .ok_or_else(|| anyhow!("missing receipt tx index"))?;
-    println!("sendBundle block: {block_number}");
-    println!("sendBundle tx index: {tx_index}");
-
-    let mut bundle = None;
-    let mut bundle_hash = None;
-    for log in receipt.logs().iter() {
-        if log.topics().first().copied() == Some(interop_bundle_sent_topic()) {
-            let (_, hash, interop_bundle) = decode_interop_bundle_sent(log.data().data.clone())?;
-            bundle = Some(interop_bundle);
-            bundle_hash = Some(hash);
-            break;
-        }
+    let (bundle_hash, _) = find_sent_bundle(&receipt)?;
+    result.source_tx_hash = Some(send_tx_hash.clone());
+    result.bundle_hash = Some(format!("{bundle_hash:#x}"));
+    if emit(&result, !human, None)? {
+        return Ok(());
     }
-
-    let bundle_hash = bundle_hash.ok_or_else(|| anyhow!("missing InteropBundleSent event"))?;
     println!("bundleHash: {bundle_hash:#x}");
-    println!(
-        "bundle status command: cast-interop bundle status {} --bundle-hash {bundle_hash:#x}",
-        format_rpc_flag(&dest_rpc)
-    );
-
-    let bundle = bundle.ok_or_else(|| anyhow!("missing InteropBundleSent bundle"))?;
-    let encoded_bundle = encode_interop_bundle(&bundle);
-
-    let timeout = Duration::from_millis(args.timeout_ms.unwrap_or(300_000));
-    let poll = Duration::from_millis(args.poll_ms.unwrap_or(1_000));
-
-    if args.watch {
-        println!("watch: waiting for finalized block on source...");
-    } else {
-        println!("Waiting for finalized block on source...");
+    if chatter {
+        print_next_steps(&src_rpc, &dest_rpc, src_chain_id, &send_tx_hash);
     }
-    wait_for_finalized_block(
-        &source_client,
-        block_number,
-        timeout,
-        Duration::from_millis(100),
-    )
-    .await?;
+    Ok(())
+}
 
-    if args.watch {
-        println!("watch: waiting for log proof on source...");
-    } else {
-        println!("Waiting for log proof on source...");
-    }
-    let log_proof = wait_for_log_proof(
-        &source_client,
-        B256::from_str(&send_tx_hash)?,
-        0,
-        timeout,
-        poll,
-    )
-    .await?;
+/// Destination-side inputs shared by `token send` and `token send-continue`.
+struct DestinationStep<'a> {
+    source_client: &'a RpcClient,
+    dest_client: &'a RpcClient,
+    src_rpc: &'a ResolvedRpc,
+    dest_rpc: &'a ResolvedRpc,
+    wallet: Option<&'a EthereumWallet>,
+    overrides: &'a TxOverrides,
+    addresses: &'a AddressBook,
+    vault: Address,
+    asset_id: &'a Bytes,
+    native: bool,
+    recipient: Address,
+    /// Amount the recipient should receive, for the balance delta check.
+    amount: U256,
+    mode: &'a str,
+    bundle_identifier: u8,
+    timeout: Duration,
+    poll: Duration,
+    /// Blocks the send receipt must be buried under (`--confirmations`).
+    confirmations: u64,
+    /// Directory for cached proofs (`--proof-cache`).
+    proof_cache: Option<PathBuf>,
+    /// Print progress; off under `--output json`.
+    human: bool,
+    /// Print debug command hints; also off under `--quiet`.
+    hints: bool,
+}
 
-    println!("proof batch: {}", log_proof.batch_number);
-    println!("proof msg index: {}", log_proof.id);
-    println!("proof root: {}", log_proof.root);
+/// What the destination step observed, for the `token send` JSON result.
+struct DestinationOutcome {
+    bundle_hash: B256,
+    l1_batch_number: u64,
+    l2_message_index: u64,
+    handler_tx_hash: String,
+    bundle_status: Option<u8>,
+    wrapped_token: Option<Address>,
+    balance_before: Option<U256>,
+    balance_after: Option<U256>,
+}
 
-    if args.watch {
-        println!("watch: waiting for interop root on destination...");
-    } else {
-        println!("Waiting for interop root on destination...");
-    }
-    wait_for_root(
-        &dest_client,
-        addresses.interop_root_storage,
-        src_chain_id,
-        log_proof.batch_number,
-        log_proof.root.clone(),
+/// Wait for proof/root of a sent token bundle and run the destination handler.
+///
+/// Reports the bundle status after verify, or the wrapped balance and its
+/// change against the sent amount after execute.
+async fn complete_on_destination(
+    step: DestinationStep<'_>,
+    send_tx_hash: B256,
+    receipt: TransactionReceipt,
+) -> Result<DestinationOutcome> {
+    let DestinationStep {
+        source_client,
+        dest_client,
+        src_rpc,
+        dest_rpc,
+        wallet,
+        overrides,
+        addresses,
+        vault,
+        asset_id,
+        native,
+        recipient: to,
+        amount,
+        mode,
+        bundle_identifier,
         timeout,
         poll,
-    )
-    .await?;
+        confirmations,
+        proof_cache,
+        human,
+        hints,
+    } = step;
 
-    let message = ProofMessage {
-        tx_number_in_batch: tx_index,
-        sender: address_to_hex(addresses.interop_center),
-        data: format!(
-            "0x{}{}",
-            hex::encode([BUNDLE_IDENTIFIER]),
-            hex::encode(encoded_bundle.as_ref())
+    let block_number = receipt
+        .block_number
+        .ok_or_else(|| anyhow!("missing receipt block number"))?;
+    let tx_index = receipt
+        .transaction_index
+        .ok_or_else(|| anyhow!("missing receipt tx index"))?;
+    tracing::info!(block_number, tx_index, "sendBundle mined");
+
+    let (bundle_hash, _) = find_sent_bundle(&receipt)?;
+    progress(human, format_args!("bundleHash: {bundle_hash:#x}"));
+    progress(
+        hints,
+        format_args!(
+            "bundle status command: cast-interop bundle status {} --bundle-hash {bundle_hash:#x}",
+            format_rpc_flag(dest_rpc)
         ),
-    };
+    );
 
-    let proof = MessageInclusionProof {
-        chain_id: src_chain_id.to_string(),
-        l1_batch_number: log_proof.batch_number,
-        l2_message_index: log_proof.id,
-        root: log_proof.root.clone(),
-        message,
-        proof: log_proof.proof.clone(),
+    let resume = format!(
+        "cast-interop bundle relay {} {} --tx {send_tx_hash:#x} --mode {mode}",
+        format_src_flag(src_rpc),
+        format_dest_flag(dest_rpc)
+    );
+    let prover = SourceProver {
+        source_client: source_client.clone(),
+        dest_client: dest_client.clone(),
+        center: addresses.interop_center,
+        l1_messenger: addresses.l1_messenger,
+        root_storage: addresses.interop_root_storage,
+        msg_index: 0,
+        bundle_index: None,
+        bundle_identifier,
+        limit: WaitLimit::from_timeout(timeout),
+        poll,
+        confirmations,
+        cache_dir: proof_cache,
     };
+    let PreparedProof {
+        encoded_bundle,
+        proof,
+        ..
+    } = prepare_proof(&prover, send_tx_hash, &resume).await?;
+
+    tracing::info!(
+        batch = proof.l1_batch_number,
+        msg_index = proof.l2_message_index,
+        root = %proof.root,
+        "proof ready"
+    );
 
-    let handler_calldata = match args.mode.as_str() {
+    let handler_calldata = match mode {
         "verify" => encode_verify_bundle_call(encoded_bundle.clone(), proof.clone())?,
         "execute" => encode_execute_bundle_call(encoded_bundle.clone(), proof.clone())?,
         other => anyhow::bail!("invalid mode {other} (expected execute or verify)"),
     };
 
+    // A wrapped token that does not exist yet means a starting balance of 0.
+    let balance_before = if mode == "execute" {
+        Some(destination_balance(dest_client, native, vault, asset_id, to).await?)
+    } else {
+        None
+    };
+
     let handler_tx_hash = send_tx(
-        &dest_client,
-        &dest_rpc,
-        wallet.as_ref(),
+        dest_client,
+        wallet,
+        &mut overrides.clone(),
         addresses.interop_handler,
         handler_calldata,
         None,
     )
     .await?;
-    match args.mode.as_str() {
-        "verify" => println!("verifyTx: {handler_tx_hash}"),
-        _ => println!("executeTx: {handler_tx_hash}"),
+    match mode {
+        "verify" => progress(human, format_args!("verifyTx: {handler_tx_hash}")),
+        _ => progress(human, format_args!("executeTx: {handler_tx_hash}")),
     }
-    print_tx_debug("handler", &dest_rpc, &handler_tx_hash);
+    if hints {
+        print_tx_debug("handler", dest_rpc, &handler_tx_hash);
+    }
+    let mut outcome = DestinationOutcome {
+        bundle_hash,
+        l1_batch_number: proof.l1_batch_number,
+        l2_message_index: proof.l2_message_index,
+        handler_tx_hash,
+        bundle_status: None,
+        wrapped_token: None,
+        balance_before: None,
+        balance_after: None,
+    };
 
-    if args.mode == "verify" {
-        let status =
-            fetch_bundle_status(&dest_client, addresses.interop_handler, bundle_hash).await;
+    if mode == "verify" {
+        let status = fetch_bundle_status(dest_client, addresses.interop_handler, bundle_hash).await;
         if let Ok(status) = status {
-            println!("bundle status: {}", status_string(status));
+            progress(
+                human,
+                format_args!("bundle status: {}", status_string(status)),
+            );
+            outcome.bundle_status = Some(status);
         }
-        return Ok(());
+        return Ok(outcome);
     }
 
-    let wrapped_token = fetch_wrapped_token(&dest_client, vault, &asset_id).await?;
-    if wrapped_token == Address::ZERO {
-        println!("wrapped token not registered on destination yet");
-        return Ok(());
+    let balance = destination_balance(dest_client, native, vault, asset_id, to).await?;
+    let Some(token) = balance.token else {
+        progress(
+            human,
+            format_args!("wrapped token not registered on destination yet"),
+        );
+        return Ok(outcome);
+    };
+    outcome.wrapped_token = Some(token);
+    outcome.balance_before = balance_before.as_ref().map(|before| before.amount);
+    outcome.balance_after = Some(balance.amount);
+    let decimals = if native {
+        Some(18)
+    } else {
+        fetch_decimals(dest_client, token).await
+    };
+    if let Some(decimals) = decimals {
+        progress(
+            human,
+            format_args!(
+                "destination balance: {}",
+                format_units(balance.amount, decimals)
+            ),
+        );
     }
-    let balance = fetch_balance(&dest_client, wrapped_token, to).await?;
-    let dest_decimals = fetch_decimals(&dest_client, wrapped_token).await;
-    if let Some(decimals) = dest_decimals {
-        println!("destination balance: {}", format_units(balance, decimals));
+    progress(
+        human,
+        format_args!("destination balance (raw): {}", balance.amount),
+    );
+
+    let before = balance_before.map_or(U256::ZERO, |before| before.amount);
+    progress(
+        human,
+        format_args!("destination balance before (raw): {before}"),
+    );
+    let format = |value: U256| match decimals {
+        Some(decimals) => format_units(value, decimals),
+        None => value.to_string(),
+    };
+    let delta = if balance.amount >= before {
+        format!("+{}", format(balance.amount - before))
+    } else {
+        format!("-{}", format(before - balance.amount))
+    };
+    progress(
+        human,
+        format_args!(
+            "destination balance delta: {delta} (expected +{})",
+            format(amount)
+        ),
+    );
+    if balance.amount != before + amount {
+        // Native transfers to the handler signer also pay the execute gas.
+        let pays_gas = native
+            && wallet
+                .map(signer_address)
+                .transpose()?
+                .is_some_and(|signer| signer == to);
+        if pays_gas {
+            progress(
+                human,
+                format_args!(
+                    "note: recipient is the signer, so the delta includes the execute gas fee"
+                ),
+            );
+        } else {
+            eprintln!(
+                "warning: destination balance changed by {delta}, expected +{}",
+                format(amount)
+            );
+        }
     }
-    println!("destination balance (raw): {balance}");
 
-    Ok(())
+    Ok(outcome)
+}
+
+/// Recipient balance on the destination chain and the token it is held in
+/// (the zero address for the base token).
+struct DestinationBalance {
+    token: Option<Address>,
+    amount: U256,
+}
+
+/// Fetch the recipient's destination balance of the sent asset; a wrapped
+/// token that is not deployed yet reports no token and a zero balance.
+async fn destination_balance(
+    client: &RpcClient,
+    native: bool,
+    vault: Address,
+    asset_id: &Bytes,
+    recipient: Address,
+) -> Result<DestinationBalance> {
+    if native {
+        return Ok(DestinationBalance {
+            token: Some(Address::ZERO),
+            amount: client.provider.get_balance(recipient).await?,
+        });
+    }
+    let wrapped_token = fetch_wrapped_token(client, vault, asset_id, None).await?;
+    if wrapped_token == Address::ZERO {
+        return Ok(DestinationBalance {
+            token: None,
+            amount: U256::ZERO,
+        });
+    }
+    Ok(DestinationBalance {
+        token: Some(wrapped_token),
+        amount: fetch_balance(client, wrapped_token, recipient, None).await?,
+    })
+}
+
+/// Find the InteropBundleSent event in a source receipt.
+fn find_sent_bundle(receipt: &TransactionReceipt) -> Result<(B256, InteropBundle)> {
+    let tx_hash = receipt.transaction_hash;
+    let (_, hash, interop_bundle) = select_sent_bundle(receipt, tx_hash, None)?;
+    Ok((hash, interop_bundle))
 }
 
 /// Build the calldata for the second bridge hop in a token transfer.
@@ -552,6 +1963,52 @@ fn build_second_bridge_calldata(
     Ok(Bytes::from(out))
 }
 
+/// Decode an asset router transfer payload built by `build_second_bridge_calldata`.
+///
+/// Returns the asset ID, amount, and receiver.
+fn decode_second_bridge_calldata(data: &Bytes) -> Result<(B256, U256, Address)> {
+    let (version, rest) = data
+        .split_first()
+        .ok_or_else(|| anyhow!("empty bridge calldata"))?;
+    if *version != NEW_ENCODING_VERSION {
+        anyhow::bail!("unsupported bridge calldata version {version}");
+    }
+    let (asset_id, transfer_data) = <(B256, Bytes)>::abi_decode_params(rest)?;
+    let (amount, receiver, _) = <(U256, Address, Address)>::abi_decode(&transfer_data)?;
+    Ok((asset_id, amount, receiver))
+}
+
+/// Load a `token send-batch` recipients file into (recipient, amount in wei) pairs.
+fn load_recipients(path: &Path, decimals: Option<u32>) -> Result<Vec<(Address, U256)>> {
+    let contents = fs::read_to_string(path).context("failed to read recipients file")?;
+    let file: RecipientFile = serde_json::from_str(&contents).context("invalid recipients file")?;
+    if file.recipients.is_empty() {
+        anyhow::bail!("recipients file must include at least one recipient");
+    }
+    file.recipients
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let to = parse_checked_address(&entry.to)
+                .with_context(|| format!("recipients[{index}]: invalid address"))?;
+            let amount = match (entry.amount_wei.as_deref(), entry.amount.as_deref()) {
+                (Some(_), Some(_)) => {
+                    anyhow::bail!("recipients[{index}]: set amount or amountWei, not both")
+                }
+                (Some(raw), None) => parse_u256(raw)?,
+                (None, Some(amount)) => {
+                    let decimals = decimals.ok_or_else(|| {
+                        anyhow!("token decimals unavailable (set --decimals or use amountWei)")
+                    })?;
+                    parse_decimal_amount(amount, decimals)?
+                }
+                (None, None) => anyhow::bail!("recipients[{index}]: missing amount"),
+            };
+            Ok((to, amount))
+        })
+        .collect()
+}
+
 /// Resolve the approval amount based on user flags.
 ///
 /// Accepts \"infinite\" or defaults to the send amount.
@@ -574,56 +2031,23 @@ async fn resolve_amount_wei(args: &TokenSendArgs, decimals: Option<u32>) -> Resu
     let amount = args
         .amount
         .as_deref()
-        .ok_or_else(|| anyhow!("set --amount or --amount-wei"))?;
+        .ok_or_else(|| anyhow!("set --amount, --amount-wei, or --amount-all"))?;
     let decimals = decimals.ok_or_else(|| {
         anyhow!("token decimals unavailable (set --decimals or use --amount-wei)")
     })?;
     parse_decimal_amount(amount, decimals)
 }
 
-/// Parse a human-readable decimal token amount into wei.
-///
-/// Enforces that fractional digits do not exceed the token decimals.
-fn parse_decimal_amount(amount: &str, decimals: u32) -> Result<U256> {
-    let trimmed = amount.trim();
-    let mut parts = trimmed.split('.');
-    let whole_part = parts.next().unwrap_or("0");
-    let fraction_part = parts.next();
-    if parts.next().is_some() {
-        anyhow::bail!("invalid amount {amount}");
-    }
-
-    let whole = if whole_part.is_empty() {
-        U256::ZERO
-    } else {
-        parse_u256(whole_part)?
+/// Fetch the base token asset ID of a chain from the L2 bridgehub.
+async fn fetch_base_token_asset_id(client: &RpcClient, chain_id: u64) -> Result<Bytes> {
+    let call = baseTokenAssetIdCall {
+        _chainId: U256::from(chain_id),
     };
-    let base = pow10(decimals)?;
-    let mut value = whole * base;
-
-    if let Some(fraction_part) = fraction_part {
-        if fraction_part.len() > decimals as usize {
-            anyhow::bail!("amount has too many decimal places (max {decimals})");
-        }
-        if !fraction_part.is_empty() {
-            let fraction = parse_u256(fraction_part)?;
-            let scale = pow10(decimals - fraction_part.len() as u32)?;
-            value += fraction * scale;
-        }
-    }
-
-    Ok(value)
-}
-
-/// Compute 10^exp with overflow protection.
-fn pow10(exp: u32) -> Result<U256> {
-    let mut value = U256::from(1u64);
-    for _ in 0..exp {
-        value = value
-            .checked_mul(U256::from(10u64))
-            .ok_or_else(|| anyhow!("amount overflow"))?;
-    }
-    Ok(value)
+    let result = eth_call(client, L2_BRIDGEHUB_ADDRESS, Bytes::from(call.abi_encode()))
+        .await
+        .context("failed to fetch base token asset ID")?;
+    let asset_id = crate::abi::decode_bytes32(result)?;
+    Ok(Bytes::from(asset_id.to_vec()))
 }
 
 /// Fetch the wrapped token address from the native token vault.
@@ -631,22 +2055,53 @@ async fn fetch_wrapped_token(
     client: &RpcClient,
     vault: Address,
     asset_id: &Bytes,
+    block: Option<BlockId>,
 ) -> Result<Address> {
     let asset_id_b256 = B256::from_slice(asset_id.as_ref());
     let call = tokenAddressCall {
         _assetId: asset_id_b256,
     };
     let data = Bytes::from(call.abi_encode());
-    let result = eth_call(client, vault, data).await?;
+    let result = eth_call_at(client, vault, data, block).await?;
     let value: (Address,) = <(Address,)>::abi_decode(result.as_ref())?;
     Ok(value.0)
 }
 
+/// Fetch the ERC20 allowance `owner` has granted to `spender`.
+async fn fetch_allowance(
+    client: &RpcClient,
+    token: Address,
+    owner: Address,
+    spender: Address,
+) -> Result<U256> {
+    let call = allowanceCall { owner, spender };
+    let data = Bytes::from(call.abi_encode());
+    let result = eth_call(client, token, data)
+        .await
+        .context("failed to fetch token allowance")?;
+    let value: (U256,) = <(U256,)>::abi_decode(result.as_ref())?;
+    Ok(value.0)
+}
+
+/// Format an allowance, spelling out the infinite (uint256 max) approval.
+fn format_allowance(value: U256, decimals: u32) -> String {
+    if value == U256::MAX {
+        "infinite".to_string()
+    } else {
+        format_units(value, decimals)
+    }
+}
+
 /// Fetch an ERC20 balance using balanceOf.
-async fn fetch_balance(client: &RpcClient, token: Address, owner: Address) -> Result<U256> {
+async fn fetch_balance(
+    client: &RpcClient,
+    token: Address,
+    owner: Address,
+    block: Option<BlockId>,
+) -> Result<U256> {
     let call = balanceOfCall { account: owner };
     let data = Bytes::from(call.abi_encode());
-    let result = eth_call(client, token, data).await?;
+    let result = eth_call_at(client, token, data, block).await?;
     let value: (U256,) = <(U256,)>::abi_decode(result.as_ref())?;
     Ok(value.0)
 }
@@ -665,7 +2120,7 @@ async fn fetch_symbol(client: &RpcClient, token: Address) -> Option<String> {
     let call = symbolCall {};
     let data = Bytes::from(call.abi_encode());
     let result = eth_call(client, token, data).await.ok()?;
-    let value: (String,) = <(String,)>::abi_decode(result.as_ref()).ok()?;
+    let value: (String,) = <(String,)>::abi_decode_params(result.as_ref()).ok()?;
     Some(value.0)
 }
 
@@ -674,12 +2129,12 @@ async fn fetch_name(client: &RpcClient, token: Address) -> Option<String> {
     let call = nameCall {};
     let data = Bytes::from(call.abi_encode());
     let result = eth_call(client, token, data).await.ok()?;
-    let value: (String,) = <(String,)>::abi_decode(result.as_ref()).ok()?;
+    let value: (String,) = <(String,)>::abi_decode_params(result.as_ref()).ok()?;
     Some(value.0)
 }
 
 /// Format a token value with the given decimals.
-fn format_units(value: U256, decimals: u32) -> String {
+pub(crate) fn format_units(value: U256, decimals: u32) -> String {
     if decimals == 0 {
         return value.to_string();
     }
@@ -699,65 +2154,141 @@ fn format_units(value: U256, decimals: u32) -> String {
     out
 }
 
-/// Send a signed transaction and wait for a receipt.
+/// Source-chain context for the register/approve steps of the token flows.
+struct SourceSender<'a> {
+    client: &'a RpcClient,
+    rpc: &'a ResolvedRpc,
+    wallet: Option<&'a EthereumWallet>,
+    overrides: TxOverrides,
+    dry_run: bool,
+    /// Collect each step's calldata instead of sending or simulating it.
+    calldata_only: bool,
+    /// Print progress; off under `--output json`.
+    human: bool,
+    /// Print debug command hints; also off under `--quiet`.
+    hints: bool,
+    /// Steps collected under `calldata_only`.
+    raw_calls: Vec<RawCall>,
+}
+
+impl SourceSender<'_> {
+    /// Register the token with the native token vault.
+    ///
+    /// Returns the transaction hash when one was sent.
+    async fn register(&mut self, vault: Address, token: Address) -> Result<Option<String>> {
+        let call = ensureTokenIsRegisteredCall { _token: token };
+        self.step("register", vault, Bytes::from(call.abi_encode()))
+            .await
+    }
+
+    /// Approve `spender` (the vault) to pull `amount` of the token.
+    ///
+    /// Returns the transaction hash when one was sent.
+    async fn approve(
+        &mut self,
+        token: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<Option<String>> {
+        let call = approveCall {
+            spender,
+            value: amount,
+        };
+        self.step("approve", token, Bytes::from(call.abi_encode()))
+            .await
+    }
+
+    /// Send `data` to `to`, only simulate it under --dry-run, or collect it
+    /// under --calldata-only.
+    async fn step(&mut self, label: &str, to: Address, data: Bytes) -> Result<Option<String>> {
+        if self.calldata_only {
+            let call = RawCall::new(Some(label), to, U256::ZERO, &data);
+            if self.human {
+                print_raw_call(&call);
+            }
+            self.raw_calls.push(call);
+            return Ok(None);
+        }
+        if self.dry_run {
+            // Later steps are still simulated: a revert here usually only
+            // means an earlier simulated step (e.g. registration) never ran.
+            match eth_call(self.client, to, data).await {
+                Ok(_) => progress(self.human, format_args!("{label}Tx: dry-run (eth_call)")),
+                Err(err) => eprintln!("{label}Tx: {}", describe_dry_run_failure(&err)),
+            }
+            return Ok(None);
+        }
+        let tx_hash = send_tx(
+            self.client,
+            self.wallet,
+            &mut self.overrides,
+            to,
+            data,
+            None,
+        )
+        .await?;
+        progress(self.human, format_args!("{label}Tx: {tx_hash}"));
+        if self.hints {
+            print_tx_debug(label, self.rpc, &tx_hash);
+        }
+        Ok(Some(tx_hash))
+    }
+}
+
+/// Print a progress line unless `--output json` keeps stdout for the result.
+fn progress(human: bool, line: std::fmt::Arguments<'_>) {
+    if human {
+        println!("{line}");
+    }
+}
+
+/// Send a signed transaction and wait for a receipt, failing if it reverted.
+///
+/// A pinned nonce is advanced after sending so the next transaction on the
+/// same chain does not reuse it.
 async fn send_tx(
     client: &RpcClient,
-    rpc: &ResolvedRpc,
-    wallet: Option<&alloy_signer_local::PrivateKeySigner>,
+    wallet: Option<&EthereumWallet>,
+    overrides: &mut TxOverrides,
     to: Address,
     data: Bytes,
     value: Option<U256>,
 ) -> Result<String> {
     let wallet = wallet.ok_or_else(|| anyhow!("signer required"))?;
-    let chain_id = client.provider.get_chain_id().await?;
-    let provider = ProviderBuilder::new()
-        .wallet(wallet.clone())
-        .with_chain_id(chain_id)
-        .connect(&rpc.url)
-        .await?;
+    let provider = client.signer_provider(wallet.clone()).await?;
 
-    let request = TransactionRequest {
+    let mut request = TransactionRequest {
         to: Some(to.into()),
         input: TransactionInput::new(data),
         value,
         ..Default::default()
     };
+    overrides.apply(client, &mut request).await?;
 
     let pending = decode_send_transaction(provider.send_transaction(request).await)?;
 
+    if let Some(nonce) = overrides.nonce.as_mut() {
+        *nonce += 1;
+    }
+
     let tx_hash = pending.tx_hash().clone();
-    let _receipt = pending.get_receipt().await?;
+    let receipt = pending.get_receipt().await?;
+    // A reverted step must not be reported (or saved to --state-file) as done.
+    if !receipt.status() {
+        anyhow::bail!("transaction {tx_hash:#x} reverted");
+    }
     Ok(format!("{tx_hash:#x}"))
 }
 
-/// Wait for the interop root to appear on the destination chain.
-async fn wait_for_root(
-    client: &RpcClient,
-    root_storage: Address,
-    chain_id: u64,
-    batch_number: u64,
-    expected_root: String,
-    timeout: Duration,
-    poll: Duration,
-) -> Result<()> {
-    let expected = B256::from_str(&expected_root)?;
-    let start = tokio::time::Instant::now();
-    loop {
-        let data =
-            crate::abi::encode_interop_roots_call(U256::from(chain_id), U256::from(batch_number));
-        let result = eth_call(client, root_storage, data).await?;
-        let root = crate::abi::decode_bytes32(result)?;
-        if root != B256::ZERO {
-            if root == expected {
-                return Ok(());
-            }
-            anyhow::bail!("interop root mismatch: expected {expected_root}, got {root:#x}");
-        }
-        if start.elapsed() > timeout {
-            anyhow::bail!("interop root did not become available in time");
-        }
-        tokio::time::sleep(poll).await;
-    }
+/// Print an unsigned source transaction for `--calldata-only`.
+fn print_raw_call(call: &RawCall) {
+    println!(
+        "{}Tx: calldata only",
+        call.label.as_deref().unwrap_or_default()
+    );
+    println!("  to: {}", call.to);
+    println!("  value: {}", call.value);
+    println!("  data: {}", call.data);
 }
 
 /// Print a debug hint pointing to the decoded transaction view.
@@ -0,0 +1,209 @@
+use crate::error::CastInteropError;
+use crate::rpc::{eth_call, RpcClient};
+use alloy_primitives::{Address, Bytes, FixedBytes, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::{SolCall, SolValue};
+use anyhow::Result;
+use serde::Serialize;
+
+alloy_sol_types::sol! {
+    function decimals() view returns (uint8);
+    function symbol() view returns (string);
+    function name() view returns (string);
+    function supportsInterface(bytes4 interfaceId) view returns (bool);
+}
+
+/// ERC-165 interface IDs of `IERC165`, `IERC20` and `IERC20Metadata`.
+const IERC165_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+const IERC20_ID: [u8; 4] = [0x36, 0x37, 0x2b, 0x07];
+const IERC20_METADATA_ID: [u8; 4] = [0xa2, 0x19, 0xa0, 0x25];
+
+/// Which optional ERC20 functions a token implements, and whether it
+/// answers ERC-165 `supportsInterface`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInterface {
+    /// False when there is no contract at the address at all.
+    pub has_code: bool,
+    pub decimals: FunctionProbe,
+    pub symbol: FunctionProbe,
+    pub name: FunctionProbe,
+    pub erc165: Erc165Support,
+}
+
+/// Result of calling one view function on the token.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionProbe {
+    /// "ok", "notImplemented" (empty return or a revert without data),
+    /// "reverted" (a revert with a reason), "invalidReturn" (data of the
+    /// wrong type), "noCode", or "error" (the RPC call itself failed).
+    pub status: &'static str,
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Erc165Support {
+    pub supported: bool,
+    /// Only set when `supported`.
+    pub ierc20: Option<bool>,
+    pub ierc20_metadata: Option<bool>,
+}
+
+impl FunctionProbe {
+    fn ok(value: String) -> Self {
+        Self {
+            status: "ok",
+            value: Some(value),
+            details: None,
+        }
+    }
+
+    fn failed(status: &'static str, details: Option<String>) -> Self {
+        Self {
+            status,
+            value: None,
+            details,
+        }
+    }
+
+    /// Text rendering: the value, or why there is none.
+    pub fn describe(&self) -> String {
+        match (&self.value, self.status, &self.details) {
+            (Some(value), _, _) => value.clone(),
+            (None, "notImplemented", _) => "not implemented".to_string(),
+            (None, "noCode", _) => "no contract at address".to_string(),
+            (None, "invalidReturn", Some(details)) => format!("invalid return data ({details})"),
+            (None, "reverted", Some(details)) => format!("call reverted: {details}"),
+            (None, _, Some(details)) => format!("call failed: {details}"),
+            (None, status, None) => status.to_string(),
+        }
+    }
+}
+
+/// Call `decimals`, `symbol` and `name` on `token` and probe ERC-165.
+///
+/// Tells a function the token does not implement (an empty return or a bare
+/// revert, as a missing selector produces) apart from one that reverted with
+/// a reason or returned data of the wrong type.
+pub async fn probe_token(client: &RpcClient, token: Address) -> Result<TokenInterface> {
+    let has_code = !client.provider.get_code_at(token).await?.is_empty();
+    if !has_code {
+        return Ok(TokenInterface {
+            has_code,
+            decimals: FunctionProbe::failed("noCode", None),
+            symbol: FunctionProbe::failed("noCode", None),
+            name: FunctionProbe::failed("noCode", None),
+            erc165: Erc165Support {
+                supported: false,
+                ierc20: None,
+                ierc20_metadata: None,
+            },
+        });
+    }
+
+    let decimals = probe(client, token, decimalsCall {}.abi_encode(), |data| {
+        <(U256,)>::abi_decode(data)
+            .ok()
+            .filter(|(value,)| *value <= U256::from(u8::MAX))
+            .map(|(value,)| value.to_string())
+    })
+    .await;
+    let symbol = probe(client, token, symbolCall {}.abi_encode(), decode_string).await;
+    let name = probe(client, token, nameCall {}.abi_encode(), decode_string).await;
+
+    // ERC-165 asks for true on its own ID and false on 0xffffffff.
+    let supported = supports_interface(client, token, IERC165_ID).await == Some(true)
+        && supports_interface(client, token, [0xff; 4]).await == Some(false);
+    let erc165 = if supported {
+        Erc165Support {
+            supported,
+            ierc20: supports_interface(client, token, IERC20_ID).await,
+            ierc20_metadata: supports_interface(client, token, IERC20_METADATA_ID).await,
+        }
+    } else {
+        Erc165Support {
+            supported,
+            ierc20: None,
+            ierc20_metadata: None,
+        }
+    };
+
+    Ok(TokenInterface {
+        has_code,
+        decimals,
+        symbol,
+        name,
+        erc165,
+    })
+}
+
+/// Print the probe results, one line per function, with `prefix` before
+/// each name (e.g. "source ").
+pub fn print_interface(prefix: &str, interface: &TokenInterface) {
+    println!("{prefix}symbol: {}", interface.symbol.describe());
+    println!("{prefix}name: {}", interface.name.describe());
+    println!("{prefix}decimals: {}", interface.decimals.describe());
+    if !interface.has_code {
+        return;
+    }
+    let yes_no = |value: Option<bool>| match value {
+        Some(true) => "yes",
+        Some(false) => "no",
+        None => "unknown",
+    };
+    if interface.erc165.supported {
+        println!(
+            "{prefix}ERC-165: supported (IERC20: {}, IERC20Metadata: {})",
+            yes_no(interface.erc165.ierc20),
+            yes_no(interface.erc165.ierc20_metadata)
+        );
+    } else {
+        println!("{prefix}ERC-165: not supported");
+    }
+}
+
+fn decode_string(data: &[u8]) -> Option<String> {
+    <(String,)>::abi_decode_params(data)
+        .ok()
+        .map(|(value,)| value)
+}
+
+async fn probe(
+    client: &RpcClient,
+    token: Address,
+    calldata: Vec<u8>,
+    decode: impl Fn(&[u8]) -> Option<String>,
+) -> FunctionProbe {
+    match eth_call(client, token, Bytes::from(calldata)).await {
+        Ok(data) if data.is_empty() => FunctionProbe::failed("notImplemented", None),
+        Ok(data) => match decode(&data) {
+            Some(value) => FunctionProbe::ok(value),
+            None => FunctionProbe::failed("invalidReturn", Some(format!("{} bytes", data.len()))),
+        },
+        Err(err) => match err.downcast_ref::<CastInteropError>() {
+            Some(CastInteropError::Revert { reason, .. }) => {
+                FunctionProbe::failed("reverted", Some(reason.clone()))
+            }
+            _ if err.to_string().to_lowercase().contains("revert") => {
+                FunctionProbe::failed("notImplemented", None)
+            }
+            _ => FunctionProbe::failed("error", Some(format!("{err:#}"))),
+        },
+    }
+}
+
+/// `supportsInterface(id)`, or None when the call fails or does not return
+/// a bool.
+async fn supports_interface(client: &RpcClient, token: Address, id: [u8; 4]) -> Option<bool> {
+    let call = supportsInterfaceCall {
+        interfaceId: FixedBytes(id),
+    };
+    let data = eth_call(client, token, Bytes::from(call.abi_encode()))
+        .await
+        .ok()?;
+    <(bool,)>::abi_decode(&data).ok().map(|(value,)| value)
+}
@@ -5,10 +5,14 @@ use crate::abi::{
 };
 use crate::cli::TxShowArgs;
 use crate::config::Config;
-use crate::rpc::{get_transaction_receipt, RpcClient};
+use crate::output::emit;
+use crate::rpc::{
+    get_transaction_receipt, interruptible, wait_for_confirmations, wait_for_transaction_receipt,
+    RpcClient, WaitLimit,
+};
 use crate::types::{
     address_to_hex, b256_to_hex, format_hex, u256_to_string, AddressBook, EventView,
-    InteropBundleView, TxShowOutput, INTEROP_CENTER_ADDRESS, L1_SENDER_ADDRESS,
+    InteropBundleView, TxBundleView, TxShowOutput,
 };
 use alloy_primitives::{Address, B256, U256};
 use anyhow::{Context, Result};
@@ -18,146 +22,84 @@ use std::str::FromStr;
 /// Decode interop events from a transaction receipt.
 ///
 /// Prints bundle information, message hashes, and event summaries.
-pub async fn run(args: TxShowArgs, config: Config, _addresses: AddressBook) -> Result<()> {
+#[tracing::instrument(name = "debug tx", skip_all, fields(chain = args.rpc.chain.as_deref(), tx = %args.tx_hash))]
+pub async fn run(args: TxShowArgs, config: Config, addresses: AddressBook) -> Result<()> {
     let resolved = config.resolve_rpc(args.rpc.rpc.as_deref(), args.rpc.chain.as_deref())?;
-    let client = RpcClient::new(&resolved.url).await?;
+    let client = RpcClient::for_rpc(&resolved, config.rpc_timeout, &config.rpc_headers).await?;
     let tx_hash = B256::from_str(&args.tx_hash)
         .with_context(|| format!("invalid tx hash {}", args.tx_hash))?;
-    let receipt = get_transaction_receipt(&client, tx_hash).await?;
+    let receipt = if args.follow {
+        interruptible(
+            wait_for_transaction_receipt(
+                &client,
+                tx_hash,
+                config.wait_timeout(args.timeout_ms),
+                config.poll_interval(args.poll_ms),
+            ),
+            || format!("transaction {tx_hash:#x} not mined yet"),
+        )
+        .await?
+    } else {
+        get_transaction_receipt(&client, tx_hash).await?
+    };
+    interruptible(
+        wait_for_confirmations(
+            &client,
+            &receipt,
+            config.confirmations(args.confirmations),
+            WaitLimit::from_timeout(config.wait_timeout(args.timeout_ms)),
+            config.poll_interval(args.poll_ms),
+        ),
+        || format!("transaction {tx_hash:#x} not confirmed yet"),
+    )
+    .await?;
 
-    let mut bundle_view: Option<InteropBundleView> = None;
-    let mut bundle_hash: Option<String> = None;
-    let mut l2l1_msg_hash: Option<String> = None;
+    let mut bundles = Vec::new();
     let mut events = Vec::new();
 
     for log in receipt.logs() {
-        let topic0 = log.topics().get(0).cloned();
-        let Some(topic0) = topic0 else { continue };
-        if topic0 == interop_bundle_sent_topic() && log.address() == INTEROP_CENTER_ADDRESS {
-            let (l2l1_hash, interop_hash, bundle) =
-                decode_interop_bundle_sent(log.data().data.clone())?;
-            let bundle_json = crate::abi::bundle_view(&bundle);
-            bundle_view = Some(bundle_json.clone());
-            bundle_hash = Some(b256_to_hex(interop_hash));
-            l2l1_msg_hash = Some(b256_to_hex(l2l1_hash));
-            events.push(EventView {
-                name: "InteropBundleSent".to_string(),
-                address: address_to_hex(log.address()),
-                data: serde_json::to_value(&bundle_json)?,
-            });
-        } else if topic0 == l1_message_sent_topic() && log.address() == L1_SENDER_ADDRESS {
-            print!("Decoding L1MessageSent event...\n");
-            let sender = log
-                .topics()
-                .get(1)
-                .map(|topic| address_to_hex(Address::from_slice(&topic.as_slice()[12..])))
-                .unwrap_or_default();
-            let l2l1_msg_hash = log
-                .topics()
-                .get(2)
-                .map(|topic| b256_to_hex(*topic))
-                .unwrap_or_default();
-            events.push(EventView {
-                name: "L1MessageSent".to_string(),
-                address: sender.clone(),
-                data: json!({
-                    "sender": sender,
-                    "l2l1MsgHash": l2l1_msg_hash,
-                    "payload": format_hex(log.data().data.as_ref()),
-                }),
-            });
-        } else if topic0 == message_sent_topic() && log.address() != INTEROP_CENTER_ADDRESS {
-            let decoded = decode_message_sent(log.data().data.clone())?;
-            let send_id = log
-                .topics()
-                .get(1)
-                .map(|topic| b256_to_hex(*topic))
-                .unwrap_or_default();
-            events.push(EventView {
-                name: "MessageSent".to_string(),
-                address: address_to_hex(log.address()),
-                data: json!({
-                    "sendId": send_id,
-                    "sender": format_hex(decoded.sender.as_ref()),
-                    "recipient": format_hex(decoded.recipient.as_ref()),
-                    "payload": format_hex(decoded.payload.as_ref()),
-                    "value": u256_to_string(decoded.value),
-                    "attributes": decoded.attributes.iter().map(|attr| format_hex(attr.as_ref())).collect::<Vec<_>>(),
-                }),
-            });
-        } else if topic0 == bundle_verified_topic() {
-            events.push(simple_bundle_event("BundleVerified", &log));
-        } else if topic0 == bundle_executed_topic() {
-            events.push(simple_bundle_event("BundleExecuted", &log));
-        } else if topic0 == bundle_unbundled_topic() {
-            events.push(simple_bundle_event("BundleUnbundled", &log));
-        } else if topic0 == call_processed_topic() {
-            let bundle_hash = log
-                .topics()
-                .get(1)
-                .map(|topic| b256_to_hex(*topic))
-                .unwrap_or_default();
-            let call_index = log
-                .topics()
-                .get(2)
-                .map(|topic| U256::from_be_slice(topic.as_slice()))
-                .map(u256_to_string)
-                .unwrap_or_default();
-            let status = decode_u8(log.data().data.clone())?;
-            events.push(EventView {
-                name: "CallProcessed".to_string(),
-                address: address_to_hex(log.address()),
-                data: json!({
-                    "bundleHash": bundle_hash,
-                    "callIndex": call_index,
-                    "status": status,
-                }),
-            });
-        } else if !args.interop_only {
+        let Some(decoded) = decode_interop_log(log, &addresses)? else {
             continue;
+        };
+        if let Some(sent) = decoded.bundle {
+            bundles.push(TxBundleView {
+                index: bundles.len(),
+                bundle_hash: sent.bundle_hash,
+                l2l1_msg_hash: sent.l2l1_msg_hash,
+                bundle: sent.view,
+            });
         }
+        events.push(decoded.event);
     }
 
+    let first = bundles.first();
     let output = TxShowOutput {
         tx_hash: format!("{tx_hash:#x}"),
-        bundle: bundle_view.clone(),
-        bundle_hash: bundle_hash.clone(),
-        l2l1_msg_hash: l2l1_msg_hash.clone(),
+        bundle: first.map(|sent| sent.bundle.clone()),
+        bundle_hash: first.map(|sent| sent.bundle_hash.clone()),
+        l2l1_msg_hash: first.map(|sent| sent.l2l1_msg_hash.clone()),
+        bundles: bundles.clone(),
         interop_events: events.clone(),
     };
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&output)?);
+    if emit(&output, config.json_output(), args.out.as_deref())? {
         return Ok(());
     }
 
     println!("tx: {:#x}", tx_hash);
-    if let Some(bundle_hash) = bundle_hash {
-        println!("bundleHash: {bundle_hash}");
-    }
-    if let Some(l2l1_msg_hash) = l2l1_msg_hash {
-        println!("l2l1MsgHash: {l2l1_msg_hash}");
-    }
-    if let Some(bundle) = bundle_view {
+    if bundles.len() > 1 {
         println!(
-            "bundle: sourceChainId={} destinationChainId={} calls={}",
-            bundle.source_chain_id,
-            bundle.destination_chain_id,
-            bundle.calls.len()
+            "{} bundles sent (select one with --bundle-index)",
+            bundles.len()
         );
-        for (idx, call) in bundle.calls.iter().enumerate() {
-            println!(
-                "  call[{idx}] to={} from={} value={} data_len={}",
-                call.to,
-                call.from,
-                call.value,
-                (call.data.len().saturating_sub(2)) / 2
-            );
+    }
+    for sent in &bundles {
+        if bundles.len() > 1 {
+            println!("[{}]", sent.index);
         }
-        println!(
-            "bundleAttributes: executionAddress={} unbundlerAddress={}",
-            bundle.bundle_attributes.execution_address, bundle.bundle_attributes.unbundler_address
-        );
+        println!("bundleHash: {}", sent.bundle_hash);
+        println!("l2l1MsgHash: {}", sent.l2l1_msg_hash);
+        print_bundle_tree(&sent.bundle);
     }
     if !events.is_empty() {
         println!("events:");
@@ -168,6 +110,156 @@ pub async fn run(args: TxShowArgs, config: Config, _addresses: AddressBook) -> R
     Ok(())
 }
 
+/// Print a bundle as a tree: route, salt, attributes, and one line per call.
+pub(crate) fn print_bundle_tree(bundle: &InteropBundleView) {
+    println!(
+        "bundle: {} → {} ({} calls)",
+        bundle.source_chain_id,
+        bundle.destination_chain_id,
+        bundle.calls.len()
+    );
+    println!("├─ salt: {}", bundle.interop_bundle_salt);
+    println!(
+        "├─ executionAddress: {}",
+        bundle.bundle_attributes.execution_address
+    );
+    println!(
+        "├─ unbundlerAddress: {}",
+        bundle.bundle_attributes.unbundler_address
+    );
+    println!("└─ calls");
+    for (idx, call) in bundle.calls.iter().enumerate() {
+        let branch = if idx + 1 == bundle.calls.len() {
+            "└─"
+        } else {
+            "├─"
+        };
+        println!(
+            "   {branch} [{idx}] to={} from={} value={} data_len={}",
+            call.to,
+            call.from,
+            call.value,
+            (call.data.len().saturating_sub(2)) / 2
+        );
+    }
+}
+
+/// An interop event decoded from a single log.
+pub(crate) struct DecodedLog {
+    pub event: EventView,
+    /// Bundle details, set only for InteropBundleSent logs.
+    pub bundle: Option<SentBundle>,
+}
+
+pub(crate) struct SentBundle {
+    pub view: InteropBundleView,
+    pub bundle_hash: String,
+    pub l2l1_msg_hash: String,
+}
+
+/// Decode a log into an interop event view.
+///
+/// Returns None for logs that are not interop events. `InteropBundleSent`,
+/// `L1MessageSent` and `MessageSent` are only matched against the interop
+/// center and L1 messenger of `addresses`.
+pub(crate) fn decode_interop_log(
+    log: &alloy_rpc_types::Log,
+    addresses: &AddressBook,
+) -> Result<Option<DecodedLog>> {
+    let Some(topic0) = log.topics().first().cloned() else {
+        return Ok(None);
+    };
+    let mut bundle = None;
+    let event = if topic0 == interop_bundle_sent_topic()
+        && log.address() == addresses.interop_center
+    {
+        let (l2l1_hash, interop_hash, decoded) =
+            decode_interop_bundle_sent(log.data().data.clone())?;
+        let bundle_json = crate::abi::bundle_view(&decoded);
+        let event = EventView {
+            name: "InteropBundleSent".to_string(),
+            address: address_to_hex(log.address()),
+            data: serde_json::to_value(&bundle_json)?,
+        };
+        bundle = Some(SentBundle {
+            view: bundle_json,
+            bundle_hash: b256_to_hex(interop_hash),
+            l2l1_msg_hash: b256_to_hex(l2l1_hash),
+        });
+        event
+    } else if topic0 == l1_message_sent_topic() && log.address() == addresses.l1_messenger {
+        let sender = log
+            .topics()
+            .get(1)
+            .map(|topic| address_to_hex(Address::from_slice(&topic.as_slice()[12..])))
+            .unwrap_or_default();
+        let l2l1_msg_hash = log
+            .topics()
+            .get(2)
+            .map(|topic| b256_to_hex(*topic))
+            .unwrap_or_default();
+        EventView {
+            name: "L1MessageSent".to_string(),
+            address: sender.clone(),
+            data: json!({
+                "sender": sender,
+                "l2l1MsgHash": l2l1_msg_hash,
+                "payload": format_hex(log.data().data.as_ref()),
+            }),
+        }
+    } else if topic0 == message_sent_topic() && log.address() == addresses.interop_center {
+        let decoded = decode_message_sent(log.data().data.clone())?;
+        let send_id = log
+            .topics()
+            .get(1)
+            .map(|topic| b256_to_hex(*topic))
+            .unwrap_or_default();
+        EventView {
+            name: "MessageSent".to_string(),
+            address: address_to_hex(log.address()),
+            data: json!({
+                "sendId": send_id,
+                "sender": format_hex(decoded.sender.as_ref()),
+                "recipient": format_hex(decoded.recipient.as_ref()),
+                "payload": format_hex(decoded.payload.as_ref()),
+                "value": u256_to_string(decoded.value),
+                "attributes": decoded.attributes.iter().map(|attr| format_hex(attr.as_ref())).collect::<Vec<_>>(),
+            }),
+        }
+    } else if topic0 == bundle_verified_topic() {
+        simple_bundle_event("BundleVerified", log)
+    } else if topic0 == bundle_executed_topic() {
+        simple_bundle_event("BundleExecuted", log)
+    } else if topic0 == bundle_unbundled_topic() {
+        simple_bundle_event("BundleUnbundled", log)
+    } else if topic0 == call_processed_topic() {
+        let bundle_hash = log
+            .topics()
+            .get(1)
+            .map(|topic| b256_to_hex(*topic))
+            .unwrap_or_default();
+        let call_index = log
+            .topics()
+            .get(2)
+            .map(|topic| U256::from_be_slice(topic.as_slice()))
+            .map(u256_to_string)
+            .unwrap_or_default();
+        let status = decode_u8(log.data().data.clone())?;
+        EventView {
+            name: "CallProcessed".to_string(),
+            address: address_to_hex(log.address()),
+            data: json!({
+                "bundleHash": bundle_hash,
+                "callIndex": call_index,
+                "status": status,
+            }),
+        }
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(DecodedLog { event, bundle }))
+}
+
 /// Render a minimal bundle event for verified/executed/unbundled logs.
 fn simple_bundle_event(name: &str, log: &alloy_rpc_types::Log) -> EventView {
     let bundle_hash = log
@@ -181,3 +273,47 @@ fn simple_bundle_event(name: &str, log: &alloy_rpc_types::Log) -> EventView {
         data: json!({ "bundleHash": bundle_hash }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::MessageSentData;
+    use alloy_primitives::{Bytes, LogData};
+    use alloy_sol_types::SolValue;
+
+    fn message_sent_log(emitter: Address) -> alloy_rpc_types::Log {
+        let data = MessageSentData {
+            sender: Bytes::from_static(&[0x01]),
+            recipient: Bytes::from_static(&[0x02]),
+            payload: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+            value: U256::from(7u64),
+            attributes: Vec::new(),
+        };
+        alloy_rpc_types::Log {
+            inner: alloy_primitives::Log {
+                address: emitter,
+                data: LogData::new_unchecked(
+                    vec![message_sent_topic(), B256::repeat_byte(0x11)],
+                    data.abi_encode_params().into(),
+                ),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn message_sent_is_decoded_only_from_the_interop_center() {
+        let addresses =
+            AddressBook::from_config_and_flags(&Config::default(), None, None, None).unwrap();
+
+        let decoded = decode_interop_log(&message_sent_log(addresses.interop_center), &addresses)
+            .unwrap()
+            .expect("MessageSent from the interop center");
+        assert_eq!(decoded.event.name, "MessageSent");
+        assert_eq!(decoded.event.data["payload"], "0xdeadbeef");
+        assert_eq!(decoded.event.data["value"], "7");
+
+        let spoofed = message_sent_log(Address::repeat_byte(0x42));
+        assert!(decode_interop_log(&spoofed, &addresses).unwrap().is_none());
+    }
+}
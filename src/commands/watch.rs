@@ -1,157 +1,362 @@
-use crate::abi::{decode_bundle_status, encode_bundle_status_call, encode_interop_roots_call};
+use crate::abi::{
+    decode_bundle, decode_bundle_status, decode_call_status, encode_bundle_status_call,
+    encode_call_status_call, encode_interop_roots_call, interop_bundle_hash,
+};
 use crate::cli::WatchArgs;
+use crate::commands::bundle_decode::load_hex_or_path;
 use crate::config::Config;
 use crate::rpc::{
-    eth_call, get_finalized_block_number, get_log_proof, get_transaction_receipt, RpcClient,
+    eth_call, get_finalized_block_number, get_log_proof, get_transaction_receipt,
+    wait_for_confirmations, RpcClient, WaitLimit,
 };
 use crate::types::{parse_b256, AddressBook};
 use alloy_primitives::{B256, U256};
-use alloy_provider::Provider;
 use anyhow::{anyhow, Context, Result};
 use serde::Serialize;
-use std::time::Duration;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct WatchEvent {
+    /// Position in the stream, counted across all watched transactions.
+    seq: u64,
+    /// Unix time in milliseconds when the event was seen.
+    timestamp: u64,
+    tx: String,
     event: String,
     details: serde_json::Value,
 }
 
-/// Watch a transaction until proof/root/bundle status updates arrive.
+/// Per-transaction watch settings shared by every concurrent watcher.
+#[derive(Clone)]
+struct Watcher {
+    source_client: RpcClient,
+    dest_client: RpcClient,
+    addresses: AddressBook,
+    msg_index: u32,
+    bundle: Option<WatchedBundle>,
+    until: Option<WatchTarget>,
+    json: bool,
+    tagged: bool,
+    limit: WaitLimit,
+    poll: Duration,
+    /// Blocks the source receipt must be buried under before watching.
+    confirmations: u64,
+    /// Last `seq` handed out, shared by every watcher.
+    seq: Arc<AtomicU64>,
+}
+
+/// Bundle given with --bundle, whose calls get per-call status events.
+#[derive(Clone, Copy)]
+struct WatchedBundle {
+    hash: B256,
+    calls: usize,
+}
+
+#[derive(Clone, Copy)]
+enum WatchTarget {
+    Verified,
+    Executed,
+}
+
+/// Watch transactions until proof/root/bundle status updates arrive.
 ///
-/// Emits events as finalization, log proofs, roots, and bundle status change.
+/// Every `--tx` is polled concurrently; with `--until` the command returns
+/// once all of them reached the target state.
+#[tracing::instrument(name = "debug watch", skip_all, fields(chain_src = args.chain_src.as_deref(), chain_dest = args.chain_dest.as_deref()))]
 pub async fn run(args: WatchArgs, config: Config, addresses: AddressBook) -> Result<()> {
+    let until = match args.until.as_deref() {
+        None => None,
+        Some("verified") => Some(WatchTarget::Verified),
+        Some("executed") => Some(WatchTarget::Executed),
+        Some(other) => {
+            anyhow::bail!("invalid --until value {other} (expected verified or executed)")
+        }
+    };
+    let mut tx_hashes = args
+        .tx
+        .iter()
+        .map(|value| parse_b256(value))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(path) = args.tx_file.as_deref() {
+        tx_hashes.extend(load_tx_file(path)?);
+    }
+    let mut seen = HashSet::new();
+    tx_hashes.retain(|tx_hash| seen.insert(*tx_hash));
+    if tx_hashes.is_empty() {
+        anyhow::bail!("no transactions to watch (set --tx or --tx-file)");
+    }
+
+    let bundle = match args.bundle.as_deref() {
+        Some(value) => {
+            let encoded = load_hex_or_path(value)?;
+            let decoded = decode_bundle(&encoded).context("failed to decode bundle")?;
+            Some(WatchedBundle {
+                hash: interop_bundle_hash(decoded.sourceChainId, &encoded),
+                calls: decoded.calls.len(),
+            })
+        }
+        None => None,
+    };
+
     let src_rpc = config.resolve_rpc(args.rpc_src.as_deref(), args.chain_src.as_deref())?;
     let dest_rpc = config.resolve_rpc(args.rpc_dest.as_deref(), args.chain_dest.as_deref())?;
-    let source_client = RpcClient::new(&src_rpc.url).await?;
-    let dest_client = RpcClient::new(&dest_rpc.url).await?;
-
-    let tx_hash = parse_b256(&args.tx)?;
-    let receipt = get_transaction_receipt(&source_client, tx_hash).await?;
-    let block_number = receipt
-        .block_number
-        .ok_or_else(|| anyhow!("missing receipt block number"))?;
-
-    let timeout = Duration::from_millis(args.timeout_ms.unwrap_or(300_000));
-    let poll = Duration::from_millis(args.poll_ms.unwrap_or(1_000));
-    let start = tokio::time::Instant::now();
-
-    let mut finalized = false;
-    let mut log_proof = None;
-    let mut root_available = false;
-    let bundle_hash = extract_bundle_hash(&receipt)?;
-    let mut bundle_status: Option<u8> = None;
-
-    loop {
-        if !finalized {
-            let finalized_block = get_finalized_block_number(&source_client).await;
-            if let Ok(finalized_block) = finalized_block {
-                if finalized_block >= block_number {
-                    finalized = true;
-                    emit_event(
-                        args.json,
-                        "finalized",
-                        serde_json::json!({ "block": finalized_block }),
+    let watcher = Watcher {
+        source_client: RpcClient::for_rpc(&src_rpc, config.rpc_timeout, &config.rpc_headers)
+            .await?,
+        dest_client: RpcClient::for_rpc(&dest_rpc, config.rpc_timeout, &config.rpc_headers).await?,
+        addresses,
+        msg_index: args.msg_index,
+        bundle,
+        until,
+        json: config.json_output(),
+        tagged: tx_hashes.len() > 1,
+        limit: config.wait_limit(args.timeout_ms, args.max_wait_blocks),
+        poll: config.poll_interval(args.poll_ms),
+        confirmations: config.confirmations(args.confirmations),
+        seq: Arc::new(AtomicU64::new(0)),
+    };
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for tx_hash in tx_hashes {
+        let watcher = watcher.clone();
+        tasks.spawn(async move {
+            watcher
+                .watch(tx_hash)
+                .await
+                .with_context(|| format!("watch {tx_hash:#x}"))
+        });
+    }
+    while let Some(result) = tasks.join_next().await {
+        result.context("watch task panicked")??;
+    }
+    Ok(())
+}
+
+impl Watcher {
+    /// Poll a single transaction until it reaches the `--until` state.
+    ///
+    /// The receipt, bundle hash and source chain ID are read once; each poll
+    /// then only queries the stages that can still change. Source-side stages
+    /// stop once the destination has the bundle, and terminal bundle or call
+    /// statuses are not polled again.
+    async fn watch(&self, tx_hash: B256) -> Result<()> {
+        let receipt = get_transaction_receipt(&self.source_client, tx_hash).await?;
+        let block_number = receipt
+            .block_number
+            .ok_or_else(|| anyhow!("missing receipt block number"))?;
+        let source_chain_id = self.source_client.chain_id().await?;
+        wait_for_confirmations(
+            &self.source_client,
+            &receipt,
+            self.confirmations,
+            self.limit,
+            self.poll,
+        )
+        .await?;
+
+        let mut clock = self.limit.start();
+
+        let mut finalized = false;
+        let mut log_proof = None;
+        let mut root_available = false;
+        let bundle_hash =
+            extract_bundle_hash(&receipt, tx_hash, self.bundle.map(|bundle| bundle.hash))?;
+        let mut bundle_status: Option<u8> = None;
+        let call_count = match self.bundle {
+            Some(bundle) if bundle_hash == Some(bundle.hash) => bundle.calls,
+            Some(bundle) => {
+                if !self.tagged {
+                    eprintln!(
+                        "warning: --bundle hash {:#x} does not match the transaction's bundle; skipping call status",
+                        bundle.hash
                     );
                 }
+                0
             }
-        }
+            None => 0,
+        };
+        let mut call_statuses: Vec<Option<u8>> = vec![None; call_count];
+
+        loop {
+            // Verified or later implies the root was imported, so the source
+            // side has nothing left to report.
+            let delivered = matches!(bundle_status, Some(1..=3));
 
-        if log_proof.is_none() {
-            if let Some(proof) = get_log_proof(&source_client, tx_hash, args.msg_index).await? {
-                emit_event(
-                    args.json,
-                    "log_proof",
-                    serde_json::json!({
-                        "batch": proof.batch_number,
-                        "id": proof.id,
-                        "root": proof.root,
-                    }),
-                );
-                log_proof = Some(proof);
+            if !finalized && !delivered {
+                let finalized_block = get_finalized_block_number(&self.source_client).await;
+                if let Ok(finalized_block) = finalized_block {
+                    if finalized_block >= block_number {
+                        finalized = true;
+                        self.emit_event(
+                            tx_hash,
+                            "finalized",
+                            serde_json::json!({ "block": finalized_block }),
+                        );
+                    }
+                }
             }
-        }
 
-        if let Some(proof) = log_proof.as_ref() {
-            if !root_available {
-                let root = fetch_root(
-                    &dest_client,
-                    addresses.interop_root_storage,
-                    proof.batch_number,
-                    &proof.root,
-                    &source_client,
-                )
-                .await?;
-                if root {
-                    root_available = true;
-                    emit_event(
-                        args.json,
-                        "root_available",
-                        serde_json::json!({ "root": proof.root, "batch": proof.batch_number }),
+            if log_proof.is_none() && !delivered {
+                if let Some(proof) =
+                    get_log_proof(&self.source_client, tx_hash, self.msg_index).await?
+                {
+                    self.emit_event(
+                        tx_hash,
+                        "log_proof",
+                        serde_json::json!({
+                            "batch": proof.batch_number,
+                            "id": proof.id,
+                            "root": proof.root,
+                        }),
                     );
+                    log_proof = Some(proof);
                 }
             }
-        }
 
-        if let Some(hash) = bundle_hash {
-            let status = fetch_bundle_status(&dest_client, addresses.interop_handler, hash).await?;
-            if bundle_status != Some(status) {
-                bundle_status = Some(status);
-                emit_event(
-                    args.json,
-                    "bundle_status",
-                    serde_json::json!({ "bundleHash": format!("{hash:#x}"), "status": bundle_status_string(status) }),
-                );
+            if let Some(proof) = log_proof.as_ref() {
+                if !root_available && !delivered {
+                    let root = fetch_root(
+                        &self.dest_client,
+                        self.addresses.interop_root_storage,
+                        source_chain_id,
+                        proof.batch_number,
+                        &proof.root,
+                    )
+                    .await?;
+                    if root {
+                        root_available = true;
+                        self.emit_event(
+                            tx_hash,
+                            "root_available",
+                            serde_json::json!({ "root": proof.root, "batch": proof.batch_number }),
+                        );
+                    }
+                }
             }
-        }
 
-        if let Some(target) = args.until.as_deref() {
-            if target == "verified" {
-                if matches!(bundle_status, Some(1 | 2)) {
-                    return Ok(());
+            if let Some(hash) = bundle_hash {
+                // FullyExecuted and Unbundled are final.
+                if !matches!(bundle_status, Some(2 | 3)) {
+                    let status = fetch_bundle_status(
+                        &self.dest_client,
+                        self.addresses.interop_handler,
+                        hash,
+                    )
+                    .await?;
+                    if bundle_status != Some(status) {
+                        bundle_status = Some(status);
+                        self.emit_event(
+                            tx_hash,
+                            "bundle_status",
+                            serde_json::json!({ "bundleHash": format!("{hash:#x}"), "status": bundle_status_string(status) }),
+                        );
+                    }
                 }
-            } else if target == "executed" {
-                if matches!(bundle_status, Some(2)) {
-                    return Ok(());
+
+                for (index, last) in call_statuses.iter_mut().enumerate() {
+                    // Executed and Cancelled are final.
+                    if matches!(*last, Some(1 | 2)) {
+                        continue;
+                    }
+                    let status = fetch_call_status(
+                        &self.dest_client,
+                        self.addresses.interop_handler,
+                        hash,
+                        index,
+                    )
+                    .await?;
+                    if *last != Some(status) {
+                        *last = Some(status);
+                        self.emit_event(
+                            tx_hash,
+                            "call_status",
+                            serde_json::json!({
+                                "bundleHash": format!("{hash:#x}"),
+                                "callIndex": index,
+                                "status": call_status_string(status),
+                            }),
+                        );
+                    }
                 }
-            } else {
-                anyhow::bail!("invalid --until value {target} (expected verified or executed)");
             }
+
+            let reached = match self.until {
+                Some(WatchTarget::Verified) => matches!(bundle_status, Some(1 | 2)),
+                Some(WatchTarget::Executed) => matches!(bundle_status, Some(2)),
+                None => false,
+            };
+            if reached {
+                return Ok(());
+            }
+
+            if clock.expired(&self.source_client).await {
+                anyhow::bail!("watch timeout reached");
+            }
+            tokio::time::sleep(self.poll).await;
         }
+    }
 
-        if start.elapsed() > timeout {
-            anyhow::bail!("watch timeout reached");
+    /// Emit a watch event as a JSON line or human-readable text.
+    ///
+    /// JSON events are compact, one per line (NDJSON), with a `seq` and
+    /// `timestamp`; text events are prefixed with the tx hash when several
+    /// are watched.
+    fn emit_event(&self, tx_hash: B256, name: &str, details: serde_json::Value) {
+        if self.json {
+            // Holding stdout while numbering keeps `seq` in output order.
+            let mut stdout = std::io::stdout().lock();
+            let event = WatchEvent {
+                seq: self.seq.fetch_add(1, Ordering::Relaxed) + 1,
+                timestamp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_millis() as u64)
+                    .unwrap_or_default(),
+                tx: format!("{tx_hash:#x}"),
+                event: name.to_string(),
+                details,
+            };
+            let _ = writeln!(
+                stdout,
+                "{}",
+                serde_json::to_string(&event).unwrap_or_default()
+            );
+        } else if self.tagged {
+            println!("[{tx_hash:#x}] {name}: {details}");
+        } else {
+            println!("{name}: {details}");
         }
-        tokio::time::sleep(poll).await;
     }
 }
 
-/// Emit a watch event as JSON or human-readable text.
-fn emit_event(json: bool, name: &str, details: serde_json::Value) {
-    if json {
-        let event = WatchEvent {
-            event: name.to_string(),
-            details,
-        };
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&event).unwrap_or_default()
-        );
-    } else {
-        println!("{name}: {details}");
-    }
+/// Read transaction hashes from a file, one per line.
+///
+/// Blank lines and lines starting with `#` are skipped.
+pub(crate) fn load_tx_file(path: &Path) -> Result<Vec<B256>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_b256)
+        .collect()
 }
 
 /// Check if the expected root has been published on the destination chain.
 async fn fetch_root(
     dest_client: &RpcClient,
     root_storage: alloy_primitives::Address,
+    source_chain_id: u64,
     batch_number: u64,
     expected_root: &str,
-    source_client: &RpcClient,
 ) -> Result<bool> {
-    let source_chain_id = source_client.provider.get_chain_id().await?;
     let data = encode_interop_roots_call(U256::from(source_chain_id), U256::from(batch_number));
     let result = eth_call(dest_client, root_storage, data).await?;
     let root = crate::abi::decode_bytes32(result)?;
@@ -169,16 +374,42 @@ async fn fetch_bundle_status(
     decode_bundle_status(data)
 }
 
-/// Extract a bundle hash from an InteropBundleSent log, if present.
-fn extract_bundle_hash(receipt: &alloy_rpc_types::TransactionReceipt) -> Result<Option<B256>> {
-    for log in receipt.logs() {
-        if log.topics().first().copied() == Some(crate::abi::interop_bundle_sent_topic()) {
-            let (_, hash, _) = crate::abi::decode_interop_bundle_sent(log.data().data.clone())
-                .context("failed to decode InteropBundleSent")?;
-            return Ok(Some(hash));
-        }
+/// Fetch the status of one call in a bundle from the handler contract.
+async fn fetch_call_status(
+    client: &RpcClient,
+    handler: alloy_primitives::Address,
+    bundle_hash: B256,
+    index: usize,
+) -> Result<u8> {
+    let call = encode_call_status_call(bundle_hash, U256::from(index));
+    let data = eth_call(client, handler, call).await?;
+    decode_call_status(data)
+}
+
+/// Extract a bundle hash from the InteropBundleSent logs, if present.
+///
+/// Prefers `wanted` (the hash of `--bundle`) when the transaction sends it, and
+/// otherwise takes the first bundle, warning when there are several.
+fn extract_bundle_hash(
+    receipt: &alloy_rpc_types::TransactionReceipt,
+    tx_hash: B256,
+    wanted: Option<B256>,
+) -> Result<Option<B256>> {
+    let hashes: Vec<B256> = crate::interop::sent_bundles(receipt)
+        .context("failed to decode InteropBundleSent")?
+        .into_iter()
+        .map(|(_, hash, _)| hash)
+        .collect();
+    if let Some(wanted) = wanted.filter(|wanted| hashes.contains(wanted)) {
+        return Ok(Some(wanted));
     }
-    Ok(None)
+    if hashes.len() > 1 {
+        eprintln!(
+            "warning: {tx_hash:#x} sends {} bundles; watching the first (pass another one with --bundle to watch it)",
+            hashes.len()
+        );
+    }
+    Ok(hashes.first().copied())
 }
 
 /// Render a bundle status enum into a readable string.
@@ -191,3 +422,13 @@ fn bundle_status_string(value: u8) -> &'static str {
         _ => "Unknown",
     }
 }
+
+/// Render a call status enum into a readable string.
+fn call_status_string(value: u8) -> &'static str {
+    match value {
+        0 => "Unprocessed",
+        1 => "Executed",
+        2 => "Cancelled",
+        _ => "Unknown",
+    }
+}
@@ -1,8 +1,17 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::CastInteropError;
+use crate::rpc::WaitLimit;
+use crate::types::{
+    BUNDLE_IDENTIFIER, DEFAULT_POLL_MS, DEFAULT_RPC_TIMEOUT_MS, DEFAULT_WAIT_TIMEOUT_MS,
+};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
@@ -11,8 +20,40 @@ pub struct Config {
     pub addresses: Option<AddressConfig>,
     pub abi: Option<AbiConfig>,
     pub signer: Option<SignerConfig>,
+    /// Default output format; `--output` (or the deprecated `--json`) wins.
+    pub output: Option<OutputFormat>,
+    pub defaults: Option<DefaultsConfig>,
+    pub interop: Option<InteropConfig>,
+    pub profiles: Option<BTreeMap<String, ProfileConfig>>,
+    /// Drop progress lines and hints, keeping only results, set from the
+    /// global `--quiet`.
+    #[serde(skip)]
+    pub quiet: bool,
+    /// Broadcast without the confirmation prompt, set from the global `--yes`.
+    #[serde(skip)]
+    pub yes: bool,
     #[serde(skip)]
     pub path: PathBuf,
+    /// Profile selected with `--profile`, already applied to the fields above.
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+    /// Per-request RPC timeout, set from `--rpc-timeout-ms`.
+    #[serde(skip)]
+    pub rpc_timeout: Duration,
+    /// Extra RPC request headers, set from `--rpc-header`.
+    #[serde(skip)]
+    pub rpc_headers: HeaderMap,
+    /// Byte prepended to an encoded bundle in its L2→L1 message, from
+    /// `--bundle-prefix`, else `interop.bundle_identifier`, else 0x01.
+    #[serde(skip)]
+    pub bundle_identifier: u8,
+    /// Simulate instead of sending, set from the global `--dry-run`.
+    #[serde(skip)]
+    pub dry_run: bool,
+    /// ASCII `[ok]`/`[warn]`/`[fail]` instead of emoji in check reports, from
+    /// `--ascii` or stdout not being a terminal.
+    #[serde(skip)]
+    pub plain_icons: bool,
 }
 
 impl Default for Config {
@@ -23,11 +64,32 @@ impl Default for Config {
             addresses: None,
             abi: None,
             signer: None,
+            output: None,
+            defaults: None,
+            interop: None,
+            profiles: None,
+            quiet: false,
+            yes: false,
             path: PathBuf::new(),
+            active_profile: None,
+            rpc_timeout: Duration::from_millis(DEFAULT_RPC_TIMEOUT_MS),
+            rpc_headers: HeaderMap::new(),
+            bundle_identifier: BUNDLE_IDENTIFIER,
+            dry_run: false,
+            plain_icons: false,
         }
     }
 }
 
+/// How commands print their results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct RpcConfig {
     pub default: Option<String>,
@@ -40,6 +102,19 @@ pub struct ChainConfig {
     pub rpc: String,
     #[serde(rename = "chainId")]
     pub chain_id: Option<u64>,
+    /// Endpoints tried in order when `rpc` cannot be reached.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_rpcs: Vec<String>,
+}
+
+impl ChainConfig {
+    /// The primary and fallback RPC URLs with `${VAR}` references expanded.
+    pub fn urls(&self) -> Result<Vec<String>> {
+        std::iter::once(&self.rpc)
+            .chain(&self.fallback_rpcs)
+            .map(|url| expand_env(url))
+            .collect()
+    }
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -47,6 +122,9 @@ pub struct AddressConfig {
     pub interop_center: Option<String>,
     pub interop_handler: Option<String>,
     pub interop_root_storage: Option<String>,
+    pub native_token_vault: Option<String>,
+    pub asset_router: Option<String>,
+    pub l1_messenger: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -54,41 +132,155 @@ pub struct AbiConfig {
     pub dir: Option<PathBuf>,
 }
 
+/// Protocol constants that may change between releases.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct InteropConfig {
+    /// Bundle identifier byte as hex, e.g. "0x01".
+    pub bundle_identifier: Option<String>,
+}
+
+/// Wait settings used when `--timeout-ms` / `--poll-ms` are not passed.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct DefaultsConfig {
+    pub timeout_ms: Option<u64>,
+    pub poll_ms: Option<u64>,
+    pub confirmations: Option<u64>,
+}
+
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct SignerConfig {
     pub private_key_env: Option<String>,
 }
 
+/// A named deployment (e.g. mainnet, testnet, local) selected with `--profile`.
+///
+/// `chains` replaces the top-level chain map; `addresses` and `signer` are
+/// merged field by field, so anything the profile omits falls back to the
+/// top-level config.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ProfileConfig {
+    pub chains: Option<BTreeMap<String, ChainConfig>>,
+    pub addresses: Option<AddressConfig>,
+    pub signer: Option<SignerConfig>,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct ResolvedRpc {
     pub url: String,
     pub alias: Option<String>,
     pub chain_id: Option<u64>,
+    pub fallbacks: Vec<String>,
+}
+
+impl ResolvedRpc {
+    /// Resolve a configured chain, expanding `${VAR}` references in its URLs.
+    fn from_chain(alias: &str, cfg: &ChainConfig) -> Result<Self> {
+        let urls = cfg.urls().with_context(|| format!("chain {alias}"))?;
+        let (url, fallbacks) = urls.split_first().expect("primary rpc");
+        Ok(Self {
+            url: url.clone(),
+            alias: Some(alias.to_string()),
+            chain_id: cfg.chain_id,
+            fallbacks: fallbacks.to_vec(),
+        })
+    }
+
+    /// All endpoints for this chain, primary first.
+    pub fn urls(&self) -> Vec<String> {
+        std::iter::once(self.url.clone())
+            .chain(self.fallbacks.iter().cloned())
+            .collect()
+    }
 }
 
 impl Config {
-    pub fn load(path: Option<&Path>) -> Result<Self> {
+    pub fn load(path: Option<&Path>, profile: Option<&str>) -> Result<Self> {
         let path = match path {
             Some(path) => path.to_path_buf(),
             None => default_config_path(),
         };
 
-        if !path.exists() {
-            let mut config = Self::default();
-            config.path = path;
-            return Ok(config);
-        }
-
-        let contents = fs::read_to_string(&path)
-            .with_context(|| format!("failed to read config {}", path.display()))?;
-        let mut config: Config = toml::from_str(&contents)
-            .with_context(|| format!("failed to parse config {}", path.display()))?;
+        let mut config = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read config {}", path.display()))?;
+            toml::from_str(&contents)
+                .with_context(|| format!("failed to parse config {}", path.display()))?
+        } else {
+            Self::default()
+        };
         config.path = path;
+        if let Some(profile) = profile {
+            config.apply_profile(profile)?;
+        }
+        config.bundle_identifier = match config
+            .interop
+            .as_ref()
+            .and_then(|interop| interop.bundle_identifier.as_deref())
+        {
+            Some(value) => parse_bundle_identifier(value).with_context(|| {
+                format!(
+                    "invalid interop.bundle_identifier in {}",
+                    config.path.display()
+                )
+            })?,
+            None => BUNDLE_IDENTIFIER,
+        };
         Ok(config)
     }
 
+    /// Overlay the named profile onto the top-level fields.
+    fn apply_profile(&mut self, name: &str) -> Result<()> {
+        let Some(profile) = self
+            .profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(name))
+            .cloned()
+        else {
+            let known = self
+                .profiles
+                .as_ref()
+                .map(|profiles| profiles.keys().cloned().collect::<Vec<_>>().join(", "))
+                .unwrap_or_default();
+            anyhow::bail!(
+                "unknown profile {name} in {} (known: {})",
+                self.path.display(),
+                if known.is_empty() { "none" } else { &known }
+            );
+        };
+
+        if profile.chains.is_some() {
+            self.chains = profile.chains;
+        }
+        if let Some(addresses) = profile.addresses {
+            let base = self.addresses.take().unwrap_or_default();
+            self.addresses = Some(AddressConfig {
+                interop_center: addresses.interop_center.or(base.interop_center),
+                interop_handler: addresses.interop_handler.or(base.interop_handler),
+                interop_root_storage: addresses.interop_root_storage.or(base.interop_root_storage),
+                native_token_vault: addresses.native_token_vault.or(base.native_token_vault),
+                asset_router: addresses.asset_router.or(base.asset_router),
+                l1_messenger: addresses.l1_messenger.or(base.l1_messenger),
+            });
+        }
+        if let Some(signer) = profile.signer {
+            let base = self.signer.take().unwrap_or_default();
+            self.signer = Some(SignerConfig {
+                private_key_env: signer.private_key_env.or(base.private_key_env),
+            });
+        }
+        self.active_profile = Some(name.to_string());
+        Ok(())
+    }
+
     pub fn save(&self) -> Result<()> {
+        // The top-level fields hold the merged profile view, so writing them
+        // back would copy the profile into the base config.
+        if let Some(profile) = self.active_profile.as_deref() {
+            anyhow::bail!(
+                "cannot update the config file while --profile {profile} is active; edit [profiles.{profile}] directly"
+            );
+        }
         let path = if self.path.as_os_str().is_empty() {
             default_config_path()
         } else {
@@ -102,6 +294,45 @@ impl Config {
         Ok(())
     }
 
+    /// Whether commands should print JSON instead of text.
+    pub fn json_output(&self) -> bool {
+        self.output == Some(OutputFormat::Json)
+    }
+
+    /// How long proof/root/watch waits run: `--timeout-ms`, then
+    /// `defaults.timeout_ms`, then the built-in default.
+    pub fn wait_timeout(&self, flag: Option<u64>) -> Duration {
+        let configured = self.defaults.as_ref().and_then(|cfg| cfg.timeout_ms);
+        Duration::from_millis(flag.or(configured).unwrap_or(DEFAULT_WAIT_TIMEOUT_MS))
+    }
+
+    /// Limit for waits. Without `--max-wait-blocks` this is the usual
+    /// timeout; with it, only an explicit `--timeout-ms` still applies, so the
+    /// default timeout does not cut a block-count wait short.
+    pub fn wait_limit(&self, timeout_flag: Option<u64>, max_blocks: Option<u64>) -> WaitLimit {
+        match max_blocks {
+            None => WaitLimit::from_timeout(self.wait_timeout(timeout_flag)),
+            Some(blocks) => WaitLimit {
+                timeout: timeout_flag.map(Duration::from_millis),
+                max_blocks: Some(blocks),
+            },
+        }
+    }
+
+    /// Polling interval for waits: `--poll-ms`, then `defaults.poll_ms`,
+    /// then the built-in default.
+    pub fn poll_interval(&self, flag: Option<u64>) -> Duration {
+        let configured = self.defaults.as_ref().and_then(|cfg| cfg.poll_ms);
+        Duration::from_millis(flag.or(configured).unwrap_or(DEFAULT_POLL_MS))
+    }
+
+    /// Blocks a source receipt must be buried under before it is used:
+    /// `--confirmations`, then `defaults.confirmations`, then 0.
+    pub fn confirmations(&self, flag: Option<u64>) -> u64 {
+        let configured = self.defaults.as_ref().and_then(|cfg| cfg.confirmations);
+        flag.or(configured).unwrap_or(0)
+    }
+
     pub fn abi_dir(&self) -> PathBuf {
         if let Some(abi) = &self.abi {
             if let Some(dir) = &abi.dir {
@@ -118,9 +349,26 @@ impl Config {
             .unwrap_or_else(|| "PRIVATE_KEY".to_string())
     }
 
+    /// Pick the RPC for `--rpc`/`--chain`, falling back to the default chain.
+    ///
+    /// The chosen alias is logged at debug level inside the command's span;
+    /// URLs are not, since they may carry API keys.
     pub fn resolve_rpc(&self, rpc: Option<&str>, chain: Option<&str>) -> Result<ResolvedRpc> {
+        let resolved = self.lookup_rpc(rpc, chain)?;
+        tracing::debug!(
+            chain = resolved.alias.as_deref().unwrap_or("--rpc"),
+            chain_id = resolved.chain_id,
+            fallbacks = resolved.fallbacks.len(),
+            "resolved rpc"
+        );
+        Ok(resolved)
+    }
+
+    fn lookup_rpc(&self, rpc: Option<&str>, chain: Option<&str>) -> Result<ResolvedRpc> {
         if rpc.is_some() && chain.is_some() {
-            anyhow::bail!("cannot set both --rpc and --chain");
+            return Err(
+                CastInteropError::Config("cannot set both --rpc and --chain".to_string()).into(),
+            );
         }
 
         if let Some(rpc) = rpc {
@@ -128,16 +376,13 @@ impl Config {
                 url: rpc.to_string(),
                 alias: None,
                 chain_id: None,
+                fallbacks: Vec::new(),
             });
         }
 
         if let Some(alias) = chain {
             if let Some(chain_cfg) = self.chains.as_ref().and_then(|chains| chains.get(alias)) {
-                return Ok(ResolvedRpc {
-                    url: chain_cfg.rpc.clone(),
-                    alias: Some(alias.to_string()),
-                    chain_id: chain_cfg.chain_id,
-                });
+                return ResolvedRpc::from_chain(alias, chain_cfg);
             }
             if let Some(legacy) = self.rpc.as_ref() {
                 let url = match alias {
@@ -148,53 +393,71 @@ impl Config {
                 };
                 if let Some(url) = url {
                     return Ok(ResolvedRpc {
-                        url,
+                        url: expand_env(&url).with_context(|| format!("rpc.{alias}"))?,
                         alias: Some(alias.to_string()),
                         chain_id: None,
+                        fallbacks: Vec::new(),
                     });
                 }
             }
-            anyhow::bail!("unknown chain alias: {alias}");
+            return Err(CastInteropError::Config(format!("unknown chain alias: {alias}")).into());
         }
 
         if let Some(chains) = self.chains.as_ref() {
             if let Some(chain_cfg) = chains.get("default") {
-                return Ok(ResolvedRpc {
-                    url: chain_cfg.rpc.clone(),
-                    alias: Some("default".to_string()),
-                    chain_id: chain_cfg.chain_id,
-                });
+                return ResolvedRpc::from_chain("default", chain_cfg);
             }
             if chains.len() == 1 {
                 let (alias, chain_cfg) = chains.iter().next().expect("non-empty");
-                return Ok(ResolvedRpc {
-                    url: chain_cfg.rpc.clone(),
-                    alias: Some(alias.clone()),
-                    chain_id: chain_cfg.chain_id,
-                });
+                return ResolvedRpc::from_chain(alias, chain_cfg);
             }
         }
         if let Some(default) = self.rpc.as_ref().and_then(|cfg| cfg.default.clone()) {
             return Ok(ResolvedRpc {
-                url: default,
+                url: expand_env(&default).context("rpc.default")?,
                 alias: Some("default".to_string()),
                 chain_id: None,
+                fallbacks: Vec::new(),
             });
         }
-        anyhow::bail!("no rpc configured (set --rpc or --chain, or configure a default)")
+        Err(CastInteropError::Config(
+            "no rpc configured (set --rpc or --chain, or configure a default)".to_string(),
+        )
+        .into())
     }
 
     pub fn set_chain(&mut self, alias: String, rpc: String, chain_id: u64) {
         let chains = self.chains.get_or_insert_with(BTreeMap::new);
+        let fallback_rpcs = chains
+            .get(&alias)
+            .map(|existing| existing.fallback_rpcs.clone())
+            .unwrap_or_default();
         chains.insert(
             alias,
             ChainConfig {
                 rpc,
                 chain_id: Some(chain_id),
+                fallback_rpcs,
             },
         );
     }
 
+    /// Point an existing alias at a new RPC, keeping its chain ID unless a
+    /// new one is given. Returns None if the alias is not configured.
+    pub fn update_chain(
+        &mut self,
+        alias: &str,
+        rpc: String,
+        chain_id: Option<u64>,
+    ) -> Option<&ChainConfig> {
+        let chain = self.chains.as_mut()?.get_mut(alias)?;
+        chain.rpc = rpc;
+        if chain_id.is_some() {
+            chain.chain_id = chain_id;
+        }
+        Some(chain)
+    }
+
     pub fn remove_chain(&mut self, alias: &str) -> bool {
         self.chains
             .as_mut()
@@ -211,10 +474,56 @@ impl Config {
             if let Some(chain_id) = chain.chain_id {
                 return Ok(alloy_primitives::U256::from(chain_id));
             }
-            anyhow::bail!("chainId missing for alias {value}");
+            return Err(
+                CastInteropError::Config(format!("chainId missing for alias {value}")).into(),
+            );
         }
-        crate::types::parse_u256(value)
+        crate::types::parse_u256(value).map_err(|_| {
+            CastInteropError::Config(format!(
+                "unknown chain {value}: not a configured alias or a numeric chain ID"
+            ))
+            .into()
+        })
+    }
+}
+
+/// Expand `${VAR}` references from the environment.
+///
+/// RPC URLs are stored unexpanded so API keys stay out of config.toml; they
+/// are expanded only when a URL is resolved for use.
+pub fn expand_env(value: &str) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| anyhow!("unterminated ${{ in {value}"))?;
+        let name = &after[..end];
+        if name.is_empty() {
+            anyhow::bail!("empty ${{}} in {value}");
+        }
+        let expanded = std::env::var(name).map_err(|_| {
+            anyhow!("environment variable {name} is not set (referenced in {value})")
+        })?;
+        out.push_str(&expanded);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Parse a bundle identifier given as one hex byte, with or without 0x.
+pub fn parse_bundle_identifier(value: &str) -> Result<u8> {
+    let trimmed = value.trim();
+    let digits = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+    if digits.is_empty() || digits.len() > 2 {
+        anyhow::bail!("bundle identifier must be a single hex byte like 0x01, got {value:?}");
     }
+    u8::from_str_radix(digits, 16).map_err(|_| {
+        anyhow!("bundle identifier must be a single hex byte like 0x01, got {value:?}")
+    })
 }
 
 fn default_config_path() -> PathBuf {
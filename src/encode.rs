@@ -1,7 +1,8 @@
-use crate::types::{bytes_from_hex, parse_address};
+use crate::types::{bytes_from_hex, parse_checked_address};
 use alloy_primitives::{keccak256, Address, Bytes, U256};
 use alloy_sol_types::{SolCall, SolValue};
 use anyhow::Result;
+use std::collections::HashSet;
 
 alloy_sol_types::sol! {
     function interopCallValue(uint256 _interopCallValue);
@@ -16,24 +17,47 @@ pub const DEFAULT_NATIVE_TOKEN_VAULT: &str = "0x00000000000000000000000000000000
 
 /// Encode a chain+address pair using the ERC-7930 v1 format.
 pub fn encode_evm_v1_with_address(chain_id: U256, address: Address) -> Bytes {
-    let chain_ref = to_chain_reference(chain_id);
-    let mut out = Vec::with_capacity(4 + 1 + chain_ref.len() + 1 + 20);
-    out.extend_from_slice(&EVM_V1_HEADER);
-    out.push(chain_ref.len() as u8);
-    out.extend_from_slice(&chain_ref);
-    out.push(20);
-    out.extend_from_slice(address.as_slice());
-    Bytes::from(out)
+    encode_evm_v1(&to_chain_reference(chain_id), Some(address))
 }
 
 /// Encode a chain-only ERC-7930 v1 reference (no address).
 pub fn encode_evm_v1_chain_only(chain_id: U256) -> Bytes {
-    let chain_ref = to_chain_reference(chain_id);
-    let mut out = Vec::with_capacity(4 + 1 + chain_ref.len() + 1);
+    encode_evm_v1(&to_chain_reference(chain_id), None)
+}
+
+/// Encode a chain+address pair with a fixed-width chain reference.
+///
+/// The chain ID is left-padded with zeros to `chain_ref_len` bytes, for
+/// consumers that do not accept the minimal encoding.
+pub fn encode_evm_v1_with_address_sized(
+    chain_id: U256,
+    address: Address,
+    chain_ref_len: usize,
+) -> Result<Bytes> {
+    Ok(encode_evm_v1(
+        &sized_chain_reference(chain_id, chain_ref_len)?,
+        Some(address),
+    ))
+}
+
+/// Encode a chain-only ERC-7930 v1 reference with a fixed-width chain reference.
+pub fn encode_evm_v1_chain_only_sized(chain_id: U256, chain_ref_len: usize) -> Result<Bytes> {
+    Ok(encode_evm_v1(
+        &sized_chain_reference(chain_id, chain_ref_len)?,
+        None,
+    ))
+}
+
+fn encode_evm_v1(chain_ref: &[u8], address: Option<Address>) -> Bytes {
+    let address = address
+        .as_ref()
+        .map_or(&[][..], |address| address.as_slice());
+    let mut out = Vec::with_capacity(4 + 1 + chain_ref.len() + 1 + address.len());
     out.extend_from_slice(&EVM_V1_HEADER);
     out.push(chain_ref.len() as u8);
-    out.extend_from_slice(&chain_ref);
-    out.push(0);
+    out.extend_from_slice(chain_ref);
+    out.push(address.len() as u8);
+    out.extend_from_slice(address);
     Bytes::from(out)
 }
 
@@ -78,12 +102,160 @@ pub fn encode_unbundler_address(value: Bytes) -> Bytes {
     Bytes::from(call.abi_encode())
 }
 
+/// An interop attribute decoded from its ABI-encoded call bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedAttr {
+    InteropCallValue(U256),
+    IndirectCall(U256),
+    ExecutionAddress(Bytes),
+    UnbundlerAddress(Bytes),
+}
+
+impl DecodedAttr {
+    /// Attribute function name as declared on the interop center.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DecodedAttr::InteropCallValue(_) => "interopCallValue",
+            DecodedAttr::IndirectCall(_) => "indirectCall",
+            DecodedAttr::ExecutionAddress(_) => "executionAddress",
+            DecodedAttr::UnbundlerAddress(_) => "unbundlerAddress",
+        }
+    }
+
+    /// Human-readable value: wei amounts as decimals, ERC-7930 addresses
+    /// decoded where possible (empty means permissionless).
+    pub fn value_string(&self) -> String {
+        match self {
+            DecodedAttr::InteropCallValue(value) | DecodedAttr::IndirectCall(value) => {
+                value.to_string()
+            }
+            DecodedAttr::ExecutionAddress(bytes) | DecodedAttr::UnbundlerAddress(bytes) => {
+                if bytes.is_empty() {
+                    return "permissionless".to_string();
+                }
+                match decode_evm_v1_address(bytes) {
+                    Ok((chain_id, Some(address))) if chain_id == U256::ZERO => {
+                        format!("{address:#x}")
+                    }
+                    Ok((chain_id, Some(address))) => format!("{address:#x} (chainId {chain_id})"),
+                    Ok((chain_id, None)) => format!("chainId {chain_id} (no address)"),
+                    Err(_) => format!("0x{}", hex::encode(bytes)),
+                }
+            }
+        }
+    }
+}
+
+/// Decode a single ABI-encoded attribute by its 4-byte selector.
+pub fn decode_attribute(bytes: &[u8]) -> Result<DecodedAttr> {
+    let selector: [u8; 4] = bytes
+        .get(..4)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("attribute too short to carry a selector"))?;
+    let decoded = match selector {
+        interopCallValueCall::SELECTOR => DecodedAttr::InteropCallValue(
+            interopCallValueCall::abi_decode(bytes)?._interopCallValue,
+        ),
+        indirectCallCall::SELECTOR => DecodedAttr::IndirectCall(
+            indirectCallCall::abi_decode(bytes)?._indirectCallMessageValue,
+        ),
+        executionAddressCall::SELECTOR => DecodedAttr::ExecutionAddress(
+            executionAddressCall::abi_decode(bytes)?._executionAddress,
+        ),
+        unbundlerAddressCall::SELECTOR => DecodedAttr::UnbundlerAddress(
+            unbundlerAddressCall::abi_decode(bytes)?._unbundlerAddress,
+        ),
+        other => anyhow::bail!("unknown attribute selector 0x{}", hex::encode(other)),
+    };
+    Ok(decoded)
+}
+
+/// Where an attribute list is parsed by the interop center.
+///
+/// Mirrors the center's `AttributeParsingRestrictions` for the lists this CLI builds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeScope {
+    /// `sendMessage` attributes: call and bundle attributes are both allowed.
+    CallAndBundle,
+    /// Per-call attributes in `sendBundle`.
+    Call,
+    /// Bundle-level attributes in `sendBundle`.
+    Bundle,
+}
+
+impl AttributeScope {
+    fn label(self) -> &'static str {
+        match self {
+            AttributeScope::CallAndBundle => "message",
+            AttributeScope::Call => "call",
+            AttributeScope::Bundle => "bundle",
+        }
+    }
+}
+
+/// Resolve a known attribute selector to its name and whether it is a call attribute.
+pub fn attribute_info(selector: [u8; 4]) -> Option<(&'static str, bool)> {
+    if selector == interopCallValueCall::SELECTOR {
+        Some(("interopCallValue", true))
+    } else if selector == indirectCallCall::SELECTOR {
+        Some(("indirectCall", true))
+    } else if selector == executionAddressCall::SELECTOR {
+        Some(("executionAddress", false))
+    } else if selector == unbundlerAddressCall::SELECTOR {
+        Some(("unbundlerAddress", false))
+    } else {
+        None
+    }
+}
+
+/// Validate encoded attributes the way the interop center will parse them.
+///
+/// Rejects duplicate selectors (`AttributeAlreadySet`), attributes outside the
+/// allowed scope (`AttributeViolatesRestriction`), and unknown selectors before
+/// anything is sent.
+pub fn validate_attributes(attributes: &[Bytes], scope: AttributeScope) -> Result<()> {
+    let mut seen = HashSet::new();
+    for attribute in attributes {
+        let selector: [u8; 4] = attribute
+            .get(..4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| anyhow::anyhow!("attribute too short to carry a selector"))?;
+        let Some((name, is_call_attribute)) = attribute_info(selector) else {
+            anyhow::bail!(
+                "unsupported attribute selector 0x{} in {} attributes",
+                hex::encode(selector),
+                scope.label()
+            );
+        };
+        if !seen.insert(selector) {
+            anyhow::bail!(
+                "attribute {name} is set more than once in {} attributes (would revert with AttributeAlreadySet)",
+                scope.label()
+            );
+        }
+        let allowed = match scope {
+            AttributeScope::CallAndBundle => true,
+            AttributeScope::Call => is_call_attribute,
+            AttributeScope::Bundle => !is_call_attribute,
+        };
+        if !allowed {
+            anyhow::bail!(
+                "attribute {name} is not allowed in {} attributes (would revert with AttributeViolatesRestriction)",
+                scope.label()
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Parse payload input from --payload or --payload-file.
 ///
-/// Ensures only one input source is set.
+/// Ensures only one input source is set. When neither is set, value-only
+/// messages (`has_value`) default to an empty payload.
 pub fn parse_payload(
     payload: Option<&str>,
     payload_file: Option<&std::path::Path>,
+    has_value: bool,
 ) -> Result<Bytes> {
     match (payload, payload_file) {
         (Some(_), Some(_)) => anyhow::bail!("cannot set both --payload and --payload-file"),
@@ -92,7 +264,10 @@ pub fn parse_payload(
             let contents = std::fs::read_to_string(path)?;
             bytes_from_hex(&contents)
         }
-        (None, None) => anyhow::bail!("payload required (set --payload or --payload-file)"),
+        (None, None) if has_value => Ok(Bytes::new()),
+        (None, None) => anyhow::bail!(
+            "payload required (set --payload or --payload-file, or --interop-value/--indirect for a value-only message)"
+        ),
     }
 }
 
@@ -103,7 +278,7 @@ pub fn parse_permissionless_address(value: &str) -> Result<Option<Address>> {
     if value == "permissionless" {
         return Ok(None);
     }
-    parse_address(value).map(Some)
+    parse_checked_address(value).map(Some)
 }
 
 /// Compute the assetId hash for a token and vault on a chain.
@@ -116,7 +291,10 @@ pub fn encode_asset_id(chain_id: U256, token: Address, native_token_vault: Addre
 
 /// Decode an ERC-7930 v1 chain/address reference.
 ///
-/// Returns the chain ID and optional address.
+/// Returns the chain ID and optional address. Chain references may be 0-32
+/// bytes long; the address-only form (`EVM_V1_ADDRESS_ONLY_HEADER`, an empty
+/// chain reference) yields chain ID 0. The data must end exactly after the
+/// address, so trailing bytes are rejected.
 pub fn decode_evm_v1_address(data: &Bytes) -> Result<(U256, Option<Address>)> {
     let bytes = data.as_ref();
     if bytes.len() < 6 {
@@ -126,6 +304,9 @@ pub fn decode_evm_v1_address(data: &Bytes) -> Result<(U256, Option<Address>)> {
         anyhow::bail!("unsupported ERC-7930 header");
     }
     let chain_len = bytes[4] as usize;
+    if chain_len > 32 {
+        anyhow::bail!("erc-7930 chain reference too long ({chain_len} bytes)");
+    }
     let chain_start = 5;
     let chain_end = chain_start + chain_len;
     if bytes.len() < chain_end + 1 {
@@ -138,6 +319,12 @@ pub fn decode_evm_v1_address(data: &Bytes) -> Result<(U256, Option<Address>)> {
     if bytes.len() < addr_end {
         anyhow::bail!("erc-7930 data truncated");
     }
+    if bytes.len() > addr_end {
+        anyhow::bail!(
+            "erc-7930 data is {} bytes long, expected {addr_end}",
+            bytes.len()
+        );
+    }
     let chain_id = if chain_len == 0 {
         U256::ZERO
     } else {
@@ -153,6 +340,24 @@ pub fn decode_evm_v1_address(data: &Bytes) -> Result<(U256, Option<Address>)> {
     Ok((chain_id, address))
 }
 
+/// Convert a chain ID to a `len`-byte big-endian representation, failing
+/// when the chain ID needs more bytes than that.
+fn sized_chain_reference(chain_id: U256, len: usize) -> Result<Vec<u8>> {
+    if !(1..=32).contains(&len) {
+        anyhow::bail!("invalid chain reference length {len} (expected 1-32 bytes)");
+    }
+    let minimal = to_chain_reference(chain_id);
+    if minimal.len() > len {
+        anyhow::bail!(
+            "chain ID {chain_id} needs {} bytes, more than the chain reference length {len}",
+            minimal.len()
+        );
+    }
+    let mut bytes = vec![0u8; len - minimal.len()];
+    bytes.extend_from_slice(&minimal);
+    Ok(bytes)
+}
+
 /// Convert a chain ID to a minimal big-endian byte representation.
 fn to_chain_reference(chain_id: U256) -> Vec<u8> {
     if chain_id == U256::ZERO {
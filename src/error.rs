@@ -0,0 +1,91 @@
+use crate::abi::{decode_revert, revert_data_from_message};
+use alloy_primitives::{Bytes, B256};
+use std::fmt;
+
+/// Failures of the RPC, revert-decoding and proof paths that callers may
+/// want to tell apart.
+///
+/// These are returned inside `anyhow::Error`, so the CLI keeps printing them
+/// as before; code that needs to branch on them uses
+/// `err.downcast_ref::<CastInteropError>()`.
+#[derive(Debug)]
+pub enum CastInteropError {
+    /// The node rejected a JSON-RPC request (error object or HTTP status).
+    Rpc {
+        method: String,
+        /// JSON-RPC error code; None for HTTP-level failures.
+        code: Option<i64>,
+        message: String,
+    },
+    /// A call reverted with data this tool could decode.
+    Revert {
+        /// What reverted, e.g. "dry-run" or "gas estimation".
+        action: &'static str,
+        selector: [u8; 4],
+        /// Solidity error name, e.g. `WrongDestinationChainId` or `Error`.
+        name: String,
+        /// Full revert data, including the selector.
+        data: Bytes,
+        /// Decoded rendering, e.g. `revert: BundleAlreadyProcessed(bundleHash=0x..)`.
+        reason: String,
+    },
+    /// The config file or RPC selection flags are unusable.
+    Config(String),
+    /// The source chain did not serve a log proof before the timeout.
+    ProofNotReady { tx_hash: B256, msg_index: u32 },
+    /// The destination did not import the interop root before the timeout.
+    RootNotReady { chain_id: u64, batch_number: u64 },
+    /// The destination holds a different root than the proof expects.
+    RootMismatch { expected: B256, actual: B256 },
+}
+
+impl CastInteropError {
+    /// Decode the revert carried by a node error message, if any.
+    pub fn revert(action: &'static str, message: &str) -> Option<Self> {
+        let data = revert_data_from_message(message)?;
+        let decoded = decode_revert(&data)?;
+        Some(Self::Revert {
+            action,
+            selector: decoded.selector,
+            name: decoded.name,
+            data,
+            reason: decoded.reason,
+        })
+    }
+}
+
+impl fmt::Display for CastInteropError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rpc {
+                method,
+                code: Some(code),
+                message,
+            } => write!(f, "rpc error {code} from {method}: {message}"),
+            Self::Rpc {
+                method,
+                code: None,
+                message,
+            } => write!(f, "rpc error from {method}: {message}"),
+            Self::Revert { action, reason, .. } => write!(f, "{action} reverted: {reason}"),
+            Self::Config(message) => f.write_str(message),
+            Self::ProofNotReady { tx_hash, msg_index } => write!(
+                f,
+                "log proof not available in time (tx {tx_hash:#x}, message index {msg_index})"
+            ),
+            Self::RootNotReady {
+                chain_id,
+                batch_number,
+            } => write!(
+                f,
+                "interop root did not become available in time (chain {chain_id}, batch {batch_number})"
+            ),
+            Self::RootMismatch { expected, actual } => write!(
+                f,
+                "interop root mismatch: expected {expected:#x}, got {actual:#x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CastInteropError {}
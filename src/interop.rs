@@ -0,0 +1,402 @@
+use crate::abi::{
+    decode_bundle, decode_bytes32, decode_interop_bundle_sent, encode_interop_bundle,
+    encode_interop_roots_call, interop_bundle_sent_topic,
+};
+use crate::commands::messages::{l1_messages, warn_if_ambiguous_msg_index};
+use crate::error::CastInteropError;
+use crate::output::write_atomic;
+use crate::rpc::{
+    eth_call, get_transaction_receipt, interruptible, wait_for_confirmations,
+    wait_for_finalized_block, wait_for_log_proof, LogProof, RpcClient, WaitLimit,
+};
+use crate::types::{
+    bundle_message, bytes_from_hex, format_hex, InteropBundle, MessageInclusionProof, ProofMessage,
+};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_rpc_types::TransactionReceipt;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Source-side relay inputs: the encoded bundle and its inclusion proof.
+pub struct PreparedProof {
+    pub encoded_bundle: Bytes,
+    pub proof: MessageInclusionProof,
+    pub bundle_hash: B256,
+    pub source_tx_hash: String,
+    pub source_chain_id: u64,
+}
+
+/// Everything needed to prepare the proof of a source transaction.
+#[derive(Clone)]
+pub struct SourceProver {
+    pub source_client: RpcClient,
+    pub dest_client: RpcClient,
+    pub center: Address,
+    /// Emitter of the source chain's `L1MessageSent` events.
+    pub l1_messenger: Address,
+    pub root_storage: Address,
+    pub msg_index: u32,
+    /// Which InteropBundleSent event of the transaction to prove; the first
+    /// (with a warning if there are several) when None.
+    pub bundle_index: Option<usize>,
+    /// Byte prepended to the bundle in its L2→L1 message.
+    pub bundle_identifier: u8,
+    pub limit: WaitLimit,
+    pub poll: Duration,
+    /// Blocks the source receipt must be buried under before proving.
+    pub confirmations: u64,
+    /// Directory of proofs whose root was already seen on the destination,
+    /// one file per source tx hash, message index and bundle index.
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// A proof cache entry; only reused for the same destination chain.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedProof {
+    destination_chain_id: String,
+    source_chain_id: String,
+    source_tx_hash: String,
+    bundle_hash: String,
+    encoded_bundle: String,
+    proof: MessageInclusionProof,
+}
+
+/// Fetch the bundle and its proof from the source chain, waiting for
+/// finalization, the log proof, and the interop root on the destination.
+///
+/// With a cache directory, a proof whose root was already seen is returned
+/// without waiting, once the receipt confirms it is for the selected bundle,
+/// and a fresh one is stored once its root is available.
+/// `rerun` is the command suggested when a wait is interrupted. Progress is
+/// logged at info level.
+pub async fn prepare_proof(
+    prover: &SourceProver,
+    tx_hash: B256,
+    rerun: &str,
+) -> Result<PreparedProof> {
+    let receipt = get_transaction_receipt(&prover.source_client, tx_hash).await?;
+    warn_if_ambiguous_msg_index(&receipt, tx_hash, prover.msg_index, prover.l1_messenger);
+    let (l2l1_msg_hash, bundle_hash, bundle) =
+        select_sent_bundle(&receipt, tx_hash, prover.bundle_index)?;
+    check_bundle_msg_index(
+        &receipt,
+        prover.l1_messenger,
+        l2l1_msg_hash,
+        prover.msg_index,
+    )?;
+
+    let destination_chain_id = prover.dest_client.chain_id().await?;
+    let cache_path = prover.cache_dir.as_ref().map(|dir| {
+        dir.join(format!(
+            "{tx_hash:#x}-{}-{}.json",
+            prover.msg_index,
+            prover.bundle_index.unwrap_or(0)
+        ))
+    });
+    if let Some(path) = &cache_path {
+        if let Some(cached) =
+            read_cached_proof(path, destination_chain_id, prover.center, bundle_hash)
+        {
+            tracing::info!(path = %path.display(), "using cached proof");
+            return Ok(cached);
+        }
+    }
+
+    let encoded_bundle = encode_interop_bundle(&bundle);
+    let block_number = receipt
+        .block_number
+        .ok_or_else(|| anyhow!("missing block number in receipt of {tx_hash:#x}"))?;
+
+    if prover.confirmations > 0 {
+        tracing::info!(
+            block_number,
+            confirmations = prover.confirmations,
+            "waiting for confirmations on source"
+        );
+        interruptible(
+            wait_for_confirmations(
+                &prover.source_client,
+                &receipt,
+                prover.confirmations,
+                prover.limit,
+                prover.poll,
+            ),
+            || format!("source block {block_number} not confirmed yet; resume with `{rerun}`"),
+        )
+        .await?;
+    }
+    tracing::info!(block_number, "waiting for finalized block on source");
+    interruptible(
+        wait_for_finalized_block(
+            &prover.source_client,
+            block_number,
+            prover.limit,
+            Duration::from_millis(100),
+        ),
+        || format!("source block {block_number} not finalized yet; resume with `{rerun}`"),
+    )
+    .await?;
+    tracing::info!(
+        msg_index = prover.msg_index,
+        "waiting for log proof on source"
+    );
+    let log_proof = interruptible(
+        wait_for_log_proof(
+            &prover.source_client,
+            tx_hash,
+            prover.msg_index,
+            prover.limit,
+            prover.poll,
+        ),
+        || {
+            format!(
+                "source block {block_number} finalized but log proof not available yet; resume with `{rerun}`"
+            )
+        },
+    )
+    .await?;
+
+    let source_chain_id = prover.source_client.chain_id().await?;
+
+    interruptible(
+        wait_for_root(
+            &prover.dest_client,
+            prover.root_storage,
+            source_chain_id,
+            &log_proof,
+            prover.limit,
+            prover.poll,
+        ),
+        || {
+            format!(
+                "log proof ready (batch {}) but interop root not yet available on destination; resume with `{rerun}`",
+                log_proof.batch_number
+            )
+        },
+    )
+    .await?;
+
+    let message_bytes = bundle_message(prover.bundle_identifier, &encoded_bundle);
+    let message = ProofMessage {
+        tx_number_in_batch: receipt
+            .transaction_index
+            .ok_or_else(|| anyhow!("missing tx index in receipt of {tx_hash:#x}"))?,
+        sender: format!("{:#x}", prover.center),
+        data: format_hex(&message_bytes),
+    };
+    let proof = MessageInclusionProof {
+        chain_id: source_chain_id.to_string(),
+        l1_batch_number: log_proof.batch_number,
+        l2_message_index: log_proof.id,
+        root: log_proof.root.clone(),
+        message,
+        proof: log_proof.proof.clone(),
+        l2l1_msg_hash: Some(format!("{:#x}", keccak256(&message_bytes))),
+    };
+
+    let prepared = PreparedProof {
+        encoded_bundle,
+        proof,
+        bundle_hash,
+        source_tx_hash: format!("{tx_hash:#x}"),
+        source_chain_id,
+    };
+    if let Some(path) = &cache_path {
+        write_cached_proof(path, &prepared, destination_chain_id)?;
+    }
+    Ok(prepared)
+}
+
+/// Decode every InteropBundleSent event of a receipt, in log order, as
+/// `(l2l1MsgHash, bundleHash, bundle)`.
+pub fn sent_bundles(receipt: &TransactionReceipt) -> Result<Vec<(B256, B256, InteropBundle)>> {
+    receipt
+        .logs()
+        .iter()
+        .filter(|log| log.topics().first().copied() == Some(interop_bundle_sent_topic()))
+        .map(|log| decode_interop_bundle_sent(log.data().data.clone()))
+        .collect()
+}
+
+/// Pick the InteropBundleSent event at `index` of a receipt.
+///
+/// Without an index the first one is used, with a warning when the
+/// transaction sends several bundles.
+pub fn select_sent_bundle(
+    receipt: &TransactionReceipt,
+    tx_hash: B256,
+    index: Option<usize>,
+) -> Result<(B256, B256, InteropBundle)> {
+    let mut bundles = sent_bundles(receipt)?;
+    if bundles.is_empty() {
+        return Err(anyhow!("InteropBundleSent not found in receipt"));
+    }
+    let count = bundles.len();
+    if index.is_none() && count > 1 {
+        eprintln!(
+            "warning: {tx_hash:#x} sends {count} bundles; using bundle index 0 (pass --bundle-index to pick another, list them with `cast-interop debug tx {tx_hash:#x}`)"
+        );
+    }
+    let index = index.unwrap_or(0);
+    if index >= count {
+        anyhow::bail!("bundle index {index} out of range: {tx_hash:#x} sends {count} bundles");
+    }
+    Ok(bundles.swap_remove(index))
+}
+
+/// Fail when the L2→L1 message at `msg_index` is another bundle's, since its
+/// proof could not verify the selected one.
+fn check_bundle_msg_index(
+    receipt: &TransactionReceipt,
+    l1_messenger: Address,
+    l2l1_msg_hash: B256,
+    msg_index: u32,
+) -> Result<()> {
+    let messages = l1_messages(receipt, l1_messenger);
+    match messages.get(msg_index as usize) {
+        Some(message) if message.hash != l2l1_msg_hash => {
+            if let Some(index) = messages
+                .iter()
+                .position(|message| message.hash == l2l1_msg_hash)
+            {
+                anyhow::bail!(
+                    "message index {msg_index} is not the selected bundle's L2→L1 message; pass --msg-index {index}"
+                );
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Wait for the expected interop root to appear on the destination chain.
+async fn wait_for_root(
+    client: &RpcClient,
+    root_storage: Address,
+    chain_id: u64,
+    log_proof: &LogProof,
+    limit: WaitLimit,
+    poll: Duration,
+) -> Result<()> {
+    let expected = B256::from_str(&log_proof.root)?;
+    let batch_number = log_proof.batch_number;
+    let mut client = client.clone();
+    let mut clock = limit.start();
+    let mut first_run = true;
+    loop {
+        let data = encode_interop_roots_call(U256::from(chain_id), U256::from(batch_number));
+        let result = match eth_call(&client, root_storage, data).await {
+            Ok(result) => result,
+            Err(err) => {
+                client = client.failover(err).await?;
+                continue;
+            }
+        };
+        let root = decode_bytes32(result)?;
+        if root != B256::ZERO {
+            if root == expected {
+                tracing::info!(root = %format!("{root:#x}"), "interop root available");
+                return Ok(());
+            }
+            return Err(CastInteropError::RootMismatch {
+                expected,
+                actual: root,
+            }
+            .into());
+        }
+        if clock.expired(&client).await {
+            return Err(CastInteropError::RootNotReady {
+                chain_id,
+                batch_number,
+            }
+            .into());
+        }
+        if first_run {
+            tracing::info!(
+                chain_id,
+                batch_number,
+                limit = %limit,
+                "waiting for interop root on destination"
+            );
+            first_run = false;
+        }
+        tokio::time::sleep(poll).await;
+    }
+}
+
+/// Load a cached proof, ignoring (with a warning for unreadable files) any
+/// entry that does not match the destination chain, interop center and
+/// selected bundle.
+fn read_cached_proof(
+    path: &Path,
+    destination_chain_id: u64,
+    center: Address,
+    bundle_hash: B256,
+) -> Option<PreparedProof> {
+    if !path.exists() {
+        return None;
+    }
+    let cached = fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|contents| parse_cached_proof(&contents));
+    match cached {
+        Ok((cached, prepared))
+            if cached.destination_chain_id == destination_chain_id.to_string()
+                && cached.proof.message.sender.to_lowercase()
+                    == format!("{center:#x}").to_lowercase()
+                && prepared.bundle_hash == bundle_hash =>
+        {
+            Some(prepared)
+        }
+        Ok(_) => None,
+        Err(err) => {
+            eprintln!("warning: ignoring proof cache {}: {err:#}", path.display());
+            None
+        }
+    }
+}
+
+/// Parse and check a cache entry, returning it alongside the proof it holds.
+fn parse_cached_proof(contents: &str) -> Result<(CachedProof, PreparedProof)> {
+    let cached: CachedProof = serde_json::from_str(contents)?;
+    cached.proof.validate()?;
+    let encoded_bundle = bytes_from_hex(&cached.encoded_bundle)?;
+    decode_bundle(&encoded_bundle).context("invalid cached bundle")?;
+    let prepared = PreparedProof {
+        encoded_bundle,
+        proof: cached.proof.clone(),
+        bundle_hash: B256::from_str(&cached.bundle_hash).context("invalid cached bundle hash")?,
+        source_tx_hash: cached.source_tx_hash.clone(),
+        source_chain_id: cached
+            .source_chain_id
+            .parse()
+            .context("invalid cached source chain id")?,
+    };
+    Ok((cached, prepared))
+}
+
+/// Store a proof whose root is available on the destination.
+fn write_cached_proof(
+    path: &Path,
+    prepared: &PreparedProof,
+    destination_chain_id: u64,
+) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create proof cache {}", dir.display()))?;
+    }
+    let cached = CachedProof {
+        destination_chain_id: destination_chain_id.to_string(),
+        source_chain_id: prepared.source_chain_id.to_string(),
+        source_tx_hash: prepared.source_tx_hash.clone(),
+        bundle_hash: format!("{:#x}", prepared.bundle_hash),
+        encoded_bundle: format_hex(&prepared.encoded_bundle),
+        proof: prepared.proof.clone(),
+    };
+    write_atomic(path, &serde_json::to_string_pretty(&cached)?)
+}
@@ -0,0 +1,25 @@
+//! Inspect, prove, and relay zkSync interop bundles.
+//!
+//! The `cast-interop` binary is a thin clap layer over this crate. Commands
+//! that are useful to embed take plain parameter structs, e.g.
+//! [`relay`] with [`RelayParams`], and return the same result types the CLI
+//! prints with `--output json`.
+
+pub mod abi;
+pub mod cli;
+pub mod commands;
+pub mod config;
+pub mod encode;
+pub mod error;
+pub mod interop;
+pub mod output;
+pub mod rpc;
+pub mod rpc_trace;
+pub mod signer;
+pub mod types;
+
+pub use commands::relay::{relay, RelayParams};
+pub use config::Config;
+pub use error::CastInteropError;
+pub use rpc::{TxOverrides, WaitLimit};
+pub use types::{AddressBook, RelayResult};
@@ -1,21 +1,25 @@
-mod abi;
-mod cli;
-mod commands;
-mod config;
-mod encode;
-mod rpc;
-mod signer;
-mod types;
-
 use anyhow::Result;
-use clap::Parser;
+use cast_interop::{cli, config};
+use std::process::ExitCode;
 
 use tracing_subscriber::{fmt, EnvFilter};
 
-fn init_logging() {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+fn init_logging(trace: bool, quiet: bool) {
+    // `--quiet` drops the info-level progress logs; RUST_LOG still wins.
+    let default = if quiet { "warn" } else { "info" };
+    let mut filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default));
+    if trace {
+        // `--trace` records are debug events; show them whatever RUST_LOG says.
+        filter = filter.add_directive(
+            "cast_interop::rpc_trace=debug"
+                .parse()
+                .expect("valid directive"),
+        );
+    }
 
+    // Diagnostics go to stderr so stdout only carries command output.
     fmt()
+        .with_writer(std::io::stderr)
         .with_env_filter(filter)
         .with_target(true) // show module path
         .with_thread_ids(true) // useful for async
@@ -24,9 +28,9 @@ fn init_logging() {
         .init();
 }
 #[tokio::main]
-async fn main() -> Result<()> {
-    init_logging();
-    let cli = cli::Cli::parse();
-    let config = config::Config::load(cli.config_path.as_deref())?;
+async fn main() -> Result<ExitCode> {
+    let cli = cli::Cli::parse_checked();
+    init_logging(cli.trace && cli.trace_file.is_none(), cli.quiet);
+    let config = config::Config::load(cli.config_path.as_deref(), cli.profile.as_deref())?;
     cli.run(config).await
 }
@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::io::{IsTerminal, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set once stdin has been read, since only one `-` argument can use it.
+static STDIN_READ: AtomicBool = AtomicBool::new(false);
+
+/// Print `output` as JSON when `json` is set, and write the same JSON to
+/// `out` when given (`--out`).
+///
+/// Returns `json` so callers can skip their text rendering:
+/// `if emit(&output, config.json_output(), args.out.as_deref())? { return Ok(()); }`
+pub fn emit<T: Serialize + ?Sized>(output: &T, json: bool, out: Option<&Path>) -> Result<bool> {
+    if !json && out.is_none() {
+        return Ok(false);
+    }
+    let rendered = serde_json::to_string_pretty(output)?;
+    if let Some(path) = out {
+        write_atomic(path, &rendered)?;
+    }
+    if json {
+        println!("{rendered}");
+    }
+    Ok(json)
+}
+
+/// Prefix for an "ok"/"warn"/"fail" check in report-style text output:
+/// an emoji, or with `plain` (`--ascii`, or stdout not a terminal) an ASCII tag
+/// that survives CI logs and non-UTF-8 terminals.
+pub fn status_icon(status: &str, plain: bool) -> &'static str {
+    match (status, plain) {
+        ("ok", false) => "✅",
+        ("warn", false) => "⚠️",
+        ("fail", false) => "❌",
+        (_, false) => "•",
+        ("ok", true) => "[ok]",
+        ("warn", true) => "[warn]",
+        ("fail", true) => "[fail]",
+        (_, true) => "[-]",
+    }
+}
+
+/// Read all of stdin for an argument given as `-` (e.g. `--calls -`).
+///
+/// Fails when stdin is a terminal, or when another argument already read it.
+pub fn read_stdin(flag: &str) -> Result<String> {
+    if STDIN_READ.swap(true, Ordering::SeqCst) {
+        anyhow::bail!("{flag} -: stdin was already read by another argument");
+    }
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        anyhow::bail!("{flag} -: nothing piped to stdin");
+    }
+    let mut contents = String::new();
+    stdin
+        .read_to_string(&mut contents)
+        .with_context(|| format!("failed to read {flag} from stdin"))?;
+    Ok(contents)
+}
+
+/// Write `contents` to a sibling temp file and rename it over `path`, so an
+/// interrupted write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let mut tmp_name = path
+        .file_name()
+        .ok_or_else(|| anyhow!("invalid output path {}", path.display()))?
+        .to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    fs::write(&tmp, contents).with_context(|| format!("failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(())
+}
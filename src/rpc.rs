@@ -1,33 +1,325 @@
 use alloy_primitives::{Address, Bytes, B256};
+use alloy_provider::network::EthereumWallet;
 use alloy_provider::{DynProvider, Provider, ProviderBuilder};
-use alloy_rpc_types::{BlockNumberOrTag, TransactionInput, TransactionReceipt, TransactionRequest};
+use alloy_rpc_types::{
+    BlockId, BlockNumberOrTag, TransactionInput, TransactionReceipt, TransactionRequest,
+};
+use alloy_transport_http::Http;
 use anyhow::{anyhow, Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::OnceCell;
 
-use crate::commands::bundle_action::decode_revert_reason;
+use crate::cli::TxOverrideArgs;
+use crate::config::ResolvedRpc;
+use crate::error::CastInteropError;
+use crate::rpc_trace::{self, TracedTransport};
+use crate::types::parse_u256;
 
 #[derive(Clone)]
 pub struct RpcClient {
+    /// The endpoint this client is connected to.
     pub url: String,
     pub provider: DynProvider,
     pub http: Client,
+    pub timeout: Duration,
+    /// Extra headers (from `--rpc-header`) sent with every request.
+    headers: HeaderMap,
+    /// eth_chainId of this endpoint, fetched once and shared by clones.
+    chain_id: Arc<OnceCell<u64>>,
+    /// Endpoints still available to fail over to, in order.
+    fallbacks: Vec<String>,
+    /// Chain ID a fallback must report before it is used, from the chain
+    /// config; the primary's own chain ID takes precedence once known.
+    expected_chain_id: Option<u64>,
 }
 
 impl RpcClient {
-    pub async fn new(url: &str) -> Result<Self> {
-        let http = Client::new();
+    /// Connect to the first reachable endpoint in `urls`; every request (raw
+    /// or through the provider) carries `headers` and is aborted once it takes
+    /// longer than `timeout`.
+    ///
+    /// Reachability is only probed when there is more than one endpoint, so a
+    /// single URL keeps connecting lazily.
+    pub async fn new(urls: &[String], timeout: Duration, headers: &HeaderMap) -> Result<Self> {
+        Self::connect_first(urls, None, false, timeout, headers).await
+    }
+
+    /// Connect to a resolved `--rpc`/`--chain` selection, like [`Self::new`].
+    ///
+    /// A fallback is only used when it reports the chain's configured
+    /// `chainId`, so a misconfigured backup cannot silently switch chains.
+    pub async fn for_rpc(
+        rpc: &ResolvedRpc,
+        timeout: Duration,
+        headers: &HeaderMap,
+    ) -> Result<Self> {
+        Self::connect_first(&rpc.urls(), rpc.chain_id, false, timeout, headers).await
+    }
+
+    /// Connect to the first reachable endpoint in `urls`. Every endpoint but
+    /// the primary (all of them when `failing_over`) must report
+    /// `expected_chain_id`, when set.
+    async fn connect_first(
+        urls: &[String],
+        expected_chain_id: Option<u64>,
+        failing_over: bool,
+        timeout: Duration,
+        headers: &HeaderMap,
+    ) -> Result<Self> {
+        let mut last_err = None;
+        for (index, url) in urls.iter().enumerate() {
+            let client = Self::connect(
+                url,
+                timeout,
+                headers,
+                urls[index + 1..].to_vec(),
+                expected_chain_id,
+            )?;
+            let is_fallback = failing_over || index > 0;
+            if urls.len() == 1 && !is_fallback {
+                return Ok(client);
+            }
+            match client.chain_id().await {
+                Ok(chain_id) => {
+                    if !is_fallback {
+                        return Ok(client);
+                    }
+                    match expected_chain_id {
+                        Some(expected) if chain_id != expected => {
+                            eprintln!("rpc {url} is on chain {chain_id}, expected {expected}");
+                            last_err = Some(anyhow!(
+                                "fallback rpc {url} is on chain {chain_id}, expected {expected}"
+                            ));
+                        }
+                        _ => {
+                            eprintln!("using fallback rpc {url}");
+                            return Ok(client);
+                        }
+                    }
+                }
+                Err(err) => {
+                    eprintln!("rpc {url} unreachable: {err}");
+                    last_err = Some(anyhow!(err).context(format!("rpc {url} unreachable")));
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("no rpc url configured")))
+    }
+
+    fn connect(
+        url: &str,
+        timeout: Duration,
+        headers: &HeaderMap,
+        fallbacks: Vec<String>,
+        expected_chain_id: Option<u64>,
+    ) -> Result<Self> {
+        let http = Client::builder()
+            .timeout(timeout)
+            .default_headers(headers.clone())
+            .build()
+            .context("failed to build http client")?;
 
-        let provider = ProviderBuilder::new().connect(url).await?;
+        let provider =
+            ProviderBuilder::new().connect_client(transport_client(url, timeout, headers)?);
 
         Ok(Self {
             url: url.to_string(),
             provider: provider.erased(),
             http,
+            timeout,
+            headers: headers.clone(),
+            chain_id: Arc::new(OnceCell::new()),
+            fallbacks,
+            expected_chain_id,
         })
     }
+
+    /// The endpoint's chain ID, cached after the first eth_chainId call.
+    pub async fn chain_id(&self) -> Result<u64> {
+        let chain_id = self
+            .chain_id
+            .get_or_try_init(|| async { self.provider.get_chain_id().await })
+            .await?;
+        Ok(*chain_id)
+    }
+
+    /// Move to the next reachable fallback endpoint after `err`.
+    ///
+    /// Only transport failures (unreachable node, timeout, HTTP error) move on;
+    /// reverts and JSON-RPC errors would fail the same way elsewhere. Returns
+    /// `err` unchanged when it is not retried or no fallback is left, so
+    /// polling loops can simply propagate it. The fallback must be on the
+    /// chain this endpoint reported.
+    pub async fn failover(&self, err: anyhow::Error) -> Result<Self> {
+        if self.fallbacks.is_empty() || !is_transport_error(&err) {
+            return Err(err);
+        }
+        eprintln!("rpc {} failed: {err:#}", self.url);
+        let expected_chain_id = self.chain_id.get().copied().or(self.expected_chain_id);
+        Self::connect_first(
+            &self.fallbacks,
+            expected_chain_id,
+            true,
+            self.timeout,
+            &self.headers,
+        )
+        .await
+    }
+
+    /// Build a provider for the same endpoint that signs and fills
+    /// transactions with `wallet`.
+    pub async fn signer_provider(&self, wallet: EthereumWallet) -> Result<DynProvider> {
+        let chain_id = self.chain_id().await?;
+        let provider = ProviderBuilder::new()
+            .wallet(wallet)
+            .with_chain_id(chain_id)
+            .connect_client(transport_client(&self.url, self.timeout, &self.headers)?);
+        Ok(provider.erased())
+    }
+}
+
+/// An alloy JSON-RPC client for `url`, recording its traffic when `--trace`
+/// is set.
+///
+/// The transport gets its own reqwest client with the same timeout and
+/// headers, so gated endpoints work for provider calls too.
+fn transport_client(
+    url: &str,
+    timeout: Duration,
+    headers: &HeaderMap,
+) -> Result<alloy_rpc_client::RpcClient> {
+    let parsed = url
+        .parse()
+        .with_context(|| format!("invalid rpc url {url}"))?;
+    let http = alloy_transport_http::reqwest::Client::builder()
+        .timeout(timeout)
+        .default_headers(headers.clone())
+        .build()
+        .context("failed to build http client")?;
+    let transport = Http::with_client(http, parsed);
+    let is_local = transport.guess_local();
+    Ok(if rpc_trace::enabled() {
+        alloy_rpc_client::RpcClient::new(TracedTransport::new(transport, url), is_local)
+    } else {
+        alloy_rpc_client::RpcClient::new(transport, is_local)
+    })
+}
+
+/// Parse repeated `--rpc-header "Name: Value"` flags into a header map.
+pub fn parse_rpc_headers(values: &[String]) -> Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for value in values {
+        let (name, header_value) = value
+            .split_once(':')
+            .ok_or_else(|| anyhow!("invalid --rpc-header {value:?} (expected \"Name: Value\")"))?;
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .with_context(|| format!("invalid --rpc-header name {:?}", name.trim()))?;
+        let mut header_value = HeaderValue::from_str(header_value.trim())
+            .with_context(|| format!("invalid --rpc-header value for {name}"))?;
+        header_value.set_sensitive(true);
+        headers.append(name, header_value);
+    }
+    Ok(headers)
+}
+
+/// Fee, gas limit, and nonce overrides applied to outgoing transactions.
+#[derive(Debug, Clone, Default)]
+pub struct TxOverrides {
+    pub max_fee_per_gas: Option<u128>,
+    pub max_priority_fee_per_gas: Option<u128>,
+    pub legacy: bool,
+    pub gas_limit: Option<u64>,
+    pub nonce: Option<u64>,
+}
+
+impl TxOverrides {
+    /// Parse the override flags; fees are given in wei.
+    pub fn parse(args: &TxOverrideArgs) -> Result<Self> {
+        let parse_fee = |value: Option<&str>, flag: &str| -> Result<Option<u128>> {
+            value
+                .map(|value| {
+                    let fee = parse_u256(value).with_context(|| format!("invalid {flag}"))?;
+                    u128::try_from(fee).map_err(|_| anyhow!("{flag} too large"))
+                })
+                .transpose()
+        };
+        let options = Self {
+            max_fee_per_gas: parse_fee(args.max_fee_per_gas.as_deref(), "--max-fee-per-gas")?,
+            max_priority_fee_per_gas: parse_fee(
+                args.max_priority_fee_per_gas.as_deref(),
+                "--max-priority-fee-per-gas",
+            )?,
+            legacy: args.legacy,
+            gas_limit: args.gas_limit,
+            nonce: args.nonce,
+        };
+        if let (Some(max_fee), Some(priority_fee)) =
+            (options.max_fee_per_gas, options.max_priority_fee_per_gas)
+        {
+            if priority_fee > max_fee {
+                anyhow::bail!(
+                    "--max-priority-fee-per-gas ({priority_fee}) exceeds --max-fee-per-gas ({max_fee})"
+                );
+            }
+        }
+        Ok(options)
+    }
+
+    /// Warn that a pinned nonce is ignored when nothing is sent.
+    pub fn warn_if_dry_run(&self, dry_run: bool) {
+        if dry_run && self.nonce.is_some() {
+            eprintln!("warning: --nonce has no effect with --dry-run");
+        }
+    }
+
+    /// Set the overridden fields on `request`; anything left unset is filled
+    /// by the provider as before (a set nonce skips the nonce filler).
+    ///
+    /// alloy re-estimates both EIP-1559 fields unless both are set, so a
+    /// single fee override is completed with the RPC's estimate for the other.
+    pub async fn apply(&self, client: &RpcClient, request: &mut TransactionRequest) -> Result<()> {
+        if let Some(gas_limit) = self.gas_limit {
+            request.gas = Some(gas_limit);
+        }
+        if let Some(nonce) = self.nonce {
+            request.nonce = Some(nonce);
+        }
+        if self.legacy {
+            let gas_price = match self.max_fee_per_gas {
+                Some(gas_price) => gas_price,
+                None => client.provider.get_gas_price().await?,
+            };
+            request.gas_price = Some(gas_price);
+            return Ok(());
+        }
+        if self.max_fee_per_gas.is_none() && self.max_priority_fee_per_gas.is_none() {
+            return Ok(());
+        }
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            match (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+                (Some(max_fee), Some(priority_fee)) => (max_fee, priority_fee),
+                (max_fee, priority_fee) => {
+                    let estimate = client.provider.estimate_eip1559_fees().await?;
+                    match (max_fee, priority_fee) {
+                        (Some(max_fee), _) => {
+                            (max_fee, estimate.max_priority_fee_per_gas.min(max_fee))
+                        }
+                        (_, Some(priority_fee)) => {
+                            (estimate.max_fee_per_gas.max(priority_fee), priority_fee)
+                        }
+                        (None, None) => unreachable!("no fee overrides"),
+                    }
+                }
+            };
+        request.max_fee_per_gas = Some(max_fee_per_gas);
+        request.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +342,99 @@ pub async fn get_transaction_receipt(
         .ok_or_else(|| anyhow!("transaction receipt not found"))
 }
 
+/// Poll for the receipt of a transaction that may still be pending.
+pub async fn wait_for_transaction_receipt(
+    client: &RpcClient,
+    tx_hash: B256,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<TransactionReceipt> {
+    let mut client = client.clone();
+    let start = tokio::time::Instant::now();
+    loop {
+        match client.provider.get_transaction_receipt(tx_hash).await {
+            Ok(Some(receipt)) => return Ok(receipt),
+            Ok(None) => {}
+            Err(err) => {
+                client = client.failover(err.into()).await?;
+                continue;
+            }
+        }
+        if start.elapsed() > timeout {
+            anyhow::bail!("transaction receipt not found in time");
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// How long a wait may run: a duration, a number of blocks, or both
+/// (whichever is reached first).
+#[derive(Clone, Copy, Debug)]
+pub struct WaitLimit {
+    pub timeout: Option<Duration>,
+    /// Give up once the polled chain's latest block has advanced this far.
+    pub max_blocks: Option<u64>,
+}
+
+impl WaitLimit {
+    pub fn from_timeout(timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            max_blocks: None,
+        }
+    }
+
+    /// Start the clock; the block count starts at the first check.
+    pub fn start(self) -> WaitClock {
+        WaitClock {
+            limit: self,
+            started: tokio::time::Instant::now(),
+            start_block: None,
+        }
+    }
+}
+
+impl std::fmt::Display for WaitLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.timeout, self.max_blocks) {
+            (Some(timeout), Some(blocks)) => write!(f, "{timeout:?} or {blocks} blocks"),
+            (Some(timeout), None) => write!(f, "{timeout:?}"),
+            (None, Some(blocks)) => write!(f, "{blocks} blocks"),
+            (None, None) => f.write_str("unlimited"),
+        }
+    }
+}
+
+/// A running wait, checked against its `WaitLimit` on every poll.
+pub struct WaitClock {
+    limit: WaitLimit,
+    started: tokio::time::Instant,
+    /// Latest block when the wait started; read on the first check.
+    start_block: Option<u64>,
+}
+
+impl WaitClock {
+    /// Whether the wait has run out. The block limit is measured on the
+    /// chain of `client`; a failed block lookup never ends the wait.
+    pub async fn expired(&mut self, client: &RpcClient) -> bool {
+        if self
+            .limit
+            .timeout
+            .is_some_and(|timeout| self.started.elapsed() > timeout)
+        {
+            return true;
+        }
+        let Some(max_blocks) = self.limit.max_blocks else {
+            return false;
+        };
+        let Ok(latest) = client.provider.get_block_number().await else {
+            return false;
+        };
+        let start_block = *self.start_block.get_or_insert(latest);
+        latest.saturating_sub(start_block) >= max_blocks
+    }
+}
+
 pub async fn get_finalized_block_number(client: &RpcClient) -> Result<u64> {
     let block = client
         .provider
@@ -62,20 +447,120 @@ pub async fn get_finalized_block_number(client: &RpcClient) -> Result<u64> {
 pub async fn wait_for_finalized_block(
     client: &RpcClient,
     block_number: u64,
-    timeout: Duration,
+    limit: WaitLimit,
     poll_interval: Duration,
 ) -> Result<()> {
-    let start = tokio::time::Instant::now();
+    let mut client = client.clone();
+    let mut clock = limit.start();
+    let mut last_error = None;
     loop {
-        let finalized = get_finalized_block_number(client).await.unwrap_or(0);
-        if finalized >= block_number {
-            return Ok(());
+        match get_finalized_block_number(&client).await {
+            Ok(finalized) if finalized >= block_number => return Ok(()),
+            Ok(_) => last_error = None,
+            Err(err) => {
+                let unsupported = is_finalized_tag_unsupported(&err);
+                let url = client.url.clone();
+                match client.failover(err).await {
+                    Ok(next) => client = next,
+                    // Retrying cannot help once no endpoint is left that
+                    // serves finalized blocks.
+                    Err(err) if unsupported => anyhow::bail!(
+                        "rpc {url} does not serve the finalized block tag ({err:#}); use a zkSync RPC that does"
+                    ),
+                    Err(err) => last_error = Some(err),
+                }
+            }
         }
-        if start.elapsed() > timeout {
-            anyhow::bail!("block was not finalized in time");
+        if clock.expired(&client).await {
+            match last_error {
+                Some(err) => anyhow::bail!("block was not finalized in time (last error: {err:#})"),
+                None => anyhow::bail!("block was not finalized in time"),
+            }
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Wait until the block holding `receipt` is at least `confirmations` blocks
+/// behind the latest one, then check the transaction is still in that block.
+///
+/// Guards against proving a receipt the sequencer later reorgs away; this is
+/// separate from, and usually much shorter than, L1 finalization.
+pub async fn wait_for_confirmations(
+    client: &RpcClient,
+    receipt: &TransactionReceipt,
+    confirmations: u64,
+    limit: WaitLimit,
+    poll_interval: Duration,
+) -> Result<()> {
+    if confirmations == 0 {
+        return Ok(());
+    }
+    let tx_hash = receipt.transaction_hash;
+    let block_number = receipt
+        .block_number
+        .ok_or_else(|| anyhow!("missing receipt block number"))?;
+    let target = block_number.saturating_add(confirmations);
+    let mut client = client.clone();
+    let mut clock = limit.start();
+    loop {
+        match client.provider.get_block_number().await {
+            Ok(latest) if latest >= target => break,
+            Ok(_) => {}
+            Err(err) => {
+                client = client.failover(err.into()).await?;
+                continue;
+            }
+        }
+        if clock.expired(&client).await {
+            anyhow::bail!(
+                "block {block_number} did not reach {confirmations} confirmations in time"
+            );
         }
         tokio::time::sleep(poll_interval).await;
     }
+
+    let current = client.provider.get_transaction_receipt(tx_hash).await?;
+    match current {
+        Some(current) if current.block_hash == receipt.block_hash => Ok(()),
+        Some(current) => anyhow::bail!(
+            "transaction {tx_hash:#x} was reorged out of block {block_number} ({}) into block {} ({}); rerun against the new block",
+            receipt.block_hash.unwrap_or_default(),
+            current
+                .block_number
+                .map_or_else(|| "unknown".to_string(), |number| number.to_string()),
+            current.block_hash.unwrap_or_default()
+        ),
+        None => anyhow::bail!(
+            "transaction {tx_hash:#x} was reorged out of block {block_number} and is no longer mined"
+        ),
+    }
+}
+
+/// Whether a finalized block query failed because the node does not know the
+/// `finalized` tag at all, as opposed to a transient failure worth retrying.
+fn is_finalized_tag_unsupported(err: &anyhow::Error) -> bool {
+    if is_method_not_found(err) {
+        return true;
+    }
+    let message = format!("{err:#}").to_lowercase();
+    message.contains("block tag")
+        || (message.contains("finalized")
+            && (message.contains("not supported")
+                || message.contains("unsupported")
+                || message.contains("invalid")))
+}
+
+/// Await a long-running wait, turning Ctrl-C into an error that says where
+/// the command stopped and how to pick it back up.
+pub async fn interruptible<T>(
+    wait: impl std::future::Future<Output = Result<T>>,
+    stopped: impl FnOnce() -> String,
+) -> Result<T> {
+    tokio::select! {
+        result = wait => result,
+        _ = tokio::signal::ctrl_c() => Err(anyhow!("interrupted: {}", stopped())),
+    }
 }
 
 pub async fn get_log_proof(
@@ -92,16 +577,22 @@ pub async fn wait_for_log_proof(
     client: &RpcClient,
     tx_hash: B256,
     msg_index: u32,
-    timeout: Duration,
+    limit: WaitLimit,
     poll_interval: Duration,
 ) -> Result<LogProof> {
-    let start = tokio::time::Instant::now();
+    let mut client = client.clone();
+    let mut clock = limit.start();
     loop {
-        if let Some(proof) = get_log_proof(client, tx_hash, msg_index).await? {
-            return Ok(proof);
+        match get_log_proof(&client, tx_hash, msg_index).await {
+            Ok(Some(proof)) => return Ok(proof),
+            Ok(None) => {}
+            Err(err) => {
+                client = client.failover(err).await?;
+                continue;
+            }
         }
-        if start.elapsed() > timeout {
-            anyhow::bail!("log proof not available in time");
+        if clock.expired(&client).await {
+            return Err(CastInteropError::ProofNotReady { tx_hash, msg_index }.into());
         }
         tokio::time::sleep(poll_interval).await;
     }
@@ -118,27 +609,102 @@ pub async fn raw_rpc<T: for<'de> Deserialize<'de>>(
         "method": method,
         "params": params,
     });
+    let rpc = rpc_trace::endpoint(&client.url);
+    rpc_trace::request(&rpc, &payload);
+    let started = std::time::Instant::now();
     let response = client
         .http
         .post(&client.url)
         .json(&payload)
         .send()
         .await
-        .context("rpc request failed")?;
+        .map_err(|err| {
+            rpc_trace::response(&rpc, started, Err(err.to_string()));
+            if err.is_timeout() {
+                anyhow!(err).context(format!(
+                    "rpc request {method} timed out after {}ms",
+                    client.timeout.as_millis()
+                ))
+            } else {
+                anyhow!(err).context("rpc request failed")
+            }
+        })?;
     let status = response.status();
     let value: serde_json::Value = response.json().await.context("rpc decode failed")?;
+    rpc_trace::response(&rpc, started, Ok(&value));
     if !status.is_success() {
-        anyhow::bail!("rpc error status {status}: {value}");
+        return Err(CastInteropError::Rpc {
+            method: method.to_string(),
+            code: None,
+            message: format!("status {status}: {value}"),
+        }
+        .into());
     }
     if let Some(error) = value.get("error") {
-        anyhow::bail!("rpc error: {error}");
+        return Err(CastInteropError::Rpc {
+            method: method.to_string(),
+            code: error.get("code").and_then(|code| code.as_i64()),
+            message: error
+                .get("message")
+                .and_then(|message| message.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string()),
+        }
+        .into());
     }
     serde_json::from_value(value.get("result").cloned().unwrap_or_default())
         .context("rpc missing result")
 }
 
+/// Parse a `--block` value: a decimal or 0x-prefixed block number, or one of
+/// the tags latest, safe, finalized, earliest, pending.
+pub fn parse_block_id(value: &str) -> Result<BlockId> {
+    if let Ok(number) = value.parse::<u64>() {
+        return Ok(BlockId::number(number));
+    }
+    value.parse::<BlockNumberOrTag>().map(BlockId::Number).map_err(|_| {
+        anyhow!("invalid block {value} (expected a number or latest, safe, finalized, earliest, pending)")
+    })
+}
+
+/// Whether a [`raw_rpc`] error means the node does not implement the method.
+/// Whether `err` means the endpoint could not be reached or answered at the
+/// HTTP level, as opposed to the node rejecting the request.
+fn is_transport_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(err) = cause.downcast_ref::<alloy_transport::TransportError>() {
+            return matches!(err, alloy_json_rpc::RpcError::Transport(_));
+        }
+        if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+            return !err.is_decode();
+        }
+        matches!(
+            cause.downcast_ref::<CastInteropError>(),
+            Some(CastInteropError::Rpc { code: None, .. })
+        )
+    })
+}
+
+pub fn is_method_not_found(err: &anyhow::Error) -> bool {
+    if let Some(CastInteropError::Rpc { code, .. }) = err.downcast_ref() {
+        return *code == Some(-32601);
+    }
+    let message = err.to_string();
+    message.contains("-32601") || message.to_lowercase().contains("method not found")
+}
+
 pub async fn eth_call(client: &RpcClient, to: Address, data: Bytes) -> Result<Bytes> {
-    eth_call_with_value(client, to, data, None).await
+    eth_call_with_value(client, to, data, None, None).await
+}
+
+/// `eth_call` against the state at `block` (latest when None).
+pub async fn eth_call_at(
+    client: &RpcClient,
+    to: Address,
+    data: Bytes,
+    block: Option<BlockId>,
+) -> Result<Bytes> {
+    eth_call_with_value(client, to, data, None, block).await
 }
 
 pub async fn eth_call_with_value(
@@ -146,6 +712,7 @@ pub async fn eth_call_with_value(
     to: Address,
     data: Bytes,
     value: Option<alloy_primitives::U256>,
+    block: Option<BlockId>,
 ) -> Result<Bytes> {
     let request = TransactionRequest {
         to: Some(to.into()),
@@ -153,13 +720,17 @@ pub async fn eth_call_with_value(
         value,
         ..Default::default()
     };
-    let result = client.provider.call(request).await;
+    let result = client
+        .provider
+        .call(request)
+        .block(block.unwrap_or_default())
+        .await;
 
     let result = match result {
         Ok(result) => result,
         Err(err) => {
-            if let Some(reason) = decode_revert_reason(err.to_string()) {
-                return Err(anyhow!("dry-run reverted: {reason}"));
+            if let Some(revert) = CastInteropError::revert("dry-run", &err.to_string()) {
+                return Err(revert.into());
             } else {
                 return Err(anyhow!("dry-run failed: {err}"));
             }
@@ -168,24 +739,134 @@ pub async fn eth_call_with_value(
     Ok(result)
 }
 
-/*
+/// Estimate gas for a call, decoding the revert reason if estimation fails.
 pub async fn estimate_gas(
     client: &RpcClient,
-    from: Address,
+    from: Option<Address>,
     to: Address,
     data: Bytes,
 ) -> Result<u64> {
     let request = TransactionRequest {
-        from: Some(from),
+        from,
         to: Some(to.into()),
         input: TransactionInput::new(data),
         ..Default::default()
     };
-    Ok(client.provider.estimate_gas(request).await?)
+    match client.provider.estimate_gas(request).await {
+        Ok(gas) => Ok(gas),
+        Err(err) => match CastInteropError::revert("gas estimation", &err.to_string()) {
+            Some(revert) => Err(revert.into()),
+            None => Err(anyhow!("gas estimation failed: {err}")),
+        },
+    }
 }
 
+/*
 pub async fn send_raw_transaction(client: &RpcClient, raw_tx: Bytes) -> Result<B256> {
     let tx = client.provider.send_raw_transaction(&raw_tx).await?;
     Ok(tx.tx_hash().clone())
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve `eth_chainId` answers of `chain_id` on a local port.
+    async fn chain_id_server(chain_id: u64) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                let body = loop {
+                    let read = socket.read(&mut buf).await.unwrap_or(0);
+                    if read == 0 {
+                        break None;
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let len = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= len {
+                            break Some(body.to_string());
+                        }
+                    }
+                };
+                let Some(body) = body else { continue };
+                let request: serde_json::Value = serde_json::from_str(&body).unwrap();
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": format!("{chain_id:#x}"),
+                })
+                .to_string();
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+                    response.len()
+                );
+                let _ = socket.write_all(reply.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    fn resolved(url: &str, fallbacks: Vec<String>, chain_id: Option<u64>) -> ResolvedRpc {
+        ResolvedRpc {
+            url: url.to_string(),
+            alias: Some("era".to_string()),
+            chain_id,
+            fallbacks,
+        }
+    }
+
+    #[tokio::test]
+    async fn fallback_on_another_chain_is_rejected() {
+        let fallback = chain_id_server(1).await;
+        // Nothing listens on port 1, so the primary is unreachable.
+        let rpc = resolved("http://127.0.0.1:1", vec![fallback.clone()], Some(324));
+        let err = RpcClient::for_rpc(&rpc, Duration::from_secs(5), &HeaderMap::new())
+            .await
+            .err()
+            .expect("fallback on chain 1 must not be used");
+        assert!(err.to_string().contains("expected 324"), "{err:#}");
+
+        let rpc = resolved("http://127.0.0.1:1", vec![fallback], Some(1));
+        let client = RpcClient::for_rpc(&rpc, Duration::from_secs(5), &HeaderMap::new())
+            .await
+            .unwrap();
+        assert_eq!(client.chain_id().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn json_rpc_errors_do_not_fail_over() {
+        let client = RpcClient::connect(
+            "http://127.0.0.1:1",
+            Duration::from_secs(5),
+            &HeaderMap::new(),
+            vec!["http://127.0.0.1:2".to_string()],
+            None,
+        )
+        .unwrap();
+        let revert = CastInteropError::Rpc {
+            method: "eth_call".to_string(),
+            code: Some(3),
+            message: "execution reverted".to_string(),
+        };
+        let err = client.failover(revert.into()).await.err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<CastInteropError>(),
+            Some(CastInteropError::Rpc { code: Some(3), .. })
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+//! `--trace`: record every JSON-RPC request and response, both from
+//! [`crate::rpc::raw_rpc`] and from alloy providers.
+//!
+//! Records go to the `cast_interop::rpc_trace` tracing target at debug level,
+//! or with `--trace-file` to a file as one JSON object per line. Only the
+//! endpoint's host is recorded, since RPC URLs often embed API keys, and
+//! registered secrets (the signer's private key) are masked.
+
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_transport::{Transport, TransportError, TransportFut};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context as TaskContext, Poll};
+use std::time::Instant;
+use tower::Service;
+
+/// Where trace records go once `--trace` is set.
+enum Sink {
+    Log,
+    File(Mutex<File>),
+}
+
+static SINK: OnceLock<Sink> = OnceLock::new();
+static SECRETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Turn tracing on, writing to `file` (truncated) when given.
+pub fn init(file: Option<&Path>) -> Result<()> {
+    let sink = match file {
+        Some(path) => {
+            Sink::File(Mutex::new(File::create(path).with_context(|| {
+                format!("failed to create trace file {}", path.display())
+            })?))
+        }
+        None => Sink::Log,
+    };
+    let _ = SINK.set(sink);
+    Ok(())
+}
+
+pub fn enabled() -> bool {
+    SINK.get().is_some()
+}
+
+/// Mask `secret` (hex, with or without `0x`) in every later trace record.
+pub fn redact(secret: &str) {
+    let secret = secret.trim().trim_start_matches("0x");
+    if secret.is_empty() {
+        return;
+    }
+    let mut secrets = SECRETS.lock().unwrap_or_else(|err| err.into_inner());
+    secrets.push(secret.to_string());
+    secrets.push(secret.to_ascii_lowercase());
+}
+
+/// The host (and port) of `url`, the only part of it that is traced.
+pub fn endpoint(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|parsed| {
+            let host = parsed.host_str()?.to_string();
+            Some(match parsed.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host,
+            })
+        })
+        .unwrap_or_else(|| "<rpc>".to_string())
+}
+
+/// Record a request about to be sent to `rpc`.
+pub fn request(rpc: &str, body: &Value) {
+    write(json!({ "rpc": rpc, "request": body }));
+}
+
+/// Record the response (or transport error) to a request sent at `started`.
+pub fn response(rpc: &str, started: Instant, outcome: Result<&Value, String>) {
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match outcome {
+        Ok(body) => write(json!({ "rpc": rpc, "elapsedMs": elapsed_ms, "response": body })),
+        Err(err) => write(json!({ "rpc": rpc, "elapsedMs": elapsed_ms, "error": err })),
+    }
+}
+
+fn write(record: Value) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    let mut line = record.to_string();
+    for secret in SECRETS.lock().unwrap_or_else(|err| err.into_inner()).iter() {
+        line = line.replace(secret.as_str(), "<redacted>");
+    }
+    match sink {
+        Sink::Log => tracing::debug!("{line}"),
+        Sink::File(file) => {
+            let mut file = file.lock().unwrap_or_else(|err| err.into_inner());
+            if let Err(err) = writeln!(file, "{line}") {
+                tracing::warn!("failed to write trace record: {err}");
+            }
+        }
+    }
+}
+
+/// Wraps a provider transport to record its traffic.
+#[derive(Clone)]
+pub struct TracedTransport<T> {
+    inner: T,
+    rpc: Arc<str>,
+}
+
+impl<T> TracedTransport<T> {
+    pub fn new(inner: T, url: &str) -> Self {
+        Self {
+            inner,
+            rpc: endpoint(url).into(),
+        }
+    }
+}
+
+impl<T: Transport + Clone> Service<RequestPacket> for TracedTransport<T> {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, packet: RequestPacket) -> Self::Future {
+        request(
+            &self.rpc,
+            &serde_json::to_value(&packet).unwrap_or_default(),
+        );
+        let started = Instant::now();
+        let rpc = self.rpc.clone();
+        let future = self.inner.call(packet);
+        Box::pin(async move {
+            let result = future.await;
+            match &result {
+                Ok(ResponsePacket::Single(body)) => {
+                    response(&rpc, started, Ok(&json!(body)));
+                }
+                Ok(ResponsePacket::Batch(bodies)) => {
+                    response(&rpc, started, Ok(&json!(bodies)));
+                }
+                Err(err) => response(&rpc, started, Err(err.to_string())),
+            }
+            result
+        })
+    }
+}
@@ -1,20 +1,40 @@
 use crate::config::Config;
 use alloy_primitives::Address;
+use alloy_provider::network::{Ethereum, EthereumWallet, NetworkWallet};
 use alloy_signer_local::PrivateKeySigner;
 use anyhow::{anyhow, Result};
 
 pub struct SignerOptions<'a> {
     pub private_key: Option<&'a str>,
     pub private_key_env: Option<&'a str>,
+    pub ledger: bool,
+    pub ledger_account: Option<usize>,
 }
 
-pub fn load_signer(
+/// Load the transaction signer selected by the signer flags.
+///
+/// The returned wallet wraps either a local private key or a Ledger device,
+/// so callers can hand it to a provider without caring about the backend.
+/// Under `--dry-run` nothing is signed, so the Ledger is not opened and no
+/// signer is returned for it.
+pub async fn load_signer(
     options: SignerOptions<'_>,
     config: &Config,
-) -> Result<Option<PrivateKeySigner>> {
+) -> Result<Option<EthereumWallet>> {
     if options.private_key.is_some() && options.private_key_env.is_some() {
         anyhow::bail!("cannot set both --private-key and --private-key-env");
     }
+    if options.ledger {
+        if options.private_key.is_some() || options.private_key_env.is_some() {
+            anyhow::bail!("cannot combine --ledger with --private-key or --private-key-env");
+        }
+        if config.dry_run {
+            return Ok(None);
+        }
+        return Ok(Some(
+            load_ledger(options.ledger_account.unwrap_or(0)).await?,
+        ));
+    }
 
     let env = options
         .private_key_env
@@ -22,21 +42,116 @@ pub fn load_signer(
         .unwrap_or_else(|| config.signer_env());
 
     if let Some(key) = options.private_key {
-        return Ok(Some(load_wallet(key)?));
+        return Ok(Some(EthereumWallet::from(load_wallet(key)?)));
     }
     if let Ok(key) = std::env::var(env) {
-        return Ok(Some(load_wallet(&key)?));
+        return Ok(Some(EthereumWallet::from(load_wallet(&key)?)));
     }
     Ok(None)
 }
 
-pub fn signer_address(signer: &PrivateKeySigner) -> Result<Address> {
-    Ok(signer.address())
+pub fn signer_address(signer: &EthereumWallet) -> Result<Address> {
+    Ok(NetworkWallet::<Ethereum>::default_signer_address(signer))
 }
 
 fn load_wallet(key: &str) -> Result<PrivateKeySigner> {
+    crate::rpc_trace::redact(key);
     let pk_signer: PrivateKeySigner = key
         .parse()
         .map_err(|err| anyhow!("invalid private key: {err}"))?;
     Ok(pk_signer)
 }
+
+/// Connect to a Ledger device and derive the Ledger Live account at `index`.
+///
+/// The Ethereum app is asked to display the derived address, and the signer is
+/// only returned once the user approves it on the device.
+#[cfg(feature = "ledger")]
+async fn load_ledger(index: usize) -> Result<EthereumWallet> {
+    use alloy_signer::Signer;
+    use alloy_signer_ledger::coins_ledger::{transports::LedgerAsync, Ledger};
+    use alloy_signer_ledger::{HDPath, LedgerSigner};
+    use futures_util::lock::Mutex;
+    use std::sync::Arc;
+
+    let transport = Ledger::init()
+        .await
+        .map_err(|err| anyhow!("failed to connect to ledger: {err}"))?;
+    let transport = Arc::new(Mutex::new(transport));
+    let signer =
+        LedgerSigner::new_with_transport(HDPath::LedgerLive(index), None, transport.clone())
+            .await
+            .map_err(|err| anyhow!("failed to read ledger address: {err}"))?;
+    let address = signer.address();
+    eprintln!("ledger account {index}: {address:#x} (approve this address on your device)");
+    let confirmed = confirm_ledger_address(&*transport.lock().await, index).await?;
+    if confirmed != address {
+        anyhow::bail!("ledger confirmed {confirmed:#x} but derived {address:#x}");
+    }
+    Ok(EthereumWallet::from(signer))
+}
+
+/// Ask the Ethereum app to show the Ledger Live address at `index` and wait
+/// for the user to approve it (GET_PUBLIC_KEY with the confirm flag set).
+#[cfg(feature = "ledger")]
+async fn confirm_ledger_address(
+    transport: &alloy_signer_ledger::coins_ledger::Ledger,
+    index: usize,
+) -> Result<Address> {
+    use alloy_signer_ledger::coins_ledger::{
+        common::APDUData, transports::LedgerAsync, APDUCommand,
+    };
+
+    let account =
+        u32::try_from(index).map_err(|_| anyhow!("ledger account {index} is too large"))?;
+    let mut path = vec![5u8];
+    for element in [
+        44 | 0x8000_0000,
+        60 | 0x8000_0000,
+        account | 0x8000_0000,
+        0,
+        0,
+    ] {
+        path.extend_from_slice(&u32::to_be_bytes(element));
+    }
+    let command = APDUCommand {
+        cla: 0xe0,
+        ins: 0x02,
+        p1: 0x01,
+        p2: 0x00,
+        data: APDUData::new(&path),
+        response_len: None,
+    };
+    let answer = transport
+        .exchange(&command)
+        .await
+        .map_err(|err| anyhow!("failed to confirm ledger address: {err}"))?;
+    if !answer.is_success() {
+        anyhow::bail!(
+            "ledger address was not approved on the device (status {:#06x})",
+            answer.retcode()
+        );
+    }
+    let data = answer
+        .data()
+        .ok_or_else(|| anyhow!("ledger returned no address"))?;
+    let offset = 1 + usize::from(*data.first().unwrap_or(&0));
+    let len = usize::from(
+        *data
+            .get(offset)
+            .ok_or_else(|| anyhow!("ledger returned a truncated address"))?,
+    );
+    let hex = data
+        .get(offset + 1..offset + 1 + len)
+        .ok_or_else(|| anyhow!("ledger returned a truncated address"))?;
+    let hex =
+        std::str::from_utf8(hex).map_err(|_| anyhow!("ledger returned a malformed address"))?;
+    format!("0x{hex}")
+        .parse()
+        .map_err(|err| anyhow!("ledger returned a malformed address: {err}"))
+}
+
+#[cfg(not(feature = "ledger"))]
+async fn load_ledger(_index: usize) -> Result<EthereumWallet> {
+    anyhow::bail!("ledger support is not enabled in this build (rebuild with --features ledger)")
+}
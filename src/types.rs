@@ -1,22 +1,34 @@
 use alloy_primitives::{address, Address, Bytes, B256, U256};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::io::{BufRead, IsTerminal, Write};
 use std::str::FromStr;
 
 pub const DEFAULT_INTEROP_CENTER: &str = "0x0000000000000000000000000000000000010010";
 pub const DEFAULT_INTEROP_HANDLER: &str = "0x000000000000000000000000000000000001000d";
 pub const DEFAULT_INTEROP_ROOT_STORAGE: &str = "0x0000000000000000000000000000000000010008";
 pub const DEFAULT_ASSET_ROUTER: &str = "0x0000000000000000000000000000000000010003";
+pub const DEFAULT_RPC_TIMEOUT_MS: u64 = 30_000;
+pub const DEFAULT_WAIT_TIMEOUT_MS: u64 = 300_000;
+pub const DEFAULT_POLL_MS: u64 = 1_000;
 pub const BUNDLE_IDENTIFIER: u8 = 0x01;
 
 pub const L1_SENDER_ADDRESS: Address = address!("0000000000000000000000000000000000008008");
 pub const INTEROP_CENTER_ADDRESS: Address = address!("0000000000000000000000000000000000010010");
+pub const L2_BRIDGEHUB_ADDRESS: Address = address!("0000000000000000000000000000000000010002");
 
 #[derive(Clone, Debug)]
 pub struct AddressBook {
     pub interop_center: Address,
     pub interop_handler: Address,
     pub interop_root_storage: Address,
+    /// Token commands' default for `--native-token-vault`.
+    pub native_token_vault: Address,
+    /// Token commands' default for `--asset-router`.
+    pub asset_router: Address,
+    /// Emitter of `L1MessageSent` events, matched by `debug tx`, `debug logs`,
+    /// `debug messages` and proof selection, and hashed into message leaves.
+    pub l1_messenger: Address,
 }
 
 impl AddressBook {
@@ -38,11 +50,36 @@ impl AddressBook {
             .map(|value| value.to_string())
             .or_else(|| config.addresses.as_ref()?.interop_root_storage.clone())
             .unwrap_or_else(|| DEFAULT_INTEROP_ROOT_STORAGE.to_string());
+        let native_token_vault = config
+            .addresses
+            .as_ref()
+            .and_then(|addresses| addresses.native_token_vault.clone())
+            .unwrap_or_else(|| crate::encode::DEFAULT_NATIVE_TOKEN_VAULT.to_string());
+        let asset_router = config
+            .addresses
+            .as_ref()
+            .and_then(|addresses| addresses.asset_router.clone())
+            .unwrap_or_else(|| DEFAULT_ASSET_ROUTER.to_string());
+        let l1_messenger = match config
+            .addresses
+            .as_ref()
+            .and_then(|addresses| addresses.l1_messenger.as_deref())
+        {
+            Some(value) => {
+                parse_checked_address(value).context("invalid config addresses.l1_messenger")?
+            }
+            None => L1_SENDER_ADDRESS,
+        };
 
         Ok(Self {
-            interop_center: parse_address(&center)?,
-            interop_handler: parse_address(&handler)?,
-            interop_root_storage: parse_address(&root_storage)?,
+            interop_center: parse_checked_address(&center)?,
+            interop_handler: parse_checked_address(&handler)?,
+            interop_root_storage: parse_checked_address(&root_storage)?,
+            native_token_vault: parse_checked_address(&native_token_vault)
+                .context("invalid config addresses.native_token_vault")?,
+            asset_router: parse_checked_address(&asset_router)
+                .context("invalid config addresses.asset_router")?,
+            l1_messenger,
         })
     }
 }
@@ -51,6 +88,23 @@ pub fn parse_address(value: &str) -> Result<Address> {
     Address::from_str(value).map_err(|err| anyhow!("invalid address {value}: {err}"))
 }
 
+/// Parse a user-supplied address, warning when it is mixed-case but fails
+/// its EIP-55 checksum (likely a typo). All-lowercase and all-uppercase
+/// input is accepted without a check.
+pub fn parse_checked_address(value: &str) -> Result<Address> {
+    let address = parse_address(value)?;
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    let mixed_case = digits.chars().any(|c| c.is_ascii_lowercase())
+        && digits.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case && Address::parse_checksummed(value, None).is_err() {
+        eprintln!(
+            "warning: address {value} fails its EIP-55 checksum (expected {}); check it for typos",
+            address.to_checksum(None)
+        );
+    }
+    Ok(address)
+}
+
 pub fn parse_b256(value: &str) -> Result<B256> {
     B256::from_str(value).map_err(|err| anyhow!("invalid bytes32 {value}: {err}"))
 }
@@ -59,6 +113,85 @@ pub fn parse_u256(value: &str) -> Result<U256> {
     U256::from_str(value).map_err(|err| anyhow!("invalid uint256 {value}: {err}"))
 }
 
+/// Parse a human-readable decimal token amount into wei.
+///
+/// Enforces that fractional digits do not exceed the token decimals.
+pub fn parse_decimal_amount(amount: &str, decimals: u32) -> Result<U256> {
+    let trimmed = amount.trim();
+    let mut parts = trimmed.split('.');
+    let whole_part = parts.next().unwrap_or("0");
+    let fraction_part = parts.next();
+    if parts.next().is_some() {
+        anyhow::bail!("invalid amount {amount}");
+    }
+
+    let whole = if whole_part.is_empty() {
+        U256::ZERO
+    } else {
+        parse_u256(whole_part)?
+    };
+    let base = pow10(decimals)?;
+    let mut value = whole * base;
+
+    if let Some(fraction_part) = fraction_part {
+        if fraction_part.len() > decimals as usize {
+            anyhow::bail!("amount has too many decimal places (max {decimals})");
+        }
+        if !fraction_part.is_empty() {
+            let fraction = parse_u256(fraction_part)?;
+            let scale = pow10(decimals - fraction_part.len() as u32)?;
+            value += fraction * scale;
+        }
+    }
+
+    Ok(value)
+}
+
+/// Compute 10^exp with overflow protection.
+pub fn pow10(exp: u32) -> Result<U256> {
+    let mut value = U256::from(1u64);
+    for _ in 0..exp {
+        value = value
+            .checked_mul(U256::from(10u64))
+            .ok_or_else(|| anyhow!("amount overflow"))?;
+    }
+    Ok(value)
+}
+
+/// Parse a wei value, optionally with a `wei`, `gwei`, or `ether` suffix.
+///
+/// Bare integers (decimal or 0x-hex) are taken as wei; suffixed values may be
+/// fractional as long as they resolve to a whole number of wei.
+pub fn parse_value_with_units(value: &str) -> Result<U256> {
+    let trimmed = value.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    let (amount, decimals) = if let Some(amount) = lower.strip_suffix("gwei") {
+        (amount, 9)
+    } else if let Some(amount) = lower.strip_suffix("ether") {
+        (amount, 18)
+    } else if let Some(amount) = lower.strip_suffix("wei") {
+        (amount, 0)
+    } else {
+        return parse_u256(trimmed);
+    };
+    let amount = amount.trim();
+    if amount.is_empty() {
+        anyhow::bail!("invalid value {value}: missing amount");
+    }
+    if let Some((_, fraction)) = amount.split_once('.') {
+        if fraction.len() > decimals as usize {
+            anyhow::bail!("invalid value {value}: resolves to a fractional amount of wei");
+        }
+    }
+    parse_decimal_amount(amount, decimals).with_context(|| format!("invalid value {value}"))
+}
+
+/// The L2→L1 message a bundle is sent as: the bundle identifier byte
+/// followed by the encoded bundle.
+pub fn bundle_message(identifier: u8, encoded_bundle: &[u8]) -> Vec<u8> {
+    [&[identifier][..], encoded_bundle].concat()
+}
+
 pub fn format_hex(bytes: &[u8]) -> String {
     format!("0x{}", hex::encode(bytes))
 }
@@ -77,6 +210,104 @@ pub fn require_signer_or_dry_run(has_signer: bool, dry_run: bool, cmd: &str) ->
     Ok(())
 }
 
+/// Ask before broadcasting a value-bearing transaction, showing `summary` as
+/// `label: value` lines on stderr.
+///
+/// `yes` (`--yes`) skips the prompt. Without it a non-terminal stdin fails
+/// instead of waiting for an answer that never comes.
+pub fn confirm_broadcast(action: &str, summary: &[(&str, String)], yes: bool) -> Result<()> {
+    if yes {
+        return Ok(());
+    }
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        anyhow::bail!("{action} needs confirmation but stdin is not a terminal; pass --yes to send without asking");
+    }
+    let mut stderr = std::io::stderr().lock();
+    writeln!(stderr, "about to {action}:")?;
+    for (label, value) in summary {
+        writeln!(stderr, "  {label}: {value}")?;
+    }
+    write!(stderr, "send? [y/N] ")?;
+    stderr.flush()?;
+    let mut answer = String::new();
+    stdin
+        .lock()
+        .read_line(&mut answer)
+        .context("failed to read confirmation")?;
+    if !matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes") {
+        anyhow::bail!("aborted; nothing was sent");
+    }
+    Ok(())
+}
+
+/// Refuse to send to the source chain itself, most likely a copy-paste
+/// slip in the destination; `allow` (`--allow-same-chain`) only warns.
+pub fn check_distinct_chains(source: u64, destination: U256, allow: bool) -> Result<()> {
+    if destination != U256::from(source) {
+        return Ok(());
+    }
+    if !allow {
+        anyhow::bail!(
+            "destination chain {destination} is the source chain; pass --allow-same-chain if this is intended"
+        );
+    }
+    eprintln!("warning: destination chain {destination} is the source chain");
+    Ok(())
+}
+
+/// Failing checks in a `debug doctor` or `bundle explain` report.
+///
+/// Returned once the full report is printed; `Cli::run` maps it to the
+/// process exit code so CI can gate on the report.
+#[derive(Debug)]
+pub struct ChecksFailed {
+    pub failed: usize,
+    pub warned: usize,
+}
+
+impl ChecksFailed {
+    /// Fail on any "fail" status, and on "warn" too when `fail_on_warn` is set.
+    pub fn check<'a>(
+        statuses: impl IntoIterator<Item = &'a str>,
+        fail_on_warn: bool,
+    ) -> Result<()> {
+        let (mut failed, mut warned) = (0, 0);
+        for status in statuses {
+            match status {
+                "fail" => failed += 1,
+                "warn" => warned += 1,
+                _ => {}
+            }
+        }
+        if failed > 0 || (fail_on_warn && warned > 0) {
+            return Err(ChecksFailed { failed, warned }.into());
+        }
+        Ok(())
+    }
+
+    /// 2 when a check failed, 3 when only warnings tripped `--fail-on-warn`.
+    pub fn exit_code(&self) -> u8 {
+        if self.failed > 0 {
+            2
+        } else {
+            3
+        }
+    }
+}
+
+impl std::fmt::Display for ChecksFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.failed > 0 {
+            write!(f, "{} check(s) failed", self.failed)
+        } else {
+            write!(f, "{} check(s) warned (--fail-on-warn)", self.warned)
+        }
+    }
+}
+
+impl std::error::Error for ChecksFailed {}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProofMessage {
@@ -94,6 +325,85 @@ pub struct MessageInclusionProof {
     pub root: String,
     pub message: ProofMessage,
     pub proof: Vec<String>,
+    /// keccak256 of the proven message, as emitted in L1MessageSent and
+    /// InteropBundleSent. Informational; not part of the on-chain proof.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l2l1_msg_hash: Option<String>,
+}
+
+impl MessageInclusionProof {
+    /// Check the fields that `bundle verify`/`execute` rely on, so a
+    /// malformed proof file fails with a clear message instead of deep
+    /// inside ABI encoding.
+    pub fn validate(&self) -> Result<()> {
+        if self.chain_id.is_empty() || !self.chain_id.bytes().all(|b| b.is_ascii_digit()) {
+            anyhow::bail!(
+                "invalid proof: chainId must be a decimal string, got {:?}",
+                self.chain_id
+            );
+        }
+        if !is_bytes32_hex(&self.root) {
+            anyhow::bail!(
+                "invalid proof: root must be a 0x-prefixed 32-byte hex string, got {:?}",
+                self.root
+            );
+        }
+        if self.proof.is_empty() {
+            anyhow::bail!(
+                "invalid proof: proof array is empty (fetch it again with `debug proof`)"
+            );
+        }
+        if let Some((index, node)) = self
+            .proof
+            .iter()
+            .enumerate()
+            .find(|(_, node)| !is_bytes32_hex(node))
+        {
+            anyhow::bail!(
+                "invalid proof: proof[{index}] must be a 0x-prefixed 32-byte hex string, got {node:?}"
+            );
+        }
+        if u16::try_from(self.message.tx_number_in_batch).is_err() {
+            anyhow::bail!(
+                "invalid proof: message.txNumberInBatch {} does not fit in uint16",
+                self.message.tx_number_in_batch
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that the proof is for `message`, the L2→L1 message of the
+    /// bundle it is used with.
+    ///
+    /// `debug proof` leaves `message.data` as `0x` for the caller to fill in
+    /// from the bundle, so only a non-empty `data`, and an `l2l1MsgHash`
+    /// when present, are compared.
+    pub fn check_message(&self, message: &[u8]) -> Result<()> {
+        let data = bytes_from_hex(&self.message.data).context("invalid proof message.data")?;
+        if !data.is_empty() && data.as_ref() != message {
+            anyhow::bail!(
+                "proof message.data ({} bytes) is not this bundle's message ({} bytes); the proof was fetched for another message",
+                data.len(),
+                message.len()
+            );
+        }
+        if let Some(hash) = self.l2l1_msg_hash.as_deref() {
+            let expected = alloy_primitives::keccak256(message);
+            if parse_b256(hash)? != expected {
+                anyhow::bail!(
+                    "proof l2l1MsgHash {hash} is not this bundle's message hash {expected:#x}; the proof was fetched for another message (check --msg-index)"
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `value` is `0x` followed by exactly 64 hex digits.
+fn is_bytes32_hex(value: &str) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -125,6 +435,92 @@ pub struct InteropBundleView {
     pub bundle_attributes: BundleAttributesView,
 }
 
+/// Result of `token send` under `--output json`; also the `--state-file`
+/// record of the steps done so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSendResult {
+    pub source_chain_id: String,
+    pub destination_chain_id: String,
+    pub asset_id: String,
+    /// Source token; unset for the base token (`--native`).
+    pub token: Option<String>,
+    pub recipient: String,
+    /// Amount in the token's smallest unit.
+    pub amount: String,
+    pub mode: String,
+    pub dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approve_tx_hash: Option<String>,
+    pub source_tx_hash: Option<String>,
+    pub bundle_hash: String,
+    pub l1_batch_number: Option<u64>,
+    pub l2_message_index: Option<u64>,
+    pub handler_tx_hash: Option<String>,
+    /// Handler bundle status, reported after `--mode verify`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_status: Option<String>,
+    /// Token the recipient holds on the destination (zero address for the base token).
+    pub wrapped_token: Option<String>,
+    pub balance_before: Option<String>,
+    pub balance_after: Option<String>,
+    pub expected_delta: Option<String>,
+}
+
+/// Result of `token send-batch` under `--output json`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSendBatchResult {
+    pub source_chain_id: String,
+    pub destination_chain_id: String,
+    pub asset_id: String,
+    pub token: String,
+    pub recipients: usize,
+    /// Sum of all recipient amounts in the token's smallest unit.
+    pub total_amount: String,
+    /// `msg.value` of the sendBundle transaction, in wei.
+    pub total_value: String,
+    pub dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approve_tx_hash: Option<String>,
+    pub source_tx_hash: Option<String>,
+    /// Unset when the dry-run of sendBundle failed.
+    pub bundle_hash: Option<String>,
+    /// sendBundle calldata, reported under `--dry-run`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calldata: Option<String>,
+    /// Why the sendBundle simulation failed, under `--dry-run`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run_error: Option<String>,
+}
+
+/// An unsigned transaction for `--calldata-only`, to be signed elsewhere.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawCall {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub to: String,
+    /// Value in wei.
+    pub value: String,
+    pub data: String,
+}
+
+impl RawCall {
+    pub fn new(label: Option<&str>, to: Address, value: U256, data: &[u8]) -> Self {
+        Self {
+            label: label.map(str::to_string),
+            to: address_to_hex(to),
+            value: u256_to_string(value),
+            data: format_hex(data),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BundleExtractOutput {
@@ -133,16 +529,78 @@ pub struct BundleExtractOutput {
     pub bundle: InteropBundleView,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleDiffOutput {
+    pub bundle_hash_a: String,
+    pub bundle_hash_b: String,
+    pub identical: bool,
+    pub differences: Vec<FieldDiff>,
+}
+
+/// One field that differs between two bundles. A side is unset when the
+/// field does not exist there, e.g. a call past the end of the shorter list.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiff {
+    pub field: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleHashOutput {
+    pub bundle_hash: String,
+    pub source_chain_id: String,
+    pub destination_chain_id: String,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TxShowOutput {
     pub tx_hash: String,
+    /// The first bundle sent; see `bundles` for all of them.
     pub bundle: Option<InteropBundleView>,
     pub bundle_hash: Option<String>,
     pub l2l1_msg_hash: Option<String>,
+    /// Every InteropBundleSent event, in log order; `index` is what
+    /// `--bundle-index` takes.
+    pub bundles: Vec<TxBundleView>,
     pub interop_events: Vec<EventView>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxBundleView {
+    pub index: usize,
+    pub bundle_hash: String,
+    pub l2l1_msg_hash: String,
+    pub bundle: InteropBundleView,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEventView {
+    pub block_number: Option<u64>,
+    pub tx_hash: Option<String>,
+    #[serde(flatten)]
+    pub event: EventView,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L2ToL1MessageView {
+    /// Index to pass as `--msg-index`.
+    pub index: u32,
+    pub sender: String,
+    pub l2l1_msg_hash: String,
+    /// `interopBundle` for bundles sent by the interop center, else `message`.
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_hash: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EventView {
@@ -157,6 +615,9 @@ pub struct StatusOutput {
     pub bundle_hash: String,
     pub bundle_status: String,
     pub calls: Option<Vec<CallStatusView>>,
+    /// `--block` the status was read at; unset for the latest block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -166,7 +627,7 @@ pub struct CallStatusView {
     pub status: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RelaySummary {
     pub source_chain_id: String,
@@ -176,6 +637,27 @@ pub struct RelaySummary {
     pub bundle_hash: String,
     pub source_tx_hash: String,
     pub handler_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_gas: Option<u64>,
+}
+
+/// Final `bundle relay --json` output.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayResult {
+    pub source_chain_id: String,
+    pub destination_chain_id: String,
+    pub source_tx_hash: String,
+    pub bundle_hash: String,
+    pub l1_batch_number: u64,
+    pub l2_message_index: u64,
+    pub mode: String,
+    pub dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run_error: Option<String>,
+    pub handler_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_gas: Option<u64>,
 }
 
 alloy_sol_types::sol! {